@@ -1,7 +1,12 @@
 use std::collections::HashMap;
 
+use char_animation::anim_key::AnimKey;
 use common::element::Element;
 use serde::{Deserialize, Serialize};
+use spell_data::{
+    AreaLimit, AttackCoverage, SpellCategory, SpellData, SpellHeal, SpellHitbox, SpellStatChange,
+    SpellStatus, TargetAlignments,
+};
 
 use super::common_data::RawTextData;
 
@@ -17,6 +22,80 @@ impl RawSpellData {
         let font_data: RawSpellData = serde_json::from_reader(spell_data)?;
         Ok(font_data)
     }
+
+    pub fn to_data(&self) -> SpellData {
+        let object = &self.object;
+        let data = &object.data;
+
+        let power = data
+            .skill_states
+            .iter()
+            .find_map(|state| state.power)
+            .map(|power| power.max(0) as u32);
+
+        let cast_visual_effect = object.hitbox_action.action_fx().anim_index().or_else(|| {
+            data.intro_fx
+                .iter()
+                .find_map(|intro_fx| intro_fx.anim_index())
+        });
+
+        // Chance of the additional effects, ie: the burn of Ember
+        let effect_chance = data
+            .skill_states
+            .iter()
+            .find_map(|state| state.effect_chance)
+            .map_or(100, |chance| chance.max(0) as u32);
+
+        let statuses = data
+            .on_hits
+            .iter()
+            .flat_map(|on_hit| on_hit.value.statuses(100, effect_chance))
+            .collect();
+
+        let map_status = data.on_hits.iter().find_map(|on_hit| match &on_hit.value {
+            BaseEvent::GiveMapStatus { status_id, .. } => {
+                serde_json::from_value(serde_json::Value::String(status_id.clone())).ok()
+            }
+            _ => None,
+        });
+
+        let stat_changes = data
+            .on_hits
+            .iter()
+            .flat_map(|on_hit| on_hit.value.stat_changes(100, effect_chance))
+            .collect();
+
+        let heal = data.on_hits.iter().find_map(|on_hit| on_hit.value.heal());
+
+        SpellData {
+            name: object.name.to_data(),
+            desc: object.desc.to_data(),
+            released: object.released,
+            comment: object.comment.to_owned(),
+            index_num: object.index_num,
+            base_charges: object.base_charges.max(0) as u32,
+            strikes: object.strikes.max(1) as u32,
+            element: data.element,
+            category: match data.category {
+                1 => SpellCategory::Physical,
+                2 => SpellCategory::Special,
+                3 => SpellCategory::Status,
+                _ => SpellCategory::None,
+            },
+            // A negative hit rate means the spell can't miss
+            hit_rate: (data.hit_rate >= 0).then_some(data.hit_rate as u32),
+            power,
+            target_alignments: object.hitbox_action.target_alignments(),
+            hitbox: object.hitbox_action.to_data(),
+            cast_animation: object.hitbox_action.cast_animation(),
+            cast_visual_effect,
+            hit_visual_effect: data.hit_fx.anim_index(),
+            statuses,
+            map_status,
+            heal,
+            stat_changes,
+        }
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -39,7 +118,7 @@ pub struct RawSpellObject {
 #[derive(Debug, Serialize, Deserialize)]
 #[serde(rename_all = "PascalCase")]
 pub struct RawData {
-    pub element: String,
+    pub element: Element,
     pub category: i32,
     pub hit_rate: i32,
     pub skill_states: Vec<SkillState>,
@@ -70,6 +149,41 @@ pub enum CharAnim {
     Process { process: i32, anim_override: i32 },
 }
 
+impl CharAnim {
+    pub fn to_anim_key(&self) -> AnimKey {
+        match self {
+            CharAnim::FrameType { action_type } => action_type_to_anim_key(*action_type),
+            CharAnim::Process { .. } => AnimKey::Attack,
+        }
+    }
+}
+
+/// Convert a PMDC character action index to the matching [`AnimKey`],
+/// actions without a dedicated animation fallback to [`AnimKey::Attack`]
+fn action_type_to_anim_key(action_type: i32) -> AnimKey {
+    match action_type {
+        1 => AnimKey::Idle,
+        2 => AnimKey::Walk,
+        3 => AnimKey::Sleep,
+        4 => AnimKey::Hurt,
+        6 => AnimKey::Charge,
+        7 => AnimKey::Shoot,
+        8 => AnimKey::Strike,
+        17 => AnimKey::Ricochet,
+        19 => AnimKey::Shake,
+        21 => AnimKey::Kick,
+        26 => AnimKey::Dance,
+        27 => AnimKey::Twirl,
+        28 => AnimKey::TailWhip,
+        36 => AnimKey::SpAttack,
+        37 => AnimKey::Withdraw,
+        40 => AnimKey::Swing,
+        41 => AnimKey::Double,
+        42 => AnimKey::Rotate,
+        _ => AnimKey::Attack,
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 #[serde(rename_all = "PascalCase")]
 pub struct Offset {
@@ -89,6 +203,15 @@ pub struct Anim {
     pub anim_flip: i32,
 }
 
+impl Anim {
+    pub fn anim_index(&self) -> Option<String> {
+        if self.anim_index.is_empty() {
+            return None;
+        }
+        Some(self.anim_index.to_owned())
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 #[serde(rename_all = "PascalCase")]
 pub struct ScreenMovement {
@@ -147,7 +270,7 @@ pub enum BaseEvent {
         states: Vec<EventState>,
         affect_target: bool,
         msg: EventMsg,
-        anims: Vec<Emitter>,
+        anims: Vec<BattleFx>,
     },
     #[serde(rename = "PMDC.Dungeon.StatusStackBattleEvent, PMDC")]
     #[serde(rename_all = "PascalCase")]
@@ -159,7 +282,7 @@ pub enum BaseEvent {
         silent_check: bool,
         anonymous: bool,
         trigger_msg: EventMsg,
-        anims: Vec<Emitter>,
+        anims: Vec<BattleFx>,
     },
     #[serde(rename = "PMDC.Dungeon.StealItemEvent, PMDC")]
     #[serde(rename_all = "PascalCase")]
@@ -180,7 +303,7 @@ pub enum BaseEvent {
         silent_check: bool,
         anonymous: bool,
         trigger_msg: EventMsg,
-        anims: Vec<Emitter>,
+        anims: Vec<BattleFx>,
     },
     #[serde(rename = "PMDC.Dungeon.StatusStateBattleEvent, PMDC")]
     #[serde(rename_all = "PascalCase")]
@@ -192,7 +315,7 @@ pub enum BaseEvent {
         silent_check: bool,
         anonymous: bool,
         trigger_msg: EventMsg,
-        anims: Vec<Emitter>,
+        anims: Vec<BattleFx>,
     },
     #[serde(rename = "PMDC.Dungeon.MirrorMoveEvent, PMDC")]
     #[serde(rename_all = "PascalCase")]
@@ -203,10 +326,17 @@ pub enum BaseEvent {
     #[serde(rename = "PMDC.Dungeon.WeatherHPEvent, PMDC")]
     #[serde(rename_all = "PascalCase")]
     WeatherHP {
-        weather_pair: WeatherPair,
+        weather_pair: HashMap<String, bool>,
         #[serde(rename = "HPDiv")]
         hp_div: i64,
     },
+    #[serde(rename = "PMDC.Dungeon.RestoreHPEvent, PMDC")]
+    #[serde(rename_all = "PascalCase")]
+    RestoreHP {
+        numerator: i64,
+        denominator: i64,
+        affect_target: bool,
+    },
     #[serde(rename = "PMDC.Dungeon.SetTrapEvent, PMDC")]
     #[serde(rename_all = "PascalCase")]
     SetTrap {
@@ -229,7 +359,7 @@ pub enum BaseEvent {
         silent_check: bool,
         anonymous: bool,
         trigger_msg: EventMsg,
-        anims: Vec<Emitter>,
+        anims: Vec<BattleFx>,
     },
     #[serde(rename = "PMDC.Dungeon.AddElementEvent, PMDC")]
     #[serde(rename_all = "PascalCase")]
@@ -252,6 +382,105 @@ pub enum BaseEvent {
         #[serde(rename = "StatusIDs")]
         status_ids: Vec<String>,
     },
+    /// Events that are not parsed yet
+    #[serde(other)]
+    Unknown,
+}
+
+impl BaseEvent {
+    /// Statuses given by the event and its sub events, `chance` is the one of the parent event
+    pub fn statuses(&self, chance: u32, effect_chance: u32) -> Vec<SpellStatus> {
+        match self {
+            BaseEvent::OnHit {
+                base_events,
+                chance: on_hit_chance,
+                ..
+            } => base_events
+                .iter()
+                .flat_map(|event| {
+                    event.statuses((*on_hit_chance).clamp(0, 100) as u32, effect_chance)
+                })
+                .collect(),
+            BaseEvent::Additional { base_events } => base_events
+                .iter()
+                .flat_map(|event| event.statuses(effect_chance, effect_chance))
+                .collect(),
+            BaseEvent::StatusBattle {
+                status_id,
+                affect_target,
+                ..
+            }
+            | BaseEvent::StatusStateBattle {
+                status_id,
+                affect_target,
+                ..
+            }
+            | BaseEvent::GiveContinuousDamage {
+                status_id,
+                affect_target,
+                ..
+            } => vec![SpellStatus {
+                status_id: status_id.clone(),
+                affect_target: *affect_target,
+                chance,
+            }],
+            _ => vec![],
+        }
+    }
+
+    /// Stat stages changed by the event and its sub events, `chance` is the one of the parent event
+    pub fn stat_changes(&self, chance: u32, effect_chance: u32) -> Vec<SpellStatChange> {
+        match self {
+            BaseEvent::OnHit {
+                base_events,
+                chance: on_hit_chance,
+                ..
+            } => base_events
+                .iter()
+                .flat_map(|event| {
+                    event.stat_changes((*on_hit_chance).clamp(0, 100) as u32, effect_chance)
+                })
+                .collect(),
+            BaseEvent::Additional { base_events } => base_events
+                .iter()
+                .flat_map(|event| event.stat_changes(effect_chance, effect_chance))
+                .collect(),
+            // The stat stages are stacked statuses, ie: `mod_attack`
+            BaseEvent::StatusStackBattle {
+                status_id,
+                stack,
+                affect_target,
+                ..
+            } if status_id.starts_with("mod_") => vec![SpellStatChange {
+                stat_id: status_id.clone(),
+                stages: *stack as i32,
+                affect_target: *affect_target,
+                chance,
+            }],
+            _ => vec![],
+        }
+    }
+
+    /// Health restored by the event, the weather bonus of `WeatherHP` is ignored
+    pub fn heal(&self) -> Option<SpellHeal> {
+        match self {
+            BaseEvent::RestoreHP {
+                numerator,
+                denominator,
+                affect_target,
+            } if *numerator > 0 && *denominator > 0 => Some(SpellHeal {
+                numerator: *numerator as u32,
+                denominator: *denominator as u32,
+                affect_target: *affect_target,
+            }),
+            BaseEvent::WeatherHP { hp_div, .. } if *hp_div > 0 => Some(SpellHeal {
+                numerator: 1,
+                denominator: *hp_div as u32,
+                affect_target: false,
+            }),
+            _ => None,
+        }
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize, Eq, PartialEq, Hash, Clone, Copy)]
@@ -265,8 +494,12 @@ pub enum TerrainType {
 }
 
 #[derive(Debug, Serialize, Deserialize)]
-pub struct WeatherPair {
-    pub grassy_terrain: bool,
+#[serde(rename_all = "PascalCase")]
+pub struct BattleFx {
+    pub emitter: Emitter,
+    pub sound: String,
+    pub affect_target: bool,
+    pub delay: i32,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -295,6 +528,9 @@ pub enum EventState {
     CountDown { counter: i32 },
     #[serde(rename = "PMDC.Dungeon.BadStatusState")]
     BadStatus,
+    /// States that are not parsed yet
+    #[serde(other)]
+    Unknown,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -330,6 +566,12 @@ pub struct Fx {
     pub screen_movement: ScreenMovement,
 }
 
+impl Fx {
+    pub fn anim_index(&self) -> Option<String> {
+        self.emitter.anim_index()
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 #[serde(tag = "$type")]
 pub enum HitboxAction {
@@ -474,6 +716,146 @@ pub enum HitboxAction {
     },
 }
 
+impl HitboxAction {
+    pub fn to_data(&self) -> SpellHitbox {
+        match self {
+            HitboxAction::Projectile {
+                anim,
+                emitter,
+                stream_emitter,
+                range,
+                stop_at_hit,
+                stop_at_wall,
+                ..
+            } => SpellHitbox::Projectile {
+                range: *range as u32,
+                stop_at_hit: *stop_at_hit,
+                stop_at_wall: *stop_at_wall,
+                visual_effect: anim
+                    .anim_index()
+                    .or_else(|| stream_emitter.anim_index())
+                    .or_else(|| emitter.anim_index()),
+            },
+            HitboxAction::Dash {
+                range,
+                stop_at_hit,
+                stop_at_wall,
+                wide_angle,
+                ..
+            } => SpellHitbox::Dash {
+                range: *range as u32,
+                stop_at_hit: *stop_at_hit,
+                stop_at_wall: *stop_at_wall,
+                coverage: to_attack_coverage(*wide_angle as u32),
+            },
+            HitboxAction::Attack { wide_angle, .. } => SpellHitbox::Attack {
+                coverage: to_attack_coverage(*wide_angle),
+            },
+            HitboxAction::SelfAction { .. } => SpellHitbox::SelfTarget,
+            HitboxAction::Area {
+                range, hit_area, ..
+            } => SpellHitbox::Area {
+                range: *range as u32,
+                limit: match hit_area {
+                    1 => AreaLimit::Cone,
+                    2 => AreaLimit::Sides,
+                    _ => AreaLimit::Full,
+                },
+            },
+            HitboxAction::WaveMotion { range, wide, .. } => SpellHitbox::WaveMotion {
+                range: *range as u32,
+                wide: *wide,
+            },
+            HitboxAction::Throw {
+                range,
+                anim,
+                emitter,
+                ..
+            } => SpellHitbox::Throw {
+                range: *range as u32,
+                visual_effect: anim.anim_index().or_else(|| emitter.anim_index()),
+            },
+            HitboxAction::Offset {
+                range, hit_area, ..
+            } => SpellHitbox::Offset {
+                range: *range as u32,
+                hit_area: *hit_area as u32,
+            },
+        }
+    }
+
+    pub fn action_fx(&self) -> &Fx {
+        match self {
+            HitboxAction::Projectile { action_fx, .. }
+            | HitboxAction::Dash { action_fx, .. }
+            | HitboxAction::Attack { action_fx, .. }
+            | HitboxAction::SelfAction { action_fx, .. }
+            | HitboxAction::Area { action_fx, .. }
+            | HitboxAction::WaveMotion { action_fx, .. }
+            | HitboxAction::Throw { action_fx, .. }
+            | HitboxAction::Offset { action_fx, .. } => action_fx,
+        }
+    }
+
+    pub fn cast_animation(&self) -> AnimKey {
+        match self {
+            HitboxAction::Dash { char_anim, .. } => action_type_to_anim_key(*char_anim),
+            HitboxAction::Projectile { char_anim_data, .. }
+            | HitboxAction::Attack { char_anim_data, .. }
+            | HitboxAction::SelfAction { char_anim_data, .. }
+            | HitboxAction::Area { char_anim_data, .. }
+            | HitboxAction::WaveMotion { char_anim_data, .. }
+            | HitboxAction::Throw { char_anim_data, .. }
+            | HitboxAction::Offset { char_anim_data, .. } => char_anim_data.to_anim_key(),
+        }
+    }
+
+    pub fn target_alignments(&self) -> TargetAlignments {
+        let flags = match self {
+            HitboxAction::Projectile {
+                target_alignments, ..
+            }
+            | HitboxAction::Dash {
+                target_alignments, ..
+            }
+            | HitboxAction::Attack {
+                target_alignments, ..
+            }
+            | HitboxAction::SelfAction {
+                target_alignments, ..
+            }
+            | HitboxAction::WaveMotion {
+                target_alignments, ..
+            } => *target_alignments as i64,
+            HitboxAction::Area {
+                target_alignments, ..
+            }
+            | HitboxAction::Offset {
+                target_alignments, ..
+            } => *target_alignments as i64,
+            HitboxAction::Throw {
+                target_alignments, ..
+            } => *target_alignments,
+        };
+
+        // PMDC alignment flags: 1 = self, 2 = friend, 4 = foe
+        TargetAlignments {
+            caster: flags & 1 != 0,
+            friend: flags & 2 != 0,
+            foe: flags & 4 != 0,
+        }
+    }
+}
+
+fn to_attack_coverage(wide_angle: u32) -> AttackCoverage {
+    match wide_angle {
+        1 => AttackCoverage::FrontAndCorners,
+        2 => AttackCoverage::Wide,
+        3 => AttackCoverage::Around,
+        _ => AttackCoverage::Front,
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 #[serde(tag = "$type")]
 pub enum Emitter {
@@ -714,4 +1096,378 @@ pub enum Emitter {
         start_distance: i64,
         layer: i64,
     },
+    /// Emitters that are not parsed yet
+    #[serde(other)]
+    Unknown,
+}
+
+impl Emitter {
+    /// Returns the first visual effect used by the emitter
+    pub fn anim_index(&self) -> Option<String> {
+        match self {
+            Emitter::Single { anim, .. } | Emitter::Repeat { anim, .. } => anim.anim_index(),
+            Emitter::Emitting {
+                anim, result_anim, ..
+            } => anim.anim_index().or_else(|| result_anim.anim_index()),
+            Emitter::MoveTo {
+                anim, result_anim, ..
+            } => anim.anim_index().or_else(|| result_anim.anim_index()),
+            Emitter::FiniteOverlay { anim, .. }
+            | Emitter::SwingSwitch { anim, .. }
+            | Emitter::Static { anim, .. }
+            | Emitter::Particle { anim, .. } => anim.anim_index(),
+            Emitter::Between {
+                anim_front,
+                anim_back,
+                ..
+            } => anim_front.anim_index().or_else(|| anim_back.anim_index()),
+            Emitter::Stream { anims, .. }
+            | Emitter::AttachRelease { anims, .. }
+            | Emitter::CircleSquareSprinkle { anims, .. }
+            | Emitter::FiniteRelease { anims, .. }
+            | Emitter::CircleSquareRelease { anims, .. }
+            | Emitter::CircleArea { anims, .. }
+            | Emitter::CircleSquareFountain { anims, .. }
+            | Emitter::FiniteArea { anims, .. }
+            | Emitter::StaticArea { anims, .. }
+            | Emitter::FiniteReleaseRange { anims, .. }
+            | Emitter::MultiCircleSquare {
+                emitters: anims, ..
+            } => anims.iter().find_map(|anim| anim.anim_index()),
+            Emitter::EmptyAttach
+            | Emitter::EmptyFinite { .. }
+            | Emitter::EmptyCircleSquare { .. }
+            | Emitter::EmptyShooting { .. }
+            | Emitter::Unknown => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::{json, Value};
+
+    use super::*;
+
+    fn empty_emitter() -> Value {
+        json!({ "$type": "RogueEssence.Content.EmptyAttachEmitter, RogueEssence" })
+    }
+
+    fn fx() -> Value {
+        json!({
+            "Delay": 0,
+            "Sound": "",
+            "Emitter": empty_emitter(),
+            "ScreenMovement": { "MinShake": 0, "MaxShake": 0, "MaxShakeTime": 0 }
+        })
+    }
+
+    fn char_anim() -> Value {
+        json!({ "$type": "RogueEssence.Dungeon.CharAnimFrameType, RogueEssence", "ActionType": 0 })
+    }
+
+    fn attack_hitbox(wide_angle: u32) -> Value {
+        json!({
+            "$type": "RogueEssence.Dungeon.AttackAction, RogueEssence",
+            "HitTiles": false,
+            "BurstTiles": 0,
+            "Emitter": empty_emitter(),
+            "WideAngle": wide_angle,
+            "CharAnimData": char_anim(),
+            "TargetAlignments": 4,
+            "TileEmitter": empty_emitter(),
+            "PreActions": [],
+            "ActionFX": fx(),
+            "LagBehindTime": 0
+        })
+    }
+
+    /// Raw PMDC spell with the given category, hit rate, strikes and hitbox
+    fn raw_spell(category: i32, hit_rate: i32, strikes: i32, hitbox: Value) -> Value {
+        let text = |text: &str| json!({ "DefaultText": text, "LocalTexts": {} });
+
+        json!({
+            "Version": "0.8.0",
+            "Object": {
+                "$type": "RogueEssence.Data.SkillData, RogueEssence",
+                "Name": text("Tackle"),
+                "Desc": text(""),
+                "Released": true,
+                "Comment": "",
+                "IndexNum": 33,
+                "BaseCharges": 35,
+                "Strikes": strikes,
+                "HitboxAction": hitbox,
+                "Explosion": {
+                    "TargetAlignments": 4,
+                    "HitTiles": false,
+                    "Range": 0,
+                    "Speed": 0,
+                    "TileEmitter": empty_emitter(),
+                    "Emitter": empty_emitter(),
+                    "IntroFX": [],
+                    "ExplodeFX": fx()
+                },
+                "Data": {
+                    "Element": "normal",
+                    "Category": category,
+                    "HitRate": hit_rate,
+                    "SkillStates": [{ "$type": "PMDC.Dungeon.BasePowerState, PMDC", "Power": 40 }],
+                    "BeforeTryActions": [],
+                    "BeforeActions": [],
+                    "OnActions": [],
+                    "BeforeExplosions": [],
+                    "BeforeHits": [],
+                    "OnHits": [],
+                    "OnHitTiles": [],
+                    "AfterActions": [],
+                    "ElementEffects": [],
+                    "IntroFX": [],
+                    "HitFX": fx(),
+                    "HitCharAction": char_anim()
+                }
+            }
+        })
+    }
+
+    fn to_data(raw_spell: Value) -> SpellData {
+        let buffer = serde_json::to_vec(&raw_spell).unwrap();
+        RawSpellData::parse_from_json(&buffer).unwrap().to_data()
+    }
+
+    #[test]
+    fn converts_the_raw_spell() {
+        let data = to_data(raw_spell(1, 100, 1, attack_hitbox(0)));
+
+        assert_eq!(data.name.default_text, "Tackle");
+        assert_eq!(data.base_charges, 35);
+        assert_eq!(data.power, Some(40));
+        assert_eq!(data.hit_rate, Some(100));
+        assert_eq!(data.category, SpellCategory::Physical);
+        assert!(matches!(
+            data.hitbox,
+            SpellHitbox::Attack {
+                coverage: AttackCoverage::Front
+            }
+        ));
+        assert!(data.target_alignments.foe && !data.target_alignments.friend);
+    }
+
+    #[test]
+    fn never_misses_with_a_negative_hit_rate() {
+        assert_eq!(
+            to_data(raw_spell(1, -1, 1, attack_hitbox(0))).hit_rate,
+            None
+        );
+    }
+
+    #[test]
+    fn maps_the_categories() {
+        let category = |index| to_data(raw_spell(index, 100, 1, attack_hitbox(0))).category;
+
+        assert_eq!(category(0), SpellCategory::None);
+        assert_eq!(category(1), SpellCategory::Physical);
+        assert_eq!(category(2), SpellCategory::Special);
+        assert_eq!(category(3), SpellCategory::Status);
+        assert_eq!(category(7), SpellCategory::None);
+    }
+
+    #[test]
+    fn hits_at_least_once() {
+        assert_eq!(to_data(raw_spell(1, 100, 0, attack_hitbox(0))).strikes, 1);
+        assert_eq!(to_data(raw_spell(1, 100, 2, attack_hitbox(0))).strikes, 2);
+    }
+
+    #[test]
+    fn maps_the_attack_coverages() {
+        let coverage =
+            |wide_angle| match to_data(raw_spell(1, 100, 1, attack_hitbox(wide_angle))).hitbox {
+                SpellHitbox::Attack { coverage } => coverage,
+                hitbox => panic!("unexpected hitbox {hitbox:?}"),
+            };
+
+        assert_eq!(coverage(0), AttackCoverage::Front);
+        assert_eq!(coverage(1), AttackCoverage::FrontAndCorners);
+        assert_eq!(coverage(2), AttackCoverage::Wide);
+        assert_eq!(coverage(3), AttackCoverage::Around);
+    }
+
+    #[test]
+    fn maps_the_area_limits() {
+        let limit = |hit_area| {
+            let hitbox = json!({
+                "$type": "RogueEssence.Dungeon.AreaAction, RogueEssence",
+                "Emitter": empty_emitter(),
+                "HitTiles": false,
+                "BurstTiles": 0,
+                "HitArea": hit_area,
+                "Range": 2,
+                "Speed": 10,
+                "CharAnimData": char_anim(),
+                "TargetAlignments": 4,
+                "TileEmitter": empty_emitter(),
+                "PreActions": [],
+                "ActionFX": fx(),
+                "LagBehindTime": 0
+            });
+            match to_data(raw_spell(2, 100, 1, hitbox)).hitbox {
+                SpellHitbox::Area { range: 2, limit } => limit,
+                hitbox => panic!("unexpected hitbox {hitbox:?}"),
+            }
+        };
+
+        assert_eq!(limit(0), AreaLimit::Full);
+        assert_eq!(limit(1), AreaLimit::Cone);
+        assert_eq!(limit(2), AreaLimit::Sides);
+    }
+
+    fn event(event: Value) -> BaseEvent {
+        serde_json::from_value(event).unwrap()
+    }
+
+    /// Status battle event of the given PMDC type, ie: `StatusBattleEvent`
+    fn status_event(event_type: &str, status_id: &str, affect_target: bool) -> Value {
+        json!({
+            "$type": format!("PMDC.Dungeon.{event_type}, PMDC"),
+            "StatusID": status_id,
+            "AffectTarget": affect_target,
+            "SilentCheck": false,
+            "Anonymous": false,
+            "TriggerMsg": { "Key": null },
+            "Anims": []
+        })
+    }
+
+    fn on_hit(chance: i64, base_events: Vec<Value>) -> Value {
+        json!({
+            "$type": "PMDC.Dungeon.OnHitEvent, PMDC",
+            "BaseEvents": base_events,
+            "RequireDamage": true,
+            "RequireContact": false,
+            "Chance": chance
+        })
+    }
+
+    fn additional(base_events: Vec<Value>) -> Value {
+        json!({ "$type": "PMDC.Dungeon.AdditionalEvent, PMDC", "BaseEvents": base_events })
+    }
+
+    fn status(status_id: &str, affect_target: bool, chance: u32) -> SpellStatus {
+        SpellStatus {
+            status_id: status_id.to_string(),
+            affect_target,
+            chance,
+        }
+    }
+
+    #[test]
+    fn maps_the_status_events() {
+        let sleep = event(status_event("StatusBattleEvent", "sleep", true));
+        let poison = event(status_event("GiveContinuousDamageEvent", "poison", true));
+        let confuse = event(status_event("StatusBattleEvent", "confuse", false));
+
+        assert_eq!(sleep.statuses(100, 30), vec![status("sleep", true, 100)]);
+        assert_eq!(poison.statuses(100, 30), vec![status("poison", true, 100)]);
+        assert_eq!(confuse.statuses(50, 30), vec![status("confuse", false, 50)]);
+    }
+
+    #[test]
+    fn propagates_the_chance_of_the_parent_events() {
+        let burn = || status_event("StatusBattleEvent", "burn", true);
+
+        assert_eq!(
+            event(on_hit(30, vec![burn()])).statuses(100, 10),
+            vec![status("burn", true, 30)]
+        );
+        // The additional events use the effect chance of the spell
+        assert_eq!(
+            event(on_hit(100, vec![additional(vec![burn()])])).statuses(100, 10),
+            vec![status("burn", true, 10)]
+        );
+        // The chance of the on hit events is clamped to a percentage
+        assert_eq!(
+            event(on_hit(150, vec![burn()])).statuses(100, 10),
+            vec![status("burn", true, 100)]
+        );
+    }
+
+    #[test]
+    fn ignores_the_unknown_events() {
+        let unknown = json!({ "$type": "PMDC.Dungeon.FlinchEvent, PMDC", "Flinch": true });
+
+        assert!(matches!(event(unknown.clone()), BaseEvent::Unknown));
+        assert!(event(unknown.clone()).statuses(100, 100).is_empty());
+        assert_eq!(
+            event(on_hit(
+                100,
+                vec![unknown, status_event("StatusBattleEvent", "paralyze", true)]
+            ))
+            .statuses(100, 100),
+            vec![status("paralyze", true, 100)]
+        );
+    }
+
+    fn stack_event(status_id: &str, stack: i64, affect_target: bool) -> Value {
+        json!({
+            "$type": "PMDC.Dungeon.StatusStackBattleEvent, PMDC",
+            "Stack": stack,
+            "StatusID": status_id,
+            "AffectTarget": affect_target,
+            "SilentCheck": false,
+            "Anonymous": false,
+            "TriggerMsg": { "Key": null },
+            "Anims": []
+        })
+    }
+
+    fn stat_change(
+        stat_id: &str,
+        stages: i32,
+        affect_target: bool,
+        chance: u32,
+    ) -> SpellStatChange {
+        SpellStatChange {
+            stat_id: stat_id.to_string(),
+            stages,
+            affect_target,
+            chance,
+        }
+    }
+
+    #[test]
+    fn maps_the_stacks_to_stat_stages() {
+        // Swords Dance, Growl and the speed drop of Icy Wind
+        let raise = event(stack_event("mod_attack", 2, false));
+        let lower = event(stack_event("mod_attack", -1, true));
+        let icy_wind = event(on_hit(
+            100,
+            vec![additional(vec![stack_event("mod_speed", -1, true)])],
+        ));
+
+        assert_eq!(
+            raise.stat_changes(100, 100),
+            vec![stat_change("mod_attack", 2, false, 100)]
+        );
+        assert_eq!(
+            lower.stat_changes(100, 100),
+            vec![stat_change("mod_attack", -1, true, 100)]
+        );
+        assert_eq!(
+            icy_wind.stat_changes(100, 30),
+            vec![stat_change("mod_speed", -1, true, 30)]
+        );
+    }
+
+    #[test]
+    fn ignores_the_stacks_of_other_statuses() {
+        // Stockpile is stacked without being a stat stage
+        let stockpile = event(stack_event("stockpile", 1, false));
+
+        assert!(stockpile.stat_changes(100, 100).is_empty());
+        // The stat ids are checked in game, the unknown ones are skipped there
+        assert_eq!(
+            event(stack_event("mod_unknown", 1, true)).stat_changes(100, 100),
+            vec![stat_change("mod_unknown", 1, true, 100)]
+        );
+    }
 }
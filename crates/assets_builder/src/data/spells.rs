@@ -16,7 +16,6 @@ pub fn create_spell_data(source_directory: &Path, output_directory: &str) {
         .iter()
         .map(|file| {
             let path = Path::new(file);
-            println!("{file}");
 
             // Read the file to a Vec<u8>
             let mut data = fs::read(path).unwrap();
@@ -30,17 +29,15 @@ pub fn create_spell_data(source_directory: &Path, output_directory: &str) {
             (path, data)
         })
         .map(|(path, content)| (path, RawSpellData::parse_from_json(&content).unwrap()))
-        // .map(|(path, data)| (path, data.to_data()))
+        .map(|(path, data)| (path, data.to_data()))
         .collect::<HashMap<_, _>>();
 
-    println!("{:?}", spell_data);
+    for (path, data) in spell_data {
+        let file_stem = path.file_stem().unwrap().to_str().unwrap();
+        let file_name = format!("{file_stem}.sd.ron");
 
-    // for (path, data) in spell_data {
-    //     let file_stem = path.file_stem().unwrap().to_str().unwrap();
-    //     let file_name = format!("{file_stem}.sd.ron");
-
-    //     let output_file = output_directory.join(file_name);
-    //     let mut output_file = File::create(output_file).unwrap();
-    //     let _ = data.save(&mut output_file);
-    // }
+        let output_file = output_directory.join(file_name);
+        let mut output_file = File::create(output_file).unwrap();
+        let _ = data.save(&mut output_file);
+    }
 }
@@ -1,7 +1,13 @@
 use serde::{Deserialize, Serialize};
+use strum::EnumString;
 
-#[derive(Debug, Serialize, Deserialize, Eq, PartialEq, Hash, Clone, Copy)]
+const SUPER_EFFECTIVE_MULTIPLIER: f32 = 2.;
+const NOT_VERY_EFFECTIVE_MULTIPLIER: f32 = 0.5;
+
+#[derive(Debug, Serialize, Deserialize, EnumString, Eq, PartialEq, Hash, Clone, Copy)]
+#[strum(serialize_all = "lowercase")]
 pub enum Element {
+    #[serde(rename = "none")]
     None,
     #[serde(rename = "bug")]
     Bug,
@@ -40,3 +46,163 @@ pub enum Element {
     #[serde(rename = "water")]
     Water,
 }
+
+impl Element {
+    /// Damage multiplier of an attack of this element against a single defending element
+    pub fn multiplier_against(&self, defender: Element) -> f32 {
+        let (super_effective, not_very_effective, immune) = self.chart();
+
+        if immune.contains(&defender) {
+            0.
+        } else if super_effective.contains(&defender) {
+            SUPER_EFFECTIVE_MULTIPLIER
+        } else if not_very_effective.contains(&defender) {
+            NOT_VERY_EFFECTIVE_MULTIPLIER
+        } else {
+            1.
+        }
+    }
+
+    /// Combined damage multiplier against all the elements of the defender
+    pub fn multiplier_against_all(&self, defenders: &[Element]) -> f32 {
+        defenders
+            .iter()
+            .filter(|defender| **defender != Element::None)
+            .fold(1., |multiplier, defender| {
+                multiplier * self.multiplier_against(*defender)
+            })
+    }
+
+    /// Returns the elements this element is (super effective against, not very effective against, no effect on)
+    fn chart(&self) -> (&'static [Element], &'static [Element], &'static [Element]) {
+        use Element::*;
+
+        match self {
+            None => (&[], &[], &[]),
+            Normal => (&[], &[Rock, Steel], &[Ghost]),
+            Fire => (&[Grass, Ice, Bug, Steel], &[Fire, Water, Rock, Dragon], &[]),
+            Water => (&[Fire, Ground, Rock], &[Water, Grass, Dragon], &[]),
+            Electric => (&[Water, Flying], &[Electric, Grass, Dragon], &[Ground]),
+            Grass => (
+                &[Water, Ground, Rock],
+                &[Fire, Grass, Poison, Flying, Bug, Dragon, Steel],
+                &[],
+            ),
+            Ice => (
+                &[Grass, Ground, Flying, Dragon],
+                &[Fire, Water, Ice, Steel],
+                &[],
+            ),
+            Fighting => (
+                &[Normal, Ice, Rock, Dark, Steel],
+                &[Poison, Flying, Psychic, Bug, Fairy],
+                &[Ghost],
+            ),
+            Poison => (&[Grass, Fairy], &[Poison, Ground, Rock, Ghost], &[Steel]),
+            Ground => (
+                &[Fire, Electric, Poison, Rock, Steel],
+                &[Grass, Bug],
+                &[Flying],
+            ),
+            Flying => (&[Grass, Fighting, Bug], &[Electric, Rock, Steel], &[]),
+            Psychic => (&[Fighting, Poison], &[Psychic, Steel], &[Dark]),
+            Bug => (
+                &[Grass, Psychic, Dark],
+                &[Fire, Fighting, Poison, Flying, Ghost, Steel, Fairy],
+                &[],
+            ),
+            Rock => (&[Fire, Ice, Flying, Bug], &[Fighting, Ground, Steel], &[]),
+            Ghost => (&[Psychic, Ghost], &[Dark], &[Normal]),
+            Dragon => (&[Dragon], &[Steel], &[Fairy]),
+            Dark => (&[Psychic, Ghost], &[Fighting, Dark, Fairy], &[]),
+            Steel => (&[Ice, Rock, Fairy], &[Fire, Water, Electric, Steel], &[]),
+            Fairy => (&[Fighting, Dragon, Dark], &[Fire, Poison, Steel], &[]),
+        }
+    }
+}
+
+/// How effective an attack is, used for the battle logs
+#[derive(Debug, Default, PartialEq, Eq, Clone, Copy)]
+pub enum Effectiveness {
+    Immune,
+    NotVeryEffective,
+    #[default]
+    Neutral,
+    SuperEffective,
+}
+
+impl Effectiveness {
+    pub fn from_multiplier(multiplier: f32) -> Self {
+        if multiplier == 0. {
+            Effectiveness::Immune
+        } else if multiplier < 1. {
+            Effectiveness::NotVeryEffective
+        } else if multiplier > 1. {
+            Effectiveness::SuperEffective
+        } else {
+            Effectiveness::Neutral
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use Element::*;
+
+    #[test]
+    fn multiplies_against_a_single_element() {
+        let cases = [
+            (Fire, Grass, 2.),
+            (Water, Fire, 2.),
+            (Fire, Water, 0.5),
+            (Electric, Grass, 0.5),
+            (Normal, Ghost, 0.),
+            (Electric, Ground, 0.),
+            (Normal, Normal, 1.),
+        ];
+
+        for (attacker, defender, expected) in cases {
+            assert_eq!(
+                attacker.multiplier_against(defender),
+                expected,
+                "{attacker:?} against {defender:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn stacks_against_dual_elements() {
+        let cases = [
+            (Ice, [Grass, Ground], 4.),
+            (Fire, [Water, Rock], 0.25),
+            (Ground, [Fire, Flying], 0.),
+            (Fire, [Grass, Water], 1.),
+            (Fire, [Grass, None], 2.),
+        ];
+
+        for (attacker, defenders, expected) in cases {
+            assert_eq!(
+                attacker.multiplier_against_all(&defenders),
+                expected,
+                "{attacker:?} against {defenders:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn maps_the_multiplier_to_an_effectiveness() {
+        let cases = [
+            (0., Effectiveness::Immune),
+            (0.25, Effectiveness::NotVeryEffective),
+            (0.5, Effectiveness::NotVeryEffective),
+            (1., Effectiveness::Neutral),
+            (2., Effectiveness::SuperEffective),
+            (4., Effectiveness::SuperEffective),
+        ];
+
+        for (multiplier, expected) in cases {
+            assert_eq!(Effectiveness::from_multiplier(multiplier), expected);
+        }
+    }
+}
@@ -1,2 +1,3 @@
 pub mod element;
 pub mod map_status;
+pub mod text_data;
@@ -1,6 +1,6 @@
 use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Serialize, Deserialize, Copy, Clone)]
+#[derive(Debug, Serialize, Deserialize, Copy, Clone, PartialEq, Eq, Hash)]
 pub enum MapStatus {
     #[serde(rename = "clear")]
     Clear,
@@ -10,6 +10,7 @@ pub enum MapStatus {
     Sunny,
     #[serde(rename = "sandstorm")]
     Sandstorm,
+    #[serde(rename = "hail")]
     Hail,
     Fog,
     Cloudy,
@@ -0,0 +1,20 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TextData {
+    pub default_text: String,
+    pub local_texts: LocalTexts,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LocalTexts {
+    pub ja: Option<String>,
+    pub ko: Option<String>,
+    pub zh_hant: Option<String>,
+    pub fr: Option<String>,
+    pub de: Option<String>,
+    pub es: Option<String>,
+    pub it: Option<String>,
+    pub ja_jp: Option<String>,
+    pub zh_hans: Option<String>,
+}
@@ -0,0 +1,32 @@
+use bevy::{asset::Asset, reflect::TypePath};
+use common::text_data::TextData;
+use serde::{Deserialize, Serialize};
+
+#[derive(TypePath, Asset, Debug, Clone, Serialize, Deserialize)]
+pub struct ItemData {
+    pub name: TextData,
+    pub desc: TextData,
+    /// Visual effect drawn on the tile while the item lies on the ground
+    pub sprite: String,
+    pub effect: ItemEffect,
+}
+
+impl ItemData {
+    pub fn load(buffer: &[u8]) -> Result<Self, ron::Error> {
+        let item_data = ron::de::from_bytes(buffer)?;
+        Ok(item_data)
+    }
+}
+
+/// Effect of the item when used or thrown at a pokemon
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ItemEffect {
+    /// Restore the health, ie: Oran Berry
+    Heal { value: i32 },
+    /// Fill the belly, ie: Apple
+    Food { belly: u32 },
+    /// Cure all the status conditions, ie: Heal Seed
+    CureStatus,
+    /// Restore the PP of all the moves, ie: Elixir
+    RestorePP { value: u32 },
+}
@@ -0,0 +1,56 @@
+use bevy::{
+    asset::{io::Reader, AssetLoader, AsyncReadExt, LoadContext},
+    prelude::*,
+    utils::BoxedFuture,
+};
+use thiserror::Error;
+
+pub mod data;
+
+pub use data::*;
+
+pub struct ItemDataPlugin;
+
+impl Plugin for ItemDataPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_asset::<ItemData>()
+            .init_asset_loader::<ItemDataLoader>();
+    }
+}
+
+#[derive(Default)]
+pub struct ItemDataLoader;
+
+#[non_exhaustive]
+#[derive(Debug, Error)]
+pub enum ItemDataLoaderError {
+    /// An [IO](std::io) Error
+    #[error("Could not load asset: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("Could not parse the asset {0}")]
+    DecodeError(#[from] ron::Error),
+}
+
+impl AssetLoader for ItemDataLoader {
+    type Asset = ItemData;
+    type Settings = ();
+    type Error = ItemDataLoaderError;
+
+    fn load<'a>(
+        &'a self,
+        reader: &'a mut Reader,
+        _settings: &'a (),
+        _load_context: &'a mut LoadContext,
+    ) -> BoxedFuture<'a, Result<Self::Asset, Self::Error>> {
+        Box::pin(async move {
+            let mut bytes = Vec::new();
+            reader.read_to_end(&mut bytes).await?;
+            let item_data = ItemData::load(&bytes)?;
+            Ok(item_data)
+        })
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["id.ron"]
+    }
+}
@@ -4,6 +4,7 @@ use std::{
 };
 
 use bevy::{asset::Asset, reflect::TypePath};
+pub use common::text_data::{LocalTexts, TextData};
 use common::{element::Element, map_status::MapStatus};
 use serde::{Deserialize, Serialize};
 
@@ -68,25 +69,6 @@ pub struct PokemonForm {
     pub level_skills: Vec<PokemonLevelSkill>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
-pub struct TextData {
-    pub default_text: String,
-    pub local_texts: LocalTexts,
-}
-
-#[derive(Debug, Serialize, Deserialize)]
-pub struct LocalTexts {
-    pub ja: Option<String>,
-    pub ko: Option<String>,
-    pub zh_hant: Option<String>,
-    pub fr: Option<String>,
-    pub de: Option<String>,
-    pub es: Option<String>,
-    pub it: Option<String>,
-    pub ja_jp: Option<String>,
-    pub zh_hans: Option<String>,
-}
-
 #[derive(Debug, Serialize, Deserialize)]
 pub struct PokemonLevelSkill {
     pub level: i32,
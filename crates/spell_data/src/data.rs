@@ -0,0 +1,154 @@
+use std::{
+    fs::File,
+    io::{self, Write},
+};
+
+use bevy::{asset::Asset, reflect::TypePath};
+use char_animation::anim_key::AnimKey;
+use common::{element::Element, map_status::MapStatus, text_data::TextData};
+use serde::{Deserialize, Serialize};
+
+#[derive(TypePath, Asset, Debug, Clone, Serialize, Deserialize)]
+pub struct SpellData {
+    pub name: TextData,
+    pub desc: TextData,
+    pub released: bool,
+    pub comment: String,
+    pub index_num: u32,
+    /// Number of times the spell can be used (PP)
+    pub base_charges: u32,
+    /// Number of times the spell hits per use
+    pub strikes: u32,
+    pub element: Element,
+    pub category: SpellCategory,
+    /// Chance to hit in percent, `None` if the spell never misses
+    pub hit_rate: Option<u32>,
+    pub power: Option<u32>,
+    pub target_alignments: TargetAlignments,
+    pub hitbox: SpellHitbox,
+    pub cast_animation: AnimKey,
+    pub cast_visual_effect: Option<String>,
+    pub hit_visual_effect: Option<String>,
+    /// Statuses given when the spell hits
+    #[serde(default)]
+    pub statuses: Vec<SpellStatus>,
+    /// Map status started by the spell, ie: `Rain` for Rain Dance
+    #[serde(default)]
+    pub map_status: Option<MapStatus>,
+    /// Health restored when the spell hits, ie: Recover
+    #[serde(default)]
+    pub heal: Option<SpellHeal>,
+    /// Stat stages changed when the spell hits, ie: `mod_defense` for Tail Whip
+    #[serde(default)]
+    pub stat_changes: Vec<SpellStatChange>,
+}
+
+impl SpellData {
+    pub fn load(buffer: &[u8]) -> Result<Self, ron::Error> {
+        let spell_data = ron::de::from_bytes(buffer)?;
+        Ok(spell_data)
+    }
+
+    pub fn save(&self, file: &mut File) -> Result<(), io::Error> {
+        let buffer = ron::ser::to_string_pretty(self, ron::ser::PrettyConfig::default()).unwrap();
+        file.write_all(buffer.as_bytes())?;
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SpellCategory {
+    None,
+    Physical,
+    Special,
+    Status,
+}
+
+/// Status given by a spell, ie: `burn` for Ember
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SpellStatus {
+    pub status_id: String,
+    /// Given to the target, otherwise to the caster
+    pub affect_target: bool,
+    /// Chance to give the status in percent
+    pub chance: u32,
+}
+
+/// Stat stages raised or lowered by a spell
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SpellStatChange {
+    pub stat_id: String,
+    /// Positive to raise the stat, negative to lower it
+    pub stages: i32,
+    /// Given to the target, otherwise to the caster
+    pub affect_target: bool,
+    /// Chance to change the stat in percent
+    pub chance: u32,
+}
+
+/// Health restored by a spell, as a fraction of the max health
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SpellHeal {
+    pub numerator: u32,
+    pub denominator: u32,
+    /// Restores the target, otherwise the caster
+    pub affect_target: bool,
+}
+
+/// Which pieces can be affected by the spell, relative to the caster
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
+pub struct TargetAlignments {
+    pub caster: bool,
+    pub friend: bool,
+    pub foe: bool,
+}
+
+/// Tiles hit by a melee attack
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum AttackCoverage {
+    Front,
+    FrontAndCorners,
+    Wide,
+    Around,
+}
+
+/// Shape of an area spell
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum AreaLimit {
+    Full,
+    Cone,
+    Sides,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum SpellHitbox {
+    /// Hits the tiles next to the caster
+    Attack { coverage: AttackCoverage },
+    /// Travels in a straight line from the caster
+    Projectile {
+        range: u32,
+        stop_at_hit: bool,
+        stop_at_wall: bool,
+        visual_effect: Option<String>,
+    },
+    /// Moves the caster in a straight line, hitting what is on the way
+    Dash {
+        range: u32,
+        stop_at_hit: bool,
+        stop_at_wall: bool,
+        coverage: AttackCoverage,
+    },
+    /// Only affects the caster
+    SelfTarget,
+    /// Hits the tiles around the caster
+    Area { range: u32, limit: AreaLimit },
+    /// Beam hitting everything in a line
+    WaveMotion { range: u32, wide: bool },
+    /// Thrown on the first target in range
+    Throw {
+        range: u32,
+        visual_effect: Option<String>,
+    },
+    /// Hits an area at some distance in front of the caster
+    Offset { range: u32, hit_area: u32 },
+}
@@ -0,0 +1,56 @@
+use bevy::{
+    asset::{io::Reader, AssetLoader, AsyncReadExt, LoadContext},
+    prelude::*,
+    utils::BoxedFuture,
+};
+use thiserror::Error;
+
+pub mod data;
+
+pub use data::*;
+
+pub struct SpellDataPlugin;
+
+impl Plugin for SpellDataPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_asset::<SpellData>()
+            .init_asset_loader::<SpellDataLoader>();
+    }
+}
+
+#[derive(Default)]
+pub struct SpellDataLoader;
+
+#[non_exhaustive]
+#[derive(Debug, Error)]
+pub enum SpellDataLoaderError {
+    /// An [IO](std::io) Error
+    #[error("Could not load asset: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("Could not parse the asset {0}")]
+    DecodeError(#[from] ron::Error),
+}
+
+impl AssetLoader for SpellDataLoader {
+    type Asset = SpellData;
+    type Settings = ();
+    type Error = SpellDataLoaderError;
+
+    fn load<'a>(
+        &'a self,
+        reader: &'a mut Reader,
+        _settings: &'a (),
+        _load_context: &'a mut LoadContext,
+    ) -> BoxedFuture<'a, Result<Self::Asset, Self::Error>> {
+        Box::pin(async move {
+            let mut bytes = Vec::new();
+            reader.read_to_end(&mut bytes).await?;
+            let spell_data = SpellData::load(&bytes)?;
+            Ok(spell_data)
+        })
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["sd.ron"]
+    }
+}
@@ -3,7 +3,7 @@ use std::collections::VecDeque;
 use bevy::{prelude::*, utils::info};
 
 use crate::{
-    actions::{ActionExecutedEvent, RunningAction},
+    actions::{death_action::DeathAction, ActionExecutedEvent, RunningAction},
     stats::Health,
 };
 
@@ -45,7 +45,12 @@ pub fn process_action_queue(world: &mut World) {
         world.send_event(ProcessingActionEvent);
 
         if let Ok(health) = world.query::<&Health>().get(world, queued_action.entity) {
-            if health.is_dead() {
+            // The death of the entity itself is still handled
+            let is_death = queued_action
+                .performable_actions
+                .iter()
+                .any(|action| action.as_any().is::<DeathAction>());
+            if health.is_dead() && !is_death {
                 info!("{:?} is dead ", queued_action.entity);
                 continue;
             }
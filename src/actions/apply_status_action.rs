@@ -0,0 +1,67 @@
+use bevy::prelude::*;
+use rand::Rng;
+
+use crate::{
+    pokemons::Elements,
+    rng::GameRng,
+    stats::Health,
+    status::{ActiveStatus, StatusCondition, StatusConditions},
+};
+
+use super::Action;
+
+#[derive(Debug, Clone)]
+pub struct ApplyStatusAction {
+    pub source: Entity,
+    pub target: Entity,
+    pub condition: StatusCondition,
+}
+
+impl Action for ApplyStatusAction {
+    fn execute(&self, world: &mut World) -> Result<Vec<Box<dyn Action>>, ()> {
+        if !self.can_execute(world) {
+            return Err(());
+        };
+
+        let turns = world
+            .resource_mut::<GameRng>()
+            .0
+            .gen_range(self.condition.turns());
+        let mut status_conditions = world.get_mut::<StatusConditions>(self.target).ok_or(())?;
+        status_conditions.0.push(ActiveStatus {
+            condition: self.condition,
+            turns,
+            source: self.source,
+        });
+
+        Ok(Vec::new())
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    fn is_parallel_execution(&self) -> bool {
+        false
+    }
+
+    fn can_execute(&self, world: &mut World) -> bool {
+        if world
+            .get::<Health>(self.target)
+            .is_none_or(|health| health.is_dead())
+        {
+            return false;
+        }
+
+        if world
+            .get::<Elements>(self.target)
+            .is_some_and(|elements| self.condition.is_immune(elements))
+        {
+            return false;
+        }
+
+        world
+            .get::<StatusConditions>(self.target)
+            .is_some_and(|status_conditions| status_conditions.can_receive(self.condition))
+    }
+}
@@ -0,0 +1,40 @@
+use bevy::prelude::*;
+
+use crate::status::{StatusCondition, StatusConditions};
+
+use super::Action;
+
+#[derive(Debug, Clone)]
+pub struct CureStatusAction {
+    pub entity: Entity,
+    pub condition: StatusCondition,
+}
+
+impl Action for CureStatusAction {
+    fn execute(&self, world: &mut World) -> Result<Vec<Box<dyn Action>>, ()> {
+        if !self.can_execute(world) {
+            return Err(());
+        };
+
+        let mut status_conditions = world.get_mut::<StatusConditions>(self.entity).ok_or(())?;
+        status_conditions
+            .0
+            .retain(|status| status.condition != self.condition);
+
+        Ok(Vec::new())
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    fn is_parallel_execution(&self) -> bool {
+        true
+    }
+
+    fn can_execute(&self, world: &mut World) -> bool {
+        world
+            .get::<StatusConditions>(self.entity)
+            .is_some_and(|status_conditions| status_conditions.has(self.condition))
+    }
+}
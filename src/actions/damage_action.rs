@@ -1,18 +1,42 @@
 use bevy::prelude::*;
+use common::element::Effectiveness;
 
 use crate::{
+    evolution::BattleRecord,
     map::Position,
     pieces::PieceDeathEvent,
+    protection::{Protected, Substitute},
     stats::{Health, Stats},
 };
 
-use super::{death_action::DeathAction, orient_entity, Action};
+use super::{
+    death_action::DeathAction, orient_entity, protected_action::ProtectedAction,
+    substitute_hit_action::SubstituteHitAction, Action,
+};
 
 #[derive(Debug, Clone)]
 pub struct DamageAction {
     pub attacker: Entity,
     pub target: Entity,
     pub value: i32,
+    pub effectiveness: Effectiveness,
+    pub critical: bool,
+}
+
+impl DamageAction {
+    /// The damage as performable actions, stopped by Protect or taken by the substitute instead
+    pub fn or_blocked(self) -> Vec<Box<dyn Action>> {
+        let (attacker, target, value) = (self.attacker, self.target, self.value);
+        vec![
+            Box::new(self) as Box<dyn Action>,
+            Box::new(ProtectedAction { attacker, target }),
+            Box::new(SubstituteHitAction {
+                attacker,
+                target,
+                value,
+            }),
+        ]
+    }
 }
 
 impl Action for DamageAction {
@@ -26,9 +50,14 @@ impl Action for DamageAction {
         };
 
         health.value = health.value.saturating_sub(self.value);
+        let is_dead = health.is_dead();
+
+        if let Some(mut battle_record) = world.get_mut::<BattleRecord>(self.target) {
+            battle_record.damage_taken += self.value.max(0) as u32;
+        }
 
         let mut next_actions = vec![];
-        if health.is_dead() {
+        if is_dead {
             world.send_event(PieceDeathEvent {
                 entity: self.target,
             });
@@ -54,6 +83,8 @@ impl Action for DamageAction {
     }
 
     fn can_execute(&self, world: &mut World) -> bool {
-        return world.get::<Health>(self.target).is_some();
+        world.get::<Health>(self.target).is_some()
+            && world.get::<Protected>(self.target).is_none()
+            && world.get::<Substitute>(self.target).is_none()
     }
 }
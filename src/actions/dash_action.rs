@@ -0,0 +1,79 @@
+use bevy::prelude::*;
+use char_animation::anim_key::AnimKey;
+
+use crate::map::Position;
+
+use super::Action;
+
+/// Rush of the caster of a dash spell, in a straight line up to the first blocker
+#[derive(Debug, Clone)]
+pub struct DashAction {
+    pub entity: Entity,
+    pub from: IVec2,
+    pub to: IVec2,
+    /// Animation of the caster while rushing
+    pub animation: AnimKey,
+}
+
+impl Action for DashAction {
+    fn execute(&self, world: &mut World) -> Result<Vec<Box<dyn Action>>, ()> {
+        if !self.can_execute(world) {
+            return Err(());
+        };
+
+        let mut position = world.get_mut::<Position>(self.entity).ok_or(())?;
+        position.0 = self.to;
+
+        Ok(Vec::new())
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    fn is_parallel_execution(&self) -> bool {
+        false
+    }
+
+    fn can_execute(&self, world: &mut World) -> bool {
+        // The path is checked when the spell is casted
+        world
+            .get::<Position>(self.entity)
+            .is_some_and(|position| position.0 == self.from)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn dash_action(entity: Entity) -> DashAction {
+        DashAction {
+            entity,
+            from: IVec2::ZERO,
+            to: IVec2::new(3, 0),
+            animation: AnimKey::Walk,
+        }
+    }
+
+    #[test]
+    fn rushes_the_caster_to_the_end_of_the_path() {
+        let mut world = World::new();
+        let entity = world.spawn(Position(IVec2::ZERO)).id();
+
+        dash_action(entity).execute(&mut world).unwrap();
+
+        assert_eq!(world.get::<Position>(entity).unwrap().0, IVec2::new(3, 0));
+    }
+
+    #[test]
+    fn only_rushes_from_the_start_of_the_path() {
+        let mut world = World::new();
+        // The caster was moved since the spell was casted
+        let entity = world.spawn(Position(IVec2::ONE)).id();
+
+        assert!(!dash_action(entity).can_execute(&mut world));
+        assert!(dash_action(entity).execute(&mut world).is_err());
+        assert_eq!(world.get::<Position>(entity).unwrap().0, IVec2::ONE);
+    }
+}
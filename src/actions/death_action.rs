@@ -1,8 +1,15 @@
 use bevy::prelude::*;
+use pokemon_data::PokemonData;
 
-use crate::stats::{Health, Stats};
+use crate::{
+    evolution::BattleRecord,
+    experience::defeat_exp,
+    faction::Faction,
+    pokemons::Pokemon,
+    stats::{Experience, Health, Level},
+};
 
-use super::Action;
+use super::{gain_exp_action::GainExpAction, Action, ActionQueue, QueuedAction};
 
 #[derive(Debug, Clone)]
 pub struct DeathAction {
@@ -10,12 +17,67 @@ pub struct DeathAction {
     pub target: Entity,
 }
 
+impl DeathAction {
+    fn exp_yield(&self, world: &World) -> Option<u32> {
+        let pokemon = world.get::<Pokemon>(self.target)?;
+        let level = world.get::<Level>(self.target)?;
+        let pokemon_data_handle = world.get::<Handle<PokemonData>>(self.target)?;
+        let data = world
+            .resource::<Assets<PokemonData>>()
+            .get(pokemon_data_handle)?;
+
+        let exp_yield = data.forms.get(pokemon.form_index)?.exp_yield;
+        Some(defeat_exp(exp_yield, level.0))
+    }
+
+    /// The attacker and its living teammates share the experience
+    fn exp_receivers(&self, world: &mut World) -> Vec<Entity> {
+        let faction = match world.get::<Faction>(self.attacker) {
+            Some(Faction::None) | None => return vec![self.attacker],
+            Some(faction) => *faction,
+        };
+
+        world
+            .query_filtered::<(Entity, &Faction, &Health), With<Experience>>()
+            .iter(world)
+            .filter(|(entity, entity_faction, health)| {
+                *entity != self.target && **entity_faction == faction && !health.is_dead()
+            })
+            .map(|(entity, _, _)| entity)
+            .collect()
+    }
+}
+
 impl Action for DeathAction {
     fn execute(&self, world: &mut World) -> Result<Vec<Box<dyn Action>>, ()> {
         if !self.can_execute(world) {
             return Err(());
         };
 
+        // Defeated by the weather or the terrain, nobody gains experience
+        if self.attacker == self.target {
+            return Ok(Vec::new());
+        }
+
+        if let Some(mut battle_record) = world.get_mut::<BattleRecord>(self.attacker) {
+            battle_record.kill_count += 1;
+        }
+
+        let Some(value) = self.exp_yield(world) else {
+            warn!("Unable to compute the exp yield of {:?}", self.target);
+            return Ok(Vec::new());
+        };
+
+        // Each receiver gains its experience as its own action, right after the death
+        let receivers = self.exp_receivers(world);
+        let mut action_queue = world.resource_mut::<ActionQueue>();
+        for entity in receivers.into_iter().rev() {
+            action_queue.0.push_front(QueuedAction {
+                entity,
+                performable_actions: vec![Box::new(GainExpAction { entity, value })],
+            });
+        }
+
         Ok(Vec::new())
     }
 
@@ -0,0 +1,66 @@
+use bevy::prelude::*;
+
+use crate::{
+    inventory::{spawn_ground_item, GroundItem, Inventory, Item},
+    map::{GameMap, Position, TerrainType},
+};
+
+use super::Action;
+
+/// Put the item of the bag slot on the tile under the piece
+#[derive(Debug, Clone)]
+pub struct DropItemAction {
+    pub entity: Entity,
+    pub slot: usize,
+    pub item: Item,
+}
+
+impl Action for DropItemAction {
+    fn execute(&self, world: &mut World) -> Result<Vec<Box<dyn Action>>, ()> {
+        if !self.can_execute(world) {
+            return Err(());
+        };
+
+        let position = world.get::<Position>(self.entity).ok_or(())?.0;
+        let mut inventory = world.get_mut::<Inventory>(self.entity).ok_or(())?;
+        inventory.remove(self.slot).ok_or(())?;
+
+        spawn_ground_item(world, self.item.clone(), position);
+
+        Ok(Vec::new())
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    fn is_parallel_execution(&self) -> bool {
+        false
+    }
+
+    fn can_execute(&self, world: &mut World) -> bool {
+        if world
+            .get::<Inventory>(self.entity)
+            .and_then(|inventory| inventory.0.get(self.slot))
+            .is_none_or(|item| item.id != self.item.id)
+        {
+            return false;
+        }
+
+        let Some(position) = world.get::<Position>(self.entity).map(|p| p.0) else {
+            return false;
+        };
+
+        // Only one item per tile, and never on the stairs
+        let is_ground = world
+            .get_resource::<GameMap>()
+            .and_then(|map| map.tiles.get(&position))
+            .is_some_and(|tile| tile.r#type == TerrainType::Ground);
+
+        is_ground
+            && !world
+                .query_filtered::<&Position, With<GroundItem>>()
+                .iter(world)
+                .any(|p| p.0 == position)
+    }
+}
@@ -0,0 +1,65 @@
+use std::str::FromStr;
+
+use bevy::prelude::*;
+use pokemon_data::PokemonData;
+
+use crate::{
+    experience::{GrowthRate, MAX_LEVEL},
+    stats::{Experience, Level},
+};
+
+use super::{level_up_action::LevelUpAction, Action};
+
+#[derive(Debug, Clone)]
+pub struct GainExpAction {
+    pub entity: Entity,
+    pub value: u32,
+}
+
+impl Action for GainExpAction {
+    fn execute(&self, world: &mut World) -> Result<Vec<Box<dyn Action>>, ()> {
+        if !self.can_execute(world) {
+            return Err(());
+        };
+
+        let growth_rate = world
+            .get::<Handle<PokemonData>>(self.entity)
+            .and_then(|handle| world.resource::<Assets<PokemonData>>().get(handle))
+            .and_then(|data| GrowthRate::from_str(&data.exp_table).ok())
+            .unwrap_or_default();
+
+        let current_level = world.get::<Level>(self.entity).ok_or(())?.0;
+        let mut experience = world.get_mut::<Experience>(self.entity).ok_or(())?;
+
+        let mut level = current_level;
+        experience.0 += self.value;
+        while level < MAX_LEVEL && experience.0 >= growth_rate.exp_to_next_level(level) {
+            experience.0 -= growth_rate.exp_to_next_level(level);
+            level += 1;
+        }
+        if level >= MAX_LEVEL {
+            experience.0 = 0;
+        }
+
+        if level == current_level {
+            return Ok(Vec::new());
+        }
+
+        Ok(vec![Box::new(LevelUpAction {
+            entity: self.entity,
+            level,
+        })])
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    fn is_parallel_execution(&self) -> bool {
+        false
+    }
+
+    fn can_execute(&self, world: &mut World) -> bool {
+        world.get::<Experience>(self.entity).is_some() && world.get::<Level>(self.entity).is_some()
+    }
+}
@@ -0,0 +1,47 @@
+use bevy::prelude::*;
+
+use crate::stats::Health;
+
+use super::Action;
+
+#[derive(Debug, Clone)]
+pub struct HealAction {
+    pub entity: Entity,
+    pub value: i32,
+}
+
+/// Heal the entity by the given value, only the missing health is restored.
+/// Returns `None` when there is nothing to restore.
+pub fn clamped_heal_action(world: &World, entity: Entity, value: i32) -> Option<HealAction> {
+    let value = world
+        .get::<Health>(entity)
+        .map_or(value, |health| value.min(health.max - health.value));
+    (value > 0).then_some(HealAction { entity, value })
+}
+
+impl Action for HealAction {
+    fn execute(&self, world: &mut World) -> Result<Vec<Box<dyn Action>>, ()> {
+        if !self.can_execute(world) {
+            return Err(());
+        };
+
+        let mut health = world.get_mut::<Health>(self.entity).ok_or(())?;
+        health.value = health.value.saturating_add(self.value).min(health.max);
+
+        Ok(Vec::new())
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    fn is_parallel_execution(&self) -> bool {
+        false
+    }
+
+    fn can_execute(&self, world: &mut World) -> bool {
+        world
+            .get::<Health>(self.entity)
+            .is_some_and(|health| !health.is_dead())
+    }
+}
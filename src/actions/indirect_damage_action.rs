@@ -0,0 +1,88 @@
+use bevy::prelude::*;
+use common::map_status::MapStatus;
+
+use crate::{
+    evolution::BattleRecord, map::EnvironmentType, pieces::PieceDeathEvent, stats::Health,
+    status::StatusCondition,
+};
+
+use super::{death_action::DeathAction, Action};
+
+/// What dealt an indirect damage, used by the logs and the animations
+#[derive(Debug, Clone, Copy)]
+pub enum IndirectDamageCause {
+    /// Status at the end of the turn of the entity, ie: poison
+    Status {
+        condition: StatusCondition,
+        /// Piece which gave the status
+        source: Entity,
+    },
+    /// Weather at the end of the turn, ie: sandstorm
+    Weather(MapStatus),
+    /// Tile the piece stands on at the end of the turn, ie: lava
+    Terrain(EnvironmentType),
+    /// Empty belly at the end of the turn
+    Hunger,
+}
+
+/// Damage without an attacker hitting the entity
+#[derive(Debug, Clone)]
+pub struct IndirectDamageAction {
+    pub entity: Entity,
+    pub value: i32,
+    pub cause: IndirectDamageCause,
+}
+
+impl IndirectDamageAction {
+    /// The piece credited with the death, the source of a status when it still exists
+    fn attacker(&self, world: &World) -> Entity {
+        match self.cause {
+            IndirectDamageCause::Status { source, .. } if world.get_entity(source).is_some() => {
+                source
+            }
+            _ => self.entity,
+        }
+    }
+}
+
+impl Action for IndirectDamageAction {
+    fn execute(&self, world: &mut World) -> Result<Vec<Box<dyn Action>>, ()> {
+        if !self.can_execute(world) {
+            return Err(());
+        };
+
+        let mut health = world.get_mut::<Health>(self.entity).ok_or(())?;
+        health.value = health.value.saturating_sub(self.value).max(0);
+        let is_dead = health.is_dead();
+
+        if let Some(mut battle_record) = world.get_mut::<BattleRecord>(self.entity) {
+            battle_record.damage_taken += self.value.max(0) as u32;
+        }
+
+        if !is_dead {
+            return Ok(Vec::new());
+        }
+
+        world.send_event(PieceDeathEvent {
+            entity: self.entity,
+        });
+        Ok(vec![Box::new(DeathAction {
+            attacker: self.attacker(world),
+            target: self.entity,
+        })])
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    fn is_parallel_execution(&self) -> bool {
+        false
+    }
+
+    fn can_execute(&self, world: &mut World) -> bool {
+        world
+            .get::<Health>(self.entity)
+            .is_some_and(|health| !health.is_dead())
+    }
+}
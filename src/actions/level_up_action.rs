@@ -0,0 +1,39 @@
+use bevy::prelude::*;
+
+use crate::stats::Level;
+
+use super::Action;
+
+/// Stats are recomputed by the `update_stats_system` once the level changed
+#[derive(Debug, Clone)]
+pub struct LevelUpAction {
+    pub entity: Entity,
+    pub level: u32,
+}
+
+impl Action for LevelUpAction {
+    fn execute(&self, world: &mut World) -> Result<Vec<Box<dyn Action>>, ()> {
+        if !self.can_execute(world) {
+            return Err(());
+        };
+
+        let mut level = world.get_mut::<Level>(self.entity).ok_or(())?;
+        level.0 = self.level;
+
+        Ok(Vec::new())
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    fn is_parallel_execution(&self) -> bool {
+        false
+    }
+
+    fn can_execute(&self, world: &mut World) -> bool {
+        world
+            .get::<Level>(self.entity)
+            .is_some_and(|level| level.0 < self.level)
+    }
+}
@@ -0,0 +1,42 @@
+use bevy::prelude::*;
+use common::map_status::MapStatus;
+
+use crate::map_status::{MapStatusChangedEvent, MapStatuses, MAP_STATUS_TURNS};
+
+use super::Action;
+
+/// Start a map status for a few turns, ie: the rain of Rain Dance
+#[derive(Debug, Clone)]
+pub struct MapStatusAction {
+    pub status: MapStatus,
+}
+
+impl Action for MapStatusAction {
+    fn execute(&self, world: &mut World) -> Result<Vec<Box<dyn Action>>, ()> {
+        if !self.can_execute(world) {
+            return Err(());
+        };
+
+        world
+            .resource_mut::<MapStatuses>()
+            .start(self.status, Some(MAP_STATUS_TURNS));
+        world.send_event(MapStatusChangedEvent {
+            status: self.status,
+            started: true,
+        });
+
+        Ok(Vec::new())
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    fn is_parallel_execution(&self) -> bool {
+        true
+    }
+
+    fn can_execute(&self, _world: &mut World) -> bool {
+        true
+    }
+}
@@ -1,18 +1,32 @@
 use bevy::prelude::*;
+use common::element::Element;
+use spell_data::{SpellCategory, TargetAlignments};
 
 use crate::{
+    damage::{hit_damage_action, hit_lands},
+    faction::is_targetable,
     ivec2::IVec2Ext,
-    map::Position,
+    map::{GameMap, Position},
     stats::{Health, Stats},
 };
 
-use super::{damage_action::DamageAction, orient_entity, Action};
+use super::{miss_action::MissAction, orient_entity, Action};
+
+// Power of the regular attack, which has no element
+const MELEE_HIT_POWER: u32 = 20;
+// Hit rate in percent of the regular attack
+const MELEE_HIT_RATE: Option<u32> = Some(90);
+// The regular attack never hurts the team of the attacker
+const MELEE_HIT_TARGET_ALIGNMENTS: TargetAlignments = TargetAlignments {
+    caster: false,
+    friend: false,
+    foe: true,
+};
 
 #[derive(Debug, Clone)]
 pub struct MeleeHitAction {
     pub attacker: Entity,
     pub target: IVec2,
-    pub damage: i32,
 }
 
 impl Action for MeleeHitAction {
@@ -25,22 +39,37 @@ impl Action for MeleeHitAction {
             .query_filtered::<(Entity, &Position), With<Health>>()
             .iter(world)
             .filter(|(_, p)| p.0 == self.target)
+            .filter(|(entity, _)| {
+                is_targetable(world, self.attacker, *entity, &MELEE_HIT_TARGET_ALIGNMENTS)
+            })
+            .map(|(entity, _)| entity)
             .collect::<Vec<_>>();
 
         if target_entities.is_empty() {
             return Err(());
         };
 
-        let result = target_entities
-            .iter()
-            .map(|target| {
-                Box::new(DamageAction {
+        let mut result: Vec<Box<dyn Action>> = Vec::new();
+        for target in target_entities {
+            if !hit_lands(world, self.attacker, target, MELEE_HIT_RATE) {
+                result.push(Box::new(MissAction {
                     attacker: self.attacker,
-                    target: target.0,
-                    value: self.damage,
-                }) as Box<dyn Action>
-            })
-            .collect::<Vec<_>>();
+                    target,
+                }));
+                continue;
+            }
+            result.extend(
+                hit_damage_action(
+                    world,
+                    self.attacker,
+                    target,
+                    MELEE_HIT_POWER,
+                    SpellCategory::Physical,
+                    Element::None,
+                )
+                .or_blocked(),
+            );
+        }
 
         orient_entity(world, self.attacker, self.target);
 
@@ -59,14 +88,24 @@ impl Action for MeleeHitAction {
         let Some(attacker_position) = world.get::<Position>(self.attacker) else {
             return false;
         };
-        if attacker_position.0.manhattan(self.target) > 1 {
+        if attacker_position.0.chebyshev(self.target) != 1 {
             return false;
         };
+        let attacker_position = attacker_position.0;
+        if world
+            .get_resource::<GameMap>()
+            .is_some_and(|map| map.is_corner_blocked(attacker_position, self.target))
+        {
+            return false;
+        }
 
         let target_entities = world
             .query_filtered::<(Entity, &Position), With<Health>>()
             .iter(world)
             .filter(|(_, p)| p.0 == self.target)
+            .filter(|(entity, _)| {
+                is_targetable(world, self.attacker, *entity, &MELEE_HIT_TARGET_ALIGNMENTS)
+            })
             .collect::<Vec<_>>();
 
         if target_entities.is_empty() {
@@ -0,0 +1,32 @@
+use bevy::prelude::*;
+
+use super::Action;
+
+/// The hit of the attacker didn't land, the target dodges it
+#[derive(Debug, Clone)]
+pub struct MissAction {
+    pub attacker: Entity,
+    pub target: Entity,
+}
+
+impl Action for MissAction {
+    fn execute(&self, world: &mut World) -> Result<Vec<Box<dyn Action>>, ()> {
+        if !self.can_execute(world) {
+            return Err(());
+        };
+
+        Ok(Vec::new())
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    fn is_parallel_execution(&self) -> bool {
+        false
+    }
+
+    fn can_execute(&self, world: &mut World) -> bool {
+        world.get_entity(self.target).is_some()
+    }
+}
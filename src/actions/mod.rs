@@ -6,14 +6,39 @@ use std::{any::Any, fmt::Debug};
 
 pub use self::action_queue::*;
 mod action_queue;
+pub mod apply_status_action;
+pub mod cure_status_action;
 pub mod damage_action;
+pub mod dash_action;
 pub mod death_action;
 pub mod destroy_wall_action;
+pub mod drop_item_action;
+pub mod gain_exp_action;
+pub mod heal_action;
+pub mod indirect_damage_action;
+pub mod level_up_action;
+pub mod map_status_action;
 pub mod melee_hit_action;
+pub mod miss_action;
+pub mod next_floor_action;
+pub mod pick_up_item_action;
+pub mod plan_action;
+pub mod protect_action;
+pub mod protected_action;
+pub mod protection_tick_action;
+pub mod restore_belly_action;
+pub mod restore_pp_action;
 pub mod skip_action;
 pub mod spell_action;
 pub mod spell_hit_action;
 pub mod spell_projectile_action;
+pub mod stat_stage_action;
+pub mod status_blocked_action;
+pub mod status_check_action;
+pub mod status_tick_action;
+pub mod substitute_hit_action;
+pub mod throw_item_action;
+pub mod use_item_action;
 pub mod walk_action;
 
 pub struct ActionsPlugin;
@@ -63,6 +88,10 @@ pub fn orient_entity(world: &mut World, entity: Entity, target: IVec2) {
         return;
     };
     let direction = target - grid_position.0;
+    // Targeting its own tile keeps the current facing
+    if direction == IVec2::ZERO {
+        return;
+    }
 
     let Some(mut facing_orientation) = world.get_mut::<FacingOrientation>(entity) else {
         return;
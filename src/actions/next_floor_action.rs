@@ -0,0 +1,89 @@
+use bevy::{ecs::system::RunSystemOnce, prelude::*};
+
+use crate::{
+    inventory::GroundItem,
+    map::{spawn_map, Dungeon, FloorChangedEvent, GameMap, Position, TerrainType, Tilemap},
+    pieces::{Piece, PieceKind},
+};
+
+use super::{Action, ActionQueue};
+
+#[derive(Debug, Clone)]
+pub struct NextFloorAction {
+    pub entity: Entity,
+}
+
+impl Action for NextFloorAction {
+    fn execute(&self, world: &mut World) -> Result<Vec<Box<dyn Action>>, ()> {
+        if !self.can_execute(world) {
+            return Err(());
+        };
+
+        // The remaining actions of the turn belong to the previous floor
+        world.resource_mut::<ActionQueue>().0.clear();
+
+        // Tear down the current floor
+        let mut previous_floor_entities = world
+            .query_filtered::<Entity, With<Tilemap>>()
+            .iter(world)
+            .collect::<Vec<_>>();
+        previous_floor_entities.extend(
+            world
+                .query::<(Entity, &Piece)>()
+                .iter(world)
+                .filter(|(_, piece)| matches!(piece.kind, PieceKind::Npc))
+                .map(|(entity, _)| entity),
+        );
+        previous_floor_entities.extend(
+            world
+                .query_filtered::<Entity, With<GroundItem>>()
+                .iter(world),
+        );
+
+        for entity in previous_floor_entities {
+            world.entity_mut(entity).despawn_recursive();
+        }
+
+        // Generate the next one
+        let (floor, seed) = {
+            let mut dungeon = world.resource_mut::<Dungeon>();
+            dungeon.floor += 1;
+            (dungeon.floor, dungeon.floor_seed())
+        };
+        info!("Floor {floor} seed: {seed}");
+
+        let map = GameMap::generate(seed);
+        let spawn = map.player_spawn();
+        world.insert_resource(map);
+        world.run_system_once(spawn_map);
+
+        let mut position = world.get_mut::<Position>(self.entity).ok_or(())?;
+        position.0 = spawn;
+
+        world.send_event(FloorChangedEvent { floor });
+
+        Ok(Vec::new())
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    fn is_parallel_execution(&self) -> bool {
+        false
+    }
+
+    fn can_execute(&self, world: &mut World) -> bool {
+        let Some(position) = world.get::<Position>(self.entity) else {
+            return false;
+        };
+        let Some(board) = world.get_resource::<GameMap>() else {
+            return false;
+        };
+
+        board
+            .tiles
+            .get(&position.0)
+            .is_some_and(|tile| tile.r#type == TerrainType::Stairs)
+    }
+}
@@ -0,0 +1,55 @@
+use bevy::prelude::*;
+
+use crate::{
+    inventory::{GroundItem, Inventory, Item},
+    map::Position,
+};
+
+use super::Action;
+
+#[derive(Debug, Clone)]
+pub struct PickUpItemAction {
+    pub entity: Entity,
+    pub ground_item: Entity,
+    pub item: Item,
+}
+
+impl Action for PickUpItemAction {
+    fn execute(&self, world: &mut World) -> Result<Vec<Box<dyn Action>>, ()> {
+        if !self.can_execute(world) {
+            return Err(());
+        };
+
+        let mut inventory = world.get_mut::<Inventory>(self.entity).ok_or(())?;
+        inventory.add(self.item.clone()).map_err(|_| ())?;
+        world.entity_mut(self.ground_item).despawn_recursive();
+
+        Ok(Vec::new())
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    fn is_parallel_execution(&self) -> bool {
+        true
+    }
+
+    fn can_execute(&self, world: &mut World) -> bool {
+        if world
+            .get::<Inventory>(self.entity)
+            .is_none_or(|inventory| inventory.is_full())
+        {
+            return false;
+        }
+
+        // The piece may have left the tile of the item
+        let Some(position) = world.get::<Position>(self.entity) else {
+            return false;
+        };
+        world
+            .get::<Position>(self.ground_item)
+            .is_some_and(|item_position| item_position.0 == position.0)
+            && world.get::<GroundItem>(self.ground_item).is_some()
+    }
+}
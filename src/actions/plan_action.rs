@@ -0,0 +1,37 @@
+use bevy::prelude::*;
+
+use crate::ai::AIPlanning;
+
+use super::{Action, NextActions};
+
+/// Plan the actions of the npc right before it acts, used for the second action of a fast npc
+/// since the actions planned at the start of the turn are stale once it acted
+#[derive(Debug, Clone)]
+pub struct PlanAction {
+    pub entity: Entity,
+}
+
+impl Action for PlanAction {
+    fn execute(&self, world: &mut World) -> Result<Vec<Box<dyn Action>>, ()> {
+        if !self.can_execute(world) {
+            return Err(());
+        };
+
+        world.run_schedule(AIPlanning);
+
+        let next_actions = world.get::<NextActions>(self.entity).ok_or(())?;
+        Ok(next_actions.0.clone())
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    fn is_parallel_execution(&self) -> bool {
+        false
+    }
+
+    fn can_execute(&self, world: &mut World) -> bool {
+        world.get::<NextActions>(self.entity).is_some()
+    }
+}
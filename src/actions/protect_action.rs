@@ -0,0 +1,61 @@
+use bevy::prelude::*;
+
+use crate::{
+    protection::{substitute_cost, Protected, Protection, Substitute},
+    stats::Health,
+};
+
+use super::Action;
+
+/// Raise a protection on the entity, a substitute costs a part of its health
+#[derive(Debug, Clone)]
+pub struct ProtectAction {
+    pub entity: Entity,
+    pub protection: Protection,
+}
+
+impl Action for ProtectAction {
+    fn execute(&self, world: &mut World) -> Result<Vec<Box<dyn Action>>, ()> {
+        if !self.can_execute(world) {
+            return Err(());
+        };
+
+        match self.protection {
+            Protection::Protect => {
+                world.entity_mut(self.entity).insert(Protected::default());
+            }
+            Protection::Substitute => {
+                let mut health = world.get_mut::<Health>(self.entity).ok_or(())?;
+                let cost = substitute_cost(health.max);
+                health.value -= cost;
+                world
+                    .entity_mut(self.entity)
+                    .insert(Substitute { health: cost });
+            }
+        }
+
+        Ok(Vec::new())
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    fn is_parallel_execution(&self) -> bool {
+        false
+    }
+
+    fn can_execute(&self, world: &mut World) -> bool {
+        let Some(health) = world.get::<Health>(self.entity) else {
+            return false;
+        };
+        match self.protection {
+            Protection::Protect => !health.is_dead(),
+            // A pokemon too weak to pay for it can't make a substitute, nor make a second one
+            Protection::Substitute => {
+                health.value > substitute_cost(health.max)
+                    && world.get::<Substitute>(self.entity).is_none()
+            }
+        }
+    }
+}
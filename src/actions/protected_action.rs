@@ -0,0 +1,39 @@
+use bevy::prelude::*;
+
+use crate::{map::Position, protection::Protected};
+
+use super::{orient_entity, Action};
+
+/// The damage of the attacker is stopped by the protection of the target
+#[derive(Debug, Clone)]
+pub struct ProtectedAction {
+    pub attacker: Entity,
+    pub target: Entity,
+}
+
+impl Action for ProtectedAction {
+    fn execute(&self, world: &mut World) -> Result<Vec<Box<dyn Action>>, ()> {
+        if !self.can_execute(world) {
+            return Err(());
+        };
+
+        // The target faces the attacker, like when it takes the damage
+        if let Some(attacker_position) = world.get::<Position>(self.attacker) {
+            orient_entity(world, self.target, attacker_position.0);
+        }
+
+        Ok(Vec::new())
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    fn is_parallel_execution(&self) -> bool {
+        false
+    }
+
+    fn can_execute(&self, world: &mut World) -> bool {
+        world.get::<Protected>(self.target).is_some()
+    }
+}
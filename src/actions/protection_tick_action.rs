@@ -0,0 +1,39 @@
+use bevy::prelude::*;
+
+use crate::protection::Protected;
+
+use super::Action;
+
+/// Count down the protection of the entity at the start of its turn, it fades once it runs out
+#[derive(Debug, Clone)]
+pub struct ProtectionTickAction {
+    pub entity: Entity,
+}
+
+impl Action for ProtectionTickAction {
+    fn execute(&self, world: &mut World) -> Result<Vec<Box<dyn Action>>, ()> {
+        if !self.can_execute(world) {
+            return Err(());
+        };
+
+        let mut protected = world.get_mut::<Protected>(self.entity).ok_or(())?;
+        protected.turns = protected.turns.saturating_sub(1);
+        if protected.turns == 0 {
+            world.entity_mut(self.entity).remove::<Protected>();
+        }
+
+        Ok(Vec::new())
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    fn is_parallel_execution(&self) -> bool {
+        true
+    }
+
+    fn can_execute(&self, world: &mut World) -> bool {
+        world.get::<Protected>(self.entity).is_some()
+    }
+}
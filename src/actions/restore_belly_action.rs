@@ -0,0 +1,36 @@
+use bevy::prelude::*;
+
+use crate::belly::Belly;
+
+use super::Action;
+
+#[derive(Debug, Clone)]
+pub struct RestoreBellyAction {
+    pub entity: Entity,
+    pub value: u32,
+}
+
+impl Action for RestoreBellyAction {
+    fn execute(&self, world: &mut World) -> Result<Vec<Box<dyn Action>>, ()> {
+        if !self.can_execute(world) {
+            return Err(());
+        };
+
+        let mut belly = world.get_mut::<Belly>(self.entity).ok_or(())?;
+        belly.restore(self.value);
+
+        Ok(Vec::new())
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    fn is_parallel_execution(&self) -> bool {
+        true
+    }
+
+    fn can_execute(&self, world: &mut World) -> bool {
+        world.get::<Belly>(self.entity).is_some()
+    }
+}
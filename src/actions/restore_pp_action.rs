@@ -0,0 +1,39 @@
+use bevy::prelude::*;
+
+use crate::move_set::MoveSet;
+
+use super::Action;
+
+/// Restore the PP of all the moves of the entity
+#[derive(Debug, Clone)]
+pub struct RestorePPAction {
+    pub entity: Entity,
+    pub value: u32,
+}
+
+impl Action for RestorePPAction {
+    fn execute(&self, world: &mut World) -> Result<Vec<Box<dyn Action>>, ()> {
+        if !self.can_execute(world) {
+            return Err(());
+        };
+
+        let mut move_set = world.get_mut::<MoveSet>(self.entity).ok_or(())?;
+        for m in move_set.0.iter_mut().flatten() {
+            m.restore_pp(self.value);
+        }
+
+        Ok(Vec::new())
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    fn is_parallel_execution(&self) -> bool {
+        true
+    }
+
+    fn can_execute(&self, world: &mut World) -> bool {
+        world.get::<MoveSet>(self.entity).is_some()
+    }
+}
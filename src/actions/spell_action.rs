@@ -1,19 +1,31 @@
 use bevy::prelude::*;
 
 use crate::{
-    ivec2::OrientationExt,
-    map::Position,
+    faction::is_targetable,
+    ivec2::{IVec2Ext, OrientationExt},
+    map::{GameMap, Position},
+    move_set::MoveSet,
     pieces::FacingOrientation,
     spells::{Spell, SpellType},
     stats::{Health, Stats},
 };
 
-use super::{orient_entity, spell_projectile_action::SpellProjectileAction, Action};
+use super::{
+    dash_action::DashAction, map_status_action::MapStatusAction, orient_entity,
+    spell_hit_action::SpellHitAction, spell_projectile_action::SpellProjectileAction,
+    walk_action::can_walk, Action, ActionQueue, QueuedAction,
+};
 
 #[derive(Debug, Clone)]
 pub struct SpellAction {
     pub caster: Entity,
     pub spell: Spell,
+    /// Slot of the move spending its PP, `None` for Struggle
+    pub slot: Option<usize>,
+    /// Index of the strike, only the first one spends PP
+    pub strike: u32,
+    /// Direction the caster turns to before casting, `None` to cast in the facing direction
+    pub direction: Option<IVec2>,
 }
 
 impl Action for SpellAction {
@@ -24,6 +36,29 @@ impl Action for SpellAction {
 
         let world: &mut World = world;
 
+        if let Some(slot) = self.slot.filter(|_| self.strike == 0) {
+            let mut move_set = world.get_mut::<MoveSet>(self.caster).ok_or(())?;
+            let m = move_set.get_mut(slot).ok_or(())?;
+            m.pp = m.pp.saturating_sub(1);
+        }
+
+        // The next strike is casted right after this one
+        if self.strike + 1 < self.spell.strikes {
+            world
+                .resource_mut::<ActionQueue>()
+                .0
+                .push_front(QueuedAction {
+                    entity: self.caster,
+                    performable_actions: vec![Box::new(SpellAction {
+                        caster: self.caster,
+                        spell: self.spell.clone(),
+                        slot: self.slot,
+                        strike: self.strike + 1,
+                        direction: self.direction,
+                    })],
+                });
+        }
+
         let Ok((facing_orientation, position)) = world
             .query::<(&FacingOrientation, &Position)>()
             .get(world, self.caster)
@@ -31,25 +66,40 @@ impl Action for SpellAction {
             return Err(());
         };
 
-        let direction_vector = facing_orientation.0.to_vector();
+        let direction_vector = self
+            .direction
+            .unwrap_or_else(|| facing_orientation.0.to_vector());
         let position_vector = position.0;
 
         let direction = direction_vector + position_vector;
         orient_entity(world, self.caster, direction);
 
+        // The map status starts once the spell hits
+        if let Some(status) = self.spell.map_status {
+            world
+                .resource_mut::<ActionQueue>()
+                .0
+                .push_front(QueuedAction {
+                    entity: self.caster,
+                    performable_actions: vec![Box::new(MapStatusAction { status })],
+                });
+        }
+
         let mut target: IVec2 = direction_vector * *self.spell.range.end() + position_vector;
 
         // Looks for any target in range of the spell
         for i in self.spell.range.clone() {
             let test_position = direction_vector * i + position_vector;
 
-            let targetable_entities = world
+            let has_targetable_entity = world
                 .query_filtered::<(Entity, &Position), With<Health>>()
                 .iter(world)
                 .filter(|(_, p)| p.0 == test_position)
-                .collect::<Vec<_>>();
+                .any(|(entity, _)| {
+                    is_targetable(world, self.caster, entity, &self.spell.target_alignments)
+                });
 
-            if targetable_entities.is_empty() {
+            if !has_targetable_entity {
                 continue;
             }
             target = test_position;
@@ -57,13 +107,118 @@ impl Action for SpellAction {
         }
 
         match &self.spell.spell_type {
+            SpellType::Melee => {
+                let result = world
+                    .query_filtered::<(Entity, &Position), With<Health>>()
+                    .iter(world)
+                    .filter(|(_, p)| p.0 == target)
+                    .filter(|(entity, _)| {
+                        is_targetable(world, self.caster, *entity, &self.spell.target_alignments)
+                    })
+                    .map(|(entity, _)| {
+                        Box::new(SpellHitAction {
+                            caster: self.caster,
+                            target: entity,
+                            hit: self.spell.hit.clone(),
+                        }) as Box<dyn Action>
+                    })
+                    .collect::<Vec<_>>();
+
+                Ok(result)
+            }
             SpellType::Projectile(projectile_spell) => Ok(vec![Box::new(SpellProjectileAction {
                 caster: self.caster,
                 projectile: projectile_spell.clone(),
                 spell: self.spell.clone(),
                 target,
             })]),
-            _ => Ok(vec![]),
+            SpellType::Dash { range } => {
+                // Rushes tile by tile until something blocks the way, like a walk. A wall or a piece
+                // always stops the rush, whatever the `stop_at_*` flags of the spell data say
+                let mut to = position_vector;
+                for _ in 0..*range {
+                    let next = to + direction_vector;
+                    if !can_walk(world, self.caster, to, next) {
+                        break;
+                    }
+                    to = next;
+                }
+
+                // The target must stand right in front of the caster, still in range
+                let hit_position = to + direction_vector;
+                let target = world
+                    .query_filtered::<(Entity, &Position), With<Health>>()
+                    .iter(world)
+                    .filter(|(_, p)| {
+                        p.0 == hit_position && hit_position.chebyshev(position_vector) <= *range
+                    })
+                    .map(|(entity, _)| entity)
+                    .find(|entity| {
+                        is_targetable(world, self.caster, *entity, &self.spell.target_alignments)
+                    });
+
+                if let Some(target) = target {
+                    world
+                        .resource_mut::<ActionQueue>()
+                        .0
+                        .push_front(QueuedAction {
+                            entity: self.caster,
+                            performable_actions: vec![Box::new(SpellHitAction {
+                                caster: self.caster,
+                                target,
+                                hit: self.spell.hit.clone(),
+                            })],
+                        });
+                }
+
+                Ok(vec![Box::new(DashAction {
+                    entity: self.caster,
+                    from: position_vector,
+                    to,
+                    animation: self.spell.cast.animation,
+                })])
+            }
+            SpellType::SelfTarget => Ok(vec![Box::new(SpellHitAction {
+                caster: self.caster,
+                target: self.caster,
+                hit: self.spell.hit.clone(),
+            })]),
+            area => {
+                let tiles = area.affected_tiles(
+                    world.resource::<GameMap>(),
+                    position_vector,
+                    direction_vector,
+                );
+                let targets = world
+                    .query_filtered::<(Entity, &Position), With<Health>>()
+                    .iter(world)
+                    .filter(|(_, p)| tiles.contains(&p.0))
+                    .map(|(entity, _)| entity)
+                    .collect::<Vec<_>>();
+
+                // Every target is hit one after the other
+                let hit_actions = targets
+                    .into_iter()
+                    .filter(|entity| {
+                        is_targetable(world, self.caster, *entity, &self.spell.target_alignments)
+                    })
+                    .map(|entity| QueuedAction {
+                        entity: self.caster,
+                        performable_actions: vec![Box::new(SpellHitAction {
+                            caster: self.caster,
+                            target: entity,
+                            hit: self.spell.hit.clone(),
+                        })],
+                    })
+                    .collect::<Vec<_>>();
+
+                let mut action_queue = world.resource_mut::<ActionQueue>();
+                for hit_action in hit_actions.into_iter().rev() {
+                    action_queue.0.push_front(hit_action);
+                }
+
+                Ok(Vec::new())
+            }
         }
     }
 
@@ -75,7 +230,119 @@ impl Action for SpellAction {
         false
     }
 
-    fn can_execute(&self, _world: &mut World) -> bool {
-        true
+    fn can_execute(&self, world: &mut World) -> bool {
+        let is_alive = world
+            .get::<Health>(self.caster)
+            .is_some_and(|health| !health.is_dead());
+        let has_pp = self.strike > 0
+            || self.slot.is_none_or(|slot| {
+                world
+                    .get::<MoveSet>(self.caster)
+                    .and_then(|move_set| move_set.get(slot))
+                    .is_some_and(|m| m.pp > 0)
+            });
+        is_alive && has_pp
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use char_animation::orientation::Orientation;
+    use spell_data::{AttackCoverage, SpellHitbox};
+
+    use crate::{faction::Faction, pieces::Occupier, spells::test_spell_data};
+
+    use super::*;
+
+    const DASH_RANGE: u32 = 3;
+
+    /// Rush of a caster from the left end of the corridor toward the right, with a foe on the
+    /// given tile. Returns where the rush ends and the piece hit
+    fn dash(corridor: &str, foe: Option<IVec2>) -> (IVec2, Option<Entity>) {
+        let mut world = World::new();
+        world.insert_resource(ActionQueue::default());
+        let wall = "#".repeat(corridor.len());
+        world.insert_resource(GameMap::from_rows(&[
+            wall.as_str(),
+            corridor,
+            wall.as_str(),
+        ]));
+        let caster = world
+            .spawn((
+                Health { value: 10, max: 10 },
+                Position(IVec2::new(1, 1)),
+                FacingOrientation(Orientation::Est),
+                Faction::Player,
+                Occupier,
+            ))
+            .id();
+        if let Some(position) = foe {
+            world.spawn((
+                Health { value: 10, max: 10 },
+                Position(position),
+                Faction::Foe,
+                Occupier,
+            ));
+        }
+        let data = test_spell_data(
+            "Quick Attack",
+            SpellHitbox::Dash {
+                range: DASH_RANGE,
+                stop_at_hit: true,
+                stop_at_wall: true,
+                coverage: AttackCoverage::Front,
+            },
+        );
+        let action = SpellAction {
+            caster,
+            spell: Spell::from_data("quick_attack", &data).unwrap(),
+            slot: None,
+            strike: 0,
+            direction: None,
+        };
+
+        let actions = action.execute(&mut world).unwrap();
+        let dash = actions[0].as_any().downcast_ref::<DashAction>().unwrap();
+        let target = world.resource::<ActionQueue>().0.front().map(|queued| {
+            queued.performable_actions[0]
+                .as_any()
+                .downcast_ref::<SpellHitAction>()
+                .unwrap()
+                .target
+        });
+        (dash.to, target)
+    }
+
+    #[test]
+    fn stops_the_dash_before_a_piece() {
+        let (to, target) = dash("#........#", Some(IVec2::new(3, 1)));
+
+        assert_eq!(to, IVec2::new(2, 1));
+        assert!(target.is_some());
+    }
+
+    #[test]
+    fn stops_the_dash_at_a_wall() {
+        let (to, target) = dash("#...#....#", None);
+
+        assert_eq!(to, IVec2::new(3, 1));
+        assert_eq!(target, None);
+    }
+
+    #[test]
+    fn hits_a_target_at_the_end_of_the_range() {
+        let (to, target) = dash("#........#", Some(IVec2::new(4, 1)));
+
+        assert_eq!(to, IVec2::new(3, 1));
+        assert!(target.is_some());
+    }
+
+    #[test]
+    fn misses_a_target_past_the_full_rush() {
+        let (to, target) = dash("#........#", Some(IVec2::new(5, 1)));
+
+        // The rush uses the whole range, the piece right after is out of reach
+        assert_eq!(to, IVec2::new(4, 1));
+        assert_eq!(target, None);
     }
 }
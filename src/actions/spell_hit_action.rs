@@ -1,8 +1,21 @@
 use bevy::prelude::*;
+use common::element::Effectiveness;
+use rand::Rng;
+use spell_data::SpellCategory;
 
-use crate::{spells::SpellHit};
+use crate::{
+    damage::{hit_damage_action, hit_lands, type_multiplier},
+    protection::{Protected, Substitute},
+    rng::GameRng,
+    spells::SpellHit,
+    stats::Health,
+};
 
-use super::{damage_action::DamageAction, Action};
+use super::{
+    apply_status_action::ApplyStatusAction, heal_action::clamped_heal_action,
+    miss_action::MissAction, protect_action::ProtectAction, stat_stage_action::StatStageAction,
+    Action, ActionQueue, QueuedAction,
+};
 
 #[derive(Debug, Clone)]
 pub struct SpellHitAction {
@@ -11,17 +24,130 @@ pub struct SpellHitAction {
     pub target: Entity,
 }
 
+impl SpellHitAction {
+    /// Queue the protection, the heal, the statuses and the stat changes of the spell
+    fn queue_secondary_effects(&self, world: &mut World) {
+        // A protection or a substitute also keeps the statuses and the stat changes off the target
+        let is_guarded = self.target != self.caster
+            && (world.get::<Protected>(self.target).is_some()
+                || world.get::<Substitute>(self.target).is_some());
+
+        // The statuses and the stat changes are given after the damage, each one as its own action
+        let mut rng = world.resource_mut::<GameRng>();
+        let mut status_actions = self
+            .hit
+            .statuses
+            .iter()
+            .filter(|status| !(is_guarded && status.affect_target))
+            .filter(|status| rng.0.gen_ratio(status.chance.min(100), 100))
+            .map(|status| QueuedAction {
+                entity: self.caster,
+                performable_actions: vec![Box::new(ApplyStatusAction {
+                    source: self.caster,
+                    target: if status.affect_target {
+                        self.target
+                    } else {
+                        self.caster
+                    },
+                    condition: status.condition,
+                })],
+            })
+            .collect::<Vec<_>>();
+        let stat_stage_actions = self
+            .hit
+            .stat_changes
+            .iter()
+            .filter(|stat_change| !(is_guarded && stat_change.affect_target))
+            .filter(|stat_change| rng.0.gen_ratio(stat_change.chance.min(100), 100))
+            .map(|stat_change| QueuedAction {
+                entity: self.caster,
+                performable_actions: vec![Box::new(StatStageAction {
+                    entity: if stat_change.affect_target {
+                        self.target
+                    } else {
+                        self.caster
+                    },
+                    stat: stat_change.stat,
+                    stages: stat_change.stages,
+                })],
+            });
+        status_actions.extend(stat_stage_actions);
+
+        // The heal comes before the statuses
+        let heal_action = self.hit.heal.and_then(|heal| {
+            let entity = if heal.affect_target {
+                self.target
+            } else {
+                self.caster
+            };
+            let max_health = world.get::<Health>(entity)?.max;
+            let value = max_health * heal.numerator as i32 / heal.denominator as i32;
+            Some(QueuedAction {
+                entity: self.caster,
+                performable_actions: vec![Box::new(clamped_heal_action(world, entity, value)?)],
+            })
+        });
+
+        let mut action_queue = world.resource_mut::<ActionQueue>();
+        for status_action in status_actions.into_iter().rev() {
+            action_queue.0.push_front(status_action);
+        }
+        if let Some(heal_action) = heal_action {
+            action_queue.0.push_front(heal_action);
+        }
+        // The protection is raised first, ie: Protect
+        if let Some(protection) = self.hit.protection {
+            action_queue.0.push_front(QueuedAction {
+                entity: self.caster,
+                performable_actions: vec![Box::new(ProtectAction {
+                    entity: self.caster,
+                    protection,
+                })],
+            });
+        }
+    }
+}
+
 impl Action for SpellHitAction {
     fn execute(&self, world: &mut World) -> Result<Vec<Box<dyn Action>>, ()> {
         if !self.can_execute(world) {
             return Err(());
         };
 
-        Ok(vec![Box::new(DamageAction {
-            attacker: self.caster,
-            target: self.target,
-            value: self.hit.damage,
-        })])
+        // The spells never miss their caster
+        if self.target != self.caster
+            && !hit_lands(world, self.caster, self.target, self.hit.hit_rate)
+        {
+            return Ok(vec![Box::new(MissAction {
+                attacker: self.caster,
+                target: self.target,
+            })]);
+        }
+
+        // A target immune to the element of the spell doesn't get its secondary effects either
+        let is_immune = self.target != self.caster
+            && Effectiveness::from_multiplier(type_multiplier(
+                world,
+                self.hit.element,
+                self.target,
+            )) == Effectiveness::Immune;
+        if !is_immune {
+            self.queue_secondary_effects(world);
+        }
+
+        if self.hit.category == SpellCategory::Status {
+            return Ok(vec![]);
+        }
+
+        Ok(hit_damage_action(
+            world,
+            self.caster,
+            self.target,
+            self.hit.power,
+            self.hit.category,
+            self.hit.element,
+        )
+        .or_blocked())
     }
 
     fn as_any(&self) -> &dyn std::any::Any {
@@ -36,3 +162,170 @@ impl Action for SpellHitAction {
         true
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use common::element::Element;
+
+    use crate::{
+        actions::damage_action::DamageAction, map::Position, spells::SpellHitStatus, stats::Stats,
+        status::StatusCondition,
+    };
+
+    use super::*;
+
+    /// A physical attack burning the target on every hit, the target has 50 HP
+    fn setup(hit_rate: Option<u32>) -> (World, SpellHitAction) {
+        let mut world = World::new();
+        world.insert_resource(GameRng::seeded(42));
+        world.insert_resource(ActionQueue::default());
+        let caster = world.spawn((Stats::default(), Position(IVec2::ZERO))).id();
+        let target = world
+            .spawn((
+                Stats::default(),
+                Health { value: 50, max: 50 },
+                Position(IVec2::X),
+            ))
+            .id();
+
+        let action = SpellHitAction {
+            caster,
+            hit: SpellHit {
+                visual_effect: String::new(),
+                power: 40,
+                category: SpellCategory::Physical,
+                element: Element::Normal,
+                hit_rate,
+                statuses: vec![SpellHitStatus {
+                    condition: StatusCondition::Burn,
+                    affect_target: true,
+                    chance: 100,
+                }],
+                stat_changes: vec![],
+                heal: None,
+                protection: None,
+            },
+            target,
+        };
+
+        (world, action)
+    }
+
+    fn execute(hit_rate: Option<u32>) -> Vec<Box<dyn Action>> {
+        let (mut world, action) = setup(hit_rate);
+        action.execute(&mut world).unwrap()
+    }
+
+    /// Hit the target, then execute the first performable action like the action queue.
+    /// Returns the index of the performed action and the damage of the hit
+    fn hit(world: &mut World, action: &SpellHitAction) -> (Option<usize>, i32) {
+        let actions = action.execute(world).unwrap();
+        let damage = actions[0]
+            .as_any()
+            .downcast_ref::<DamageAction>()
+            .unwrap()
+            .value;
+        let performed = actions
+            .iter()
+            .position(|action| action.execute(world).is_ok());
+        (performed, damage)
+    }
+
+    fn target_health(world: &World, action: &SpellHitAction) -> i32 {
+        world.get::<Health>(action.target).unwrap().value
+    }
+
+    fn is_burn_queued(world: &World) -> bool {
+        world.resource::<ActionQueue>().0.iter().any(|queued| {
+            queued.performable_actions[0]
+                .as_any()
+                .is::<ApplyStatusAction>()
+        })
+    }
+
+    #[test]
+    fn misses_without_any_hit_chance() {
+        let actions = execute(Some(0));
+
+        assert_eq!(actions.len(), 1);
+        assert!(actions[0].as_any().is::<MissAction>());
+    }
+
+    #[test]
+    fn always_hits_with_a_full_hit_chance() {
+        let actions = execute(Some(100));
+
+        assert!(actions[0].as_any().is::<DamageAction>());
+        assert!(!actions
+            .iter()
+            .any(|action| action.as_any().is::<MissAction>()));
+    }
+
+    #[test]
+    fn damages_and_burns_an_unguarded_target() {
+        let (mut world, action) = setup(None);
+
+        let (performed, damage) = hit(&mut world, &action);
+
+        assert_eq!(performed, Some(0));
+        assert!(damage > 0);
+        assert_eq!(target_health(&world, &action), 50 - damage);
+        assert!(is_burn_queued(&world));
+    }
+
+    #[test]
+    fn protect_blocks_the_damage_and_the_statuses() {
+        let (mut world, action) = setup(None);
+        world.entity_mut(action.target).insert(Protected::default());
+
+        let (performed, _) = hit(&mut world, &action);
+
+        assert_eq!(performed, Some(1));
+        assert_eq!(target_health(&world, &action), 50);
+        assert!(!is_burn_queued(&world));
+    }
+
+    #[test]
+    fn the_substitute_takes_the_damage_and_the_statuses() {
+        let (mut world, action) = setup(None);
+        world
+            .entity_mut(action.target)
+            .insert(Substitute { health: 1000 });
+
+        let (performed, damage) = hit(&mut world, &action);
+
+        assert_eq!(performed, Some(2));
+        assert_eq!(target_health(&world, &action), 50);
+        assert_eq!(
+            world.get::<Substitute>(action.target).unwrap().health,
+            1000 - damage
+        );
+        assert!(!is_burn_queued(&world));
+    }
+
+    #[test]
+    fn breaks_the_substitute_without_health_left() {
+        let (mut world, action) = setup(None);
+        world
+            .entity_mut(action.target)
+            .insert(Substitute { health: 1 });
+
+        hit(&mut world, &action);
+
+        assert!(world.get::<Substitute>(action.target).is_none());
+        assert_eq!(target_health(&world, &action), 50);
+    }
+
+    #[test]
+    fn protect_comes_before_the_substitute() {
+        let (mut world, action) = setup(None);
+        world
+            .entity_mut(action.target)
+            .insert((Protected::default(), Substitute { health: 1 }));
+
+        let (performed, _) = hit(&mut world, &action);
+
+        assert_eq!(performed, Some(1));
+        assert_eq!(world.get::<Substitute>(action.target).unwrap().health, 1);
+    }
+}
@@ -1,6 +1,7 @@
 use bevy::prelude::*;
 
 use crate::{
+    faction::is_targetable,
     map::Position,
     spells::{ProjectileSpell, Spell},
     stats::{Health, Stats},
@@ -26,6 +27,9 @@ impl Action for SpellProjectileAction {
             .query_filtered::<(Entity, &Position), With<Health>>()
             .iter(world)
             .filter(|(_, p)| p.0 == self.target)
+            .filter(|(entity, _)| {
+                is_targetable(world, self.caster, *entity, &self.spell.target_alignments)
+            })
             .collect::<Vec<_>>();
 
         if target_entities.is_empty() {
@@ -0,0 +1,49 @@
+use bevy::prelude::*;
+
+use crate::stats::{BattleStat, Health, StatStageChangedEvent, Stats};
+
+use super::Action;
+
+/// Raise or lower a stat stage of the entity
+#[derive(Debug, Clone)]
+pub struct StatStageAction {
+    pub entity: Entity,
+    pub stat: BattleStat,
+    /// Positive to raise the stat, negative to lower it
+    pub stages: i32,
+}
+
+impl Action for StatStageAction {
+    fn execute(&self, world: &mut World) -> Result<Vec<Box<dyn Action>>, ()> {
+        if !self.can_execute(world) {
+            return Err(());
+        };
+
+        let mut stats = world.get_mut::<Stats>(self.entity).ok_or(())?;
+        let changed = stats.get_mut(self.stat).change_stage(self.stages);
+
+        world.send_event(StatStageChangedEvent {
+            entity: self.entity,
+            stat: self.stat,
+            stages: self.stages,
+            changed,
+        });
+
+        Ok(Vec::new())
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    fn is_parallel_execution(&self) -> bool {
+        false
+    }
+
+    fn can_execute(&self, world: &mut World) -> bool {
+        world.get::<Stats>(self.entity).is_some()
+            && world
+                .get::<Health>(self.entity)
+                .is_some_and(|health| !health.is_dead())
+    }
+}
@@ -0,0 +1,55 @@
+use bevy::prelude::*;
+use rand::seq::SliceRandom;
+
+use crate::{ivec2::DIRECTIONS, map::Position, rng::GameRng, status::StatusCondition};
+
+use super::{melee_hit_action::MeleeHitAction, walk_action::WalkAction, Action};
+
+/// The entity loses its turn because of a status, a confused entity stumbles in a random direction
+#[derive(Debug, Clone)]
+pub struct StatusBlockedAction {
+    pub entity: Entity,
+    pub condition: StatusCondition,
+}
+
+impl Action for StatusBlockedAction {
+    fn execute(&self, world: &mut World) -> Result<Vec<Box<dyn Action>>, ()> {
+        if !self.can_execute(world) {
+            return Err(());
+        };
+
+        if self.condition != StatusCondition::Confusion {
+            return Ok(Vec::new());
+        }
+
+        let position = world.get::<Position>(self.entity).ok_or(())?.0;
+        let direction = *DIRECTIONS
+            .choose(&mut world.resource_mut::<GameRng>().0)
+            .ok_or(())?;
+        let target = position + direction;
+
+        Ok(vec![
+            Box::new(WalkAction {
+                entity: self.entity,
+                from: position,
+                to: target,
+            }),
+            Box::new(MeleeHitAction {
+                attacker: self.entity,
+                target,
+            }),
+        ])
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    fn is_parallel_execution(&self) -> bool {
+        true
+    }
+
+    fn can_execute(&self, _world: &mut World) -> bool {
+        true
+    }
+}
@@ -0,0 +1,47 @@
+use bevy::prelude::*;
+
+use crate::{rng::GameRng, status::StatusConditions};
+
+use super::{status_blocked_action::StatusBlockedAction, Action};
+
+/// Wraps the actions of a turn, they are replaced by a `StatusBlockedAction` when a status
+/// prevents the entity from acting
+#[derive(Debug, Clone)]
+pub struct StatusCheckAction {
+    pub entity: Entity,
+    /// Actions of the turn, performed when no status blocks the entity
+    pub actions: Vec<Box<dyn Action>>,
+}
+
+impl Action for StatusCheckAction {
+    fn execute(&self, world: &mut World) -> Result<Vec<Box<dyn Action>>, ()> {
+        if !self.can_execute(world) {
+            return Err(());
+        };
+
+        let condition = world.resource_scope::<GameRng, _>(|world, mut rng| {
+            let status_conditions = world.get::<StatusConditions>(self.entity)?;
+            status_conditions.blocking_condition(&mut rng.0)
+        });
+        let Some(condition) = condition else {
+            return Ok(self.actions.clone());
+        };
+
+        Ok(vec![Box::new(StatusBlockedAction {
+            entity: self.entity,
+            condition,
+        })])
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    fn is_parallel_execution(&self) -> bool {
+        true
+    }
+
+    fn can_execute(&self, world: &mut World) -> bool {
+        world.get::<StatusConditions>(self.entity).is_some()
+    }
+}
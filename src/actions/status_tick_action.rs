@@ -0,0 +1,74 @@
+use bevy::prelude::*;
+
+use crate::{stats::Health, status::StatusConditions};
+
+use super::{
+    cure_status_action::CureStatusAction,
+    indirect_damage_action::{IndirectDamageAction, IndirectDamageCause},
+    Action, ActionQueue, QueuedAction,
+};
+
+/// Count down the statuses of the entity at the end of its turn
+#[derive(Debug, Clone)]
+pub struct StatusTickAction {
+    pub entity: Entity,
+}
+
+impl Action for StatusTickAction {
+    fn execute(&self, world: &mut World) -> Result<Vec<Box<dyn Action>>, ()> {
+        if !self.can_execute(world) {
+            return Err(());
+        };
+
+        let max_health = world.get::<Health>(self.entity).ok_or(())?.max;
+        let mut status_conditions = world.get_mut::<StatusConditions>(self.entity).ok_or(())?;
+
+        let mut damages = vec![];
+        let mut cures = vec![];
+        for status in status_conditions.0.iter_mut() {
+            if let Some(value) = status.condition.damage(max_health) {
+                damages.push(Box::new(IndirectDamageAction {
+                    entity: self.entity,
+                    value,
+                    cause: IndirectDamageCause::Status {
+                        condition: status.condition,
+                        source: status.source,
+                    },
+                }) as Box<dyn Action>);
+            }
+
+            status.turns = status.turns.saturating_sub(1);
+            if status.turns == 0 {
+                cures.push(Box::new(CureStatusAction {
+                    entity: self.entity,
+                    condition: status.condition,
+                }) as Box<dyn Action>);
+            }
+        }
+
+        // Each damage and cure is its own action, the damages come first
+        let mut action_queue = world.resource_mut::<ActionQueue>();
+        for action in damages.into_iter().chain(cures).rev() {
+            action_queue.0.push_front(QueuedAction {
+                entity: self.entity,
+                performable_actions: vec![action],
+            });
+        }
+
+        Ok(Vec::new())
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    fn is_parallel_execution(&self) -> bool {
+        true
+    }
+
+    fn can_execute(&self, world: &mut World) -> bool {
+        world
+            .get::<StatusConditions>(self.entity)
+            .is_some_and(|status_conditions| !status_conditions.0.is_empty())
+    }
+}
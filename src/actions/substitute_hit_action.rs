@@ -0,0 +1,51 @@
+use bevy::prelude::*;
+
+use crate::{
+    map::Position,
+    protection::{Protected, Substitute},
+};
+
+use super::{orient_entity, Action};
+
+/// The substitute of the target takes the damage in its place
+#[derive(Debug, Clone)]
+pub struct SubstituteHitAction {
+    pub attacker: Entity,
+    pub target: Entity,
+    pub value: i32,
+}
+
+impl Action for SubstituteHitAction {
+    fn execute(&self, world: &mut World) -> Result<Vec<Box<dyn Action>>, ()> {
+        if !self.can_execute(world) {
+            return Err(());
+        };
+
+        // The target faces the attacker, like when it takes the damage
+        if let Some(attacker_position) = world.get::<Position>(self.attacker) {
+            orient_entity(world, self.target, attacker_position.0);
+        }
+
+        let mut substitute = world.get_mut::<Substitute>(self.target).ok_or(())?;
+        substitute.health -= self.value;
+        if substitute.health <= 0 {
+            world.entity_mut(self.target).remove::<Substitute>();
+        }
+
+        Ok(Vec::new())
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    fn is_parallel_execution(&self) -> bool {
+        false
+    }
+
+    fn can_execute(&self, world: &mut World) -> bool {
+        // Protect stops the damage before it reaches the substitute
+        world.get::<Protected>(self.target).is_none()
+            && world.get::<Substitute>(self.target).is_some()
+    }
+}
@@ -0,0 +1,95 @@
+use bevy::prelude::*;
+
+use crate::{
+    inventory::{queue_item_effect, spawn_ground_item, Inventory, Item},
+    ivec2::OrientationExt,
+    map::{GameMap, Position, TerrainType},
+    pieces::FacingOrientation,
+    stats::Health,
+};
+
+use super::Action;
+
+// Number of tiles an item flies before falling on the ground
+const THROW_RANGE: i32 = 10;
+
+/// Throw the item of the bag slot in the facing direction,
+/// the first pokemon on the way receives its effect
+#[derive(Debug, Clone)]
+pub struct ThrowItemAction {
+    pub entity: Entity,
+    pub slot: usize,
+    pub item: Item,
+}
+
+impl Action for ThrowItemAction {
+    fn execute(&self, world: &mut World) -> Result<Vec<Box<dyn Action>>, ()> {
+        if !self.can_execute(world) {
+            return Err(());
+        };
+
+        let position = world.get::<Position>(self.entity).ok_or(())?.0;
+        let direction = world
+            .get::<FacingOrientation>(self.entity)
+            .ok_or(())?
+            .0
+            .to_vector();
+
+        let mut inventory = world.get_mut::<Inventory>(self.entity).ok_or(())?;
+        inventory.remove(self.slot).ok_or(())?;
+
+        let targets = world
+            .query::<(Entity, &Position, &Health)>()
+            .iter(world)
+            .filter(|(_, _, health)| !health.is_dead())
+            .map(|(entity, position, _)| (entity, position.0))
+            .collect::<Vec<_>>();
+        let map = world.resource::<GameMap>();
+
+        let mut landing = position;
+        let mut target = None;
+        for distance in 1..=THROW_RANGE {
+            let next = position + direction * distance;
+            if map
+                .tiles
+                .get(&next)
+                .is_none_or(|tile| tile.r#type == TerrainType::Wall)
+            {
+                break;
+            }
+            target = targets.iter().find(|(_, p)| *p == next).map(|(e, _)| *e);
+            if target.is_some() {
+                break;
+            }
+            landing = next;
+        }
+        // The item is lost when it falls in the water, the lava or the abyss
+        let is_ground = map
+            .tiles
+            .get(&landing)
+            .is_some_and(|tile| matches!(tile.r#type, TerrainType::Ground | TerrainType::Stairs));
+
+        if let Some(target) = target {
+            queue_item_effect(world, self.item.effect, target);
+        } else if is_ground {
+            spawn_ground_item(world, self.item.clone(), landing);
+        }
+
+        Ok(Vec::new())
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    fn is_parallel_execution(&self) -> bool {
+        false
+    }
+
+    fn can_execute(&self, world: &mut World) -> bool {
+        world
+            .get::<Inventory>(self.entity)
+            .and_then(|inventory| inventory.0.get(self.slot))
+            .is_some_and(|item| item.id == self.item.id)
+    }
+}
@@ -0,0 +1,43 @@
+use bevy::prelude::*;
+
+use crate::inventory::{queue_item_effect, Inventory, Item};
+
+use super::Action;
+
+/// Use the item of the bag slot on the piece itself, ie: eat a berry
+#[derive(Debug, Clone)]
+pub struct UseItemAction {
+    pub entity: Entity,
+    pub slot: usize,
+    pub item: Item,
+}
+
+impl Action for UseItemAction {
+    fn execute(&self, world: &mut World) -> Result<Vec<Box<dyn Action>>, ()> {
+        if !self.can_execute(world) {
+            return Err(());
+        };
+
+        let mut inventory = world.get_mut::<Inventory>(self.entity).ok_or(())?;
+        inventory.remove(self.slot).ok_or(())?;
+
+        queue_item_effect(world, self.item.effect, self.entity);
+
+        Ok(Vec::new())
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    fn is_parallel_execution(&self) -> bool {
+        false
+    }
+
+    fn can_execute(&self, world: &mut World) -> bool {
+        world
+            .get::<Inventory>(self.entity)
+            .and_then(|inventory| inventory.0.get(self.slot))
+            .is_some_and(|item| item.id == self.item.id)
+    }
+}
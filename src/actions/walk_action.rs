@@ -1,8 +1,10 @@
 use bevy::prelude::*;
 
 use crate::{
-    map::{GameMap, Position, TerrainType},
+    ivec2::IVec2Ext,
+    map::{GameMap, Position},
     pieces::Occupier,
+    terrain::mobility,
 };
 
 use super::{orient_entity, Action};
@@ -38,27 +40,46 @@ impl Action for WalkAction {
     }
 
     fn can_execute(&self, world: &mut World) -> bool {
-        let Some(board) = world.get_resource::<GameMap>() else {
+        // The entity may have moved since the action was planned
+        if !world
+            .get::<Position>(self.entity)
+            .is_some_and(|position| position.0 == self.from)
+        {
             return false;
-        };
+        }
 
-        // check if the targeted position is on the board
-        let Some(tile) = board.tiles.get(&self.to) else {
-            return false;
-        };
+        can_walk(world, self.entity, self.from, self.to)
+    }
+}
 
-        if tile.r#type != TerrainType::Ground {
-            return false;
-        }
+/// Whether the entity can step from a tile to the next one, the terrain must be crossable and
+/// free of any occupier
+pub fn can_walk(world: &mut World, entity: Entity, from: IVec2, to: IVec2) -> bool {
+    let mobility = mobility(world, entity);
+    let Some(board) = world.get_resource::<GameMap>() else {
+        return false;
+    };
 
-        if world
-            .query_filtered::<&Position, With<Occupier>>()
-            .iter(world)
-            .any(|p| p.0 == self.to)
-        {
-            return false;
-        };
+    // check if the targeted position is on the board
+    let Some(tile) = board.tiles.get(&to) else {
+        return false;
+    };
 
-        true
+    if !mobility.can_cross(&tile.r#type) {
+        return false;
     }
+
+    if from.chebyshev(to) != 1 || board.is_corner_blocked(from, to) {
+        return false;
+    }
+
+    if world
+        .query_filtered::<&Position, With<Occupier>>()
+        .iter(world)
+        .any(|p| p.0 == to)
+    {
+        return false;
+    };
+
+    true
 }
@@ -1,43 +1,76 @@
 mod npc;
 
-use bevy::prelude::*;
+use std::{
+    cmp::Reverse,
+    collections::{HashMap, HashSet},
+};
+
+use bevy::{ecs::schedule::ScheduleLabel, prelude::*};
 use rand::{thread_rng, Rng};
 
+use spell_data::SpellData;
+
 use crate::{
-    actions::{skip_action::SkipAction, walk_action::WalkAction, Action, NextActions},
+    actions::{
+        melee_hit_action::MeleeHitAction, skip_action::SkipAction, spell_action::SpellAction,
+        walk_action::WalkAction, Action, NextActions,
+    },
+    data::assets::spell_data::SpellDataLookup,
     faction::Faction,
-    ivec2::{find_path, ORTHO_DIRECTIONS},
-    map::{GameMap, Position},
-    pieces::Occupier,
-    player::Player,
+    fov::{FieldOfView, Vision},
+    ivec2::{find_path, IVec2Ext, OrientationExt, DIRECTIONS},
+    map::{FloorChangedEvent, GameMap, Position},
+    move_set::MoveSet,
+    pieces::{FacingOrientation, Occupier},
+    pokemons::Elements,
+    protection::{substitute_cost, Protected, Protection, Substitute},
+    spells::{Spell, SpellType},
+    stats::{Health, Stats},
+    terrain::Mobility,
     GamePlayingSet, GameState,
 };
 
 use self::npc::NPCBundle;
 
+const FLEE_SCORE: i32 = 120;
+const PROTECT_SCORE: i32 = 110;
 const PLAYER_ATTACK_SCORE: i32 = 100;
+const SPELL_SCORE: i32 = 80;
+const SUPPORT_SCORE: i32 = 70;
 const MOVE_SCORE: i32 = 50;
+const CHASE_BONUS_SCORE: i32 = 15;
+// Score of a heal when the health is empty, it scales with the missing health
+const HEAL_SCORE: i32 = 160;
+
+// Ratio of the max health under which a npc runs away from the player
+const FLEE_HEALTH_RATIO: f32 = 0.25;
+// A npc stops raising a stat with its support spells at this stage
+const MAX_SUPPORT_STAGE: i32 = 2;
 
 pub struct AIPlugin;
 
 impl Plugin for AIPlugin {
     fn build(&self, app: &mut App) {
-        app.configure_sets(
-            Update,
-            (AISet::Planning, AISet::Late)
-                .chain()
-                .in_set(GamePlayingSet::AI),
+        app.add_systems(
+            AIPlanning,
+            (
+                (plan_wander, plan_chase, plan_melee, plan_spells, plan_flee),
+                npc_action,
+            )
+                .chain(),
         )
-        .add_systems(Update, plan_walk.in_set(AISet::Planning))
-        .add_systems(Update, npc_action.in_set(AISet::Late))
-        .add_systems(OnEnter(GameState::Playing), spawn_npcs);
+        .add_systems(Update, run_ai_planning.in_set(GamePlayingSet::AI))
+        .add_systems(OnEnter(GameState::Playing), spawn_npcs)
+        .add_systems(Update, spawn_npcs.run_if(on_event::<FloorChangedEvent>()));
     }
 }
 
-#[derive(SystemSet, Debug, Hash, PartialEq, Eq, Clone)]
-enum AISet {
-    Planning, // AI Planning
-    Late,     // AI taking their actions
+/// Plans the next actions of all the npcs, ran at the start of the turn and by `PlanAction`
+#[derive(ScheduleLabel, Debug, Hash, PartialEq, Eq, Clone)]
+pub struct AIPlanning;
+
+fn run_ai_planning(world: &mut World) {
+    world.run_schedule(AIPlanning);
 }
 
 #[derive(Component)]
@@ -46,86 +79,456 @@ struct AI;
 #[derive(Component, Default, Clone)]
 struct PossibleActions(Vec<PossibleAction>);
 
+/// Last position where a hostile piece was seen, the npc keeps chasing it out of view
+#[derive(Component, Default, Clone, Copy)]
+struct TargetMemory(Option<IVec2>);
+
 #[derive(Clone, Debug)]
 struct PossibleAction {
     score: i32,
     action: Box<dyn Action>,
 }
 
-fn spawn_npcs(mut commands: Commands) {
-    spawn_test_npc(&mut commands, IVec2::new(5, 5));
-    spawn_test_npc(&mut commands, IVec2::new(4, 5));
+fn spawn_npcs(mut commands: Commands, map: Res<GameMap>) {
+    // The first room is reserved to the player
+    for room in map.rooms.iter().skip(1).take(2) {
+        spawn_test_npc(&mut commands, room.center());
+    }
 }
 
 fn spawn_test_npc(commands: &mut Commands, position: IVec2) {
     commands.spawn(NPCBundle::new("NPC".to_string(), position, Faction::Foe));
 }
 
+/// Feed the planned actions, best score first, to the next actions of the npc
 fn npc_action(mut query: Query<(Entity, &mut PossibleActions), With<AI>>, mut commands: Commands) {
     for (entity, mut possible_actions) in query.iter_mut() {
         let mut possible_actions = possible_actions.0.drain(..).collect::<Vec<_>>();
 
-        possible_actions.sort_by(|a, b| b.score.partial_cmp(&a.score).unwrap());
+        possible_actions.sort_by_key(|possible_action| Reverse(possible_action.score));
 
-        let possible_actions = possible_actions
+        let mut next_actions = possible_actions
             .drain(..)
             .map(|possible_action| possible_action.action)
             .collect::<Vec<_>>();
 
-        // TODO: replace with possible_actions
-        commands
-            .entity(entity)
-            .insert(NextActions(vec![Box::new(SkipAction)]));
+        // Skip the turn when none of the planned actions can be executed
+        next_actions.push(Box::new(SkipAction));
+
+        commands.entity(entity).insert(NextActions(next_actions));
+    }
+}
+
+/// Random walk, used when nothing better can be done
+fn plan_wander(mut query: Query<(Entity, &Position, &mut PossibleActions), With<AI>>) {
+    let mut rng = thread_rng();
+
+    for (entity, position, mut possible_actions) in query.iter_mut() {
+        let wander_possible_actions = DIRECTIONS
+            .iter()
+            .map(|direction| PossibleAction {
+                action: Box::new(WalkAction {
+                    entity,
+                    from: position.0,
+                    to: position.0 + *direction,
+                }) as Box<dyn Action>,
+                score: MOVE_SCORE + rng.gen_range(-10..0),
+            })
+            .collect::<Vec<_>>();
+
+        possible_actions.0.extend(wander_possible_actions);
     }
 }
 
-fn plan_walk(
-    mut query: Query<(Entity, &Position, &mut PossibleActions), With<AI>>,
-    player_query: Query<&Position, With<Player>>,
+/// Position of the closest visible piece the faction is hostile to
+fn nearest_hostile(
+    position: IVec2,
+    faction: &Faction,
+    targets_query: &Query<(&Position, &Faction), With<Health>>,
+    vision: Option<&Vision>,
+) -> Option<IVec2> {
+    visible_hostiles(faction, targets_query, vision)
+        .into_iter()
+        .min_by_key(|target_position| position.chebyshev(*target_position))
+}
+
+/// Positions of the pieces the faction is hostile to and can see
+fn visible_hostiles(
+    faction: &Faction,
+    targets_query: &Query<(&Position, &Faction), With<Health>>,
+    vision: Option<&Vision>,
+) -> Vec<IVec2> {
+    let Some(vision) = vision else {
+        return Vec::new();
+    };
+
+    targets_query
+        .iter()
+        .filter(|(_, target_faction)| faction.is_hostile_to(target_faction))
+        .map(|(target_position, _)| target_position.0)
+        .filter(|target_position| vision.is_visible(target_position))
+        .collect()
+}
+
+/// Walk toward the closest hostile piece in view, or to where one was last seen
+#[allow(clippy::type_complexity)]
+fn plan_chase(
+    mut query: Query<
+        (
+            Entity,
+            &Position,
+            &Faction,
+            Option<&Elements>,
+            &mut TargetMemory,
+            &mut PossibleActions,
+        ),
+        With<AI>,
+    >,
+    targets_query: Query<(&Position, &Faction), With<Health>>,
     occupier_query: Query<&Position, With<Occupier>>,
     map: Res<GameMap>,
+    field_of_view: Res<FieldOfView>,
 ) {
-    let Ok(player_position) = player_query.get_single() else {
-        return;
-    };
+    // The walkable tiles only depend on the mobility, most npcs share the same ones
+    let mut walkable_tiles: HashMap<Mobility, HashSet<IVec2>> = HashMap::new();
+    let blockers = occupier_query.iter().map(|p| p.0).collect();
 
-    for (entity, position, mut possible_actions) in query.iter_mut() {
-        // get all possible move targets
-        let positions = ORTHO_DIRECTIONS
+    for (entity, position, faction, elements, mut target_memory, mut possible_actions) in
+        query.iter_mut()
+    {
+        let vision = field_of_view.get(faction);
+        if let Some(target_position) = nearest_hostile(position.0, faction, &targets_query, vision)
+        {
+            target_memory.0 = Some(target_position);
+        }
+
+        // The target is lost once its last known position is reached
+        if target_memory.0 == Some(position.0) {
+            target_memory.0 = None;
+        }
+        let Some(target_position) = target_memory.0 else {
+            continue;
+        };
+
+        let mobility = elements.map(Mobility::from_elements).unwrap_or_default();
+        let tiles = walkable_tiles
+            .entry(mobility)
+            .or_insert_with(|| map.walkable_tiles(&mobility));
+
+        // Same corner rule as the walk action, otherwise the npc rejects paths it can walk
+        let Some(next_position) =
+            find_path(position.0, target_position, tiles, &blockers, |from, to| {
+                map.is_corner_blocked(from, to)
+            })
+            .and_then(|path| path.front().copied())
+        else {
+            continue;
+        };
+
+        possible_actions.0.push(PossibleAction {
+            action: Box::new(WalkAction {
+                entity,
+                from: position.0,
+                to: next_position,
+            }),
+            score: MOVE_SCORE + CHASE_BONUS_SCORE,
+        });
+    }
+}
+
+/// Attack the hostile pieces standing next to the npc
+fn plan_melee(
+    mut query: Query<(Entity, &Position, &Faction, &mut PossibleActions), With<AI>>,
+    targets_query: Query<(&Position, &Faction), With<Health>>,
+    field_of_view: Res<FieldOfView>,
+) {
+    for (entity, position, faction, mut possible_actions) in query.iter_mut() {
+        let melee_possible_actions =
+            visible_hostiles(faction, &targets_query, field_of_view.get(faction))
+                .into_iter()
+                .filter(|target_position| position.0.chebyshev(*target_position) == 1)
+                .map(|target_position| PossibleAction {
+                    action: Box::new(MeleeHitAction {
+                        attacker: entity,
+                        target: target_position,
+                    }) as Box<dyn Action>,
+                    score: PLAYER_ATTACK_SCORE,
+                })
+                .collect::<Vec<_>>();
+
+        possible_actions.0.extend(melee_possible_actions);
+    }
+}
+
+/// Run away from the closest hostile piece when the health is low
+#[allow(clippy::type_complexity)]
+fn plan_flee(
+    mut query: Query<(Entity, &Position, &Faction, &Health, &mut PossibleActions), With<AI>>,
+    targets_query: Query<(&Position, &Faction), With<Health>>,
+    field_of_view: Res<FieldOfView>,
+) {
+    for (entity, position, faction, health, mut possible_actions) in query.iter_mut() {
+        if health.value as f32 > health.max as f32 * FLEE_HEALTH_RATIO {
+            continue;
+        }
+        let Some(target_position) = nearest_hostile(
+            position.0,
+            faction,
+            &targets_query,
+            field_of_view.get(faction),
+        ) else {
+            continue;
+        };
+
+        let distance = position.0.chebyshev(target_position);
+
+        let flee_possible_actions = DIRECTIONS
             .iter()
-            .map(|d| *d + position.0)
+            .map(|direction| position.0 + *direction)
+            .filter(|to| to.chebyshev(target_position) > distance)
+            .map(|to| PossibleAction {
+                action: Box::new(WalkAction {
+                    entity,
+                    from: position.0,
+                    to,
+                }) as Box<dyn Action>,
+                score: FLEE_SCORE,
+            })
             .collect::<Vec<_>>();
 
-        // find possible path to the player
-        let path_to_player = find_path(
-            position.0,
-            player_position.0,
-            &map.tiles.keys().cloned().collect(),
-            &occupier_query.iter().map(|p| p.0).collect(),
-        );
-        let mut rng = thread_rng();
-        let walk_possible_actions = positions
+        possible_actions.0.extend(flee_possible_actions);
+    }
+}
+
+/// Direction in which the spell reaches a hostile piece, the facing one is tried first
+fn spell_direction(
+    spell: &Spell,
+    map: &GameMap,
+    position: IVec2,
+    facing: IVec2,
+    hostile_positions: &[IVec2],
+) -> Option<IVec2> {
+    std::iter::once(facing)
+        .chain(
+            DIRECTIONS
+                .into_iter()
+                .filter(|direction| *direction != facing),
+        )
+        .find(|direction| match spell.spell_type {
+            SpellType::Melee | SpellType::Projectile(_) | SpellType::Dash { .. } => spell
+                .range
+                .clone()
+                .any(|i| hostile_positions.contains(&(position + *direction * i))),
+            SpellType::SelfTarget => false,
+            _ => spell
+                .spell_type
+                .affected_tiles(map, position, *direction)
+                .iter()
+                .any(|tile| hostile_positions.contains(tile)),
+        })
+}
+
+/// Score of a spell affecting only its caster, `None` while it would be useless
+fn self_spell_score(
+    spell: &Spell,
+    health: &Health,
+    stats: Option<&Stats>,
+    (is_protected, has_substitute): (bool, bool),
+    hostile_distance: Option<i32>,
+) -> Option<i32> {
+    let heal_score = spell
+        .hit
+        .heal
+        .filter(|heal| !heal.affect_target && health.value < health.max)
+        .map(|_| HEAL_SCORE * (health.max - health.value) / health.max.max(1));
+
+    // Protect only when about to be hit while weakened, a substitute is made ahead of the fight
+    let protection_score = spell
+        .hit
+        .protection
+        .and_then(|protection| match protection {
+            Protection::Protect => {
+                (hostile_distance == Some(1) && !is_protected && health.value * 2 <= health.max)
+                    .then_some(PROTECT_SCORE)
+            }
+            Protection::Substitute => (hostile_distance.is_some()
+                && !has_substitute
+                && health.value > substitute_cost(health.max))
+            .then_some(SUPPORT_SCORE),
+        });
+
+    let stat_change_score = stats
+        .filter(|_| hostile_distance.is_some())
+        .filter(|stats| {
+            spell.hit.stat_changes.iter().any(|stat_change| {
+                !stat_change.affect_target
+                    && stat_change.stages > 0
+                    && stats.get(stat_change.stat).stage() < MAX_SUPPORT_STAGE
+            })
+        })
+        .map(|_| SUPPORT_SCORE);
+
+    [heal_score, protection_score, stat_change_score]
+        .into_iter()
+        .flatten()
+        .max()
+}
+
+#[allow(clippy::type_complexity)]
+fn plan_spells(
+    mut query: Query<
+        (
+            Entity,
+            &Position,
+            &Faction,
+            &FacingOrientation,
+            &MoveSet,
+            &Health,
+            Option<&Stats>,
+            (Has<Protected>, Has<Substitute>),
+            &mut PossibleActions,
+        ),
+        With<AI>,
+    >,
+    targets_query: Query<(&Position, &Faction), With<Health>>,
+    map: Res<GameMap>,
+    field_of_view: Res<FieldOfView>,
+    spell_data_lookup: Res<SpellDataLookup>,
+    spell_data_assets: Res<Assets<SpellData>>,
+    mut reported_spells: Local<HashSet<String>>,
+) {
+    for (
+        entity,
+        position,
+        faction,
+        orientation,
+        move_set,
+        health,
+        stats,
+        guards,
+        mut possible_actions,
+    ) in query.iter_mut()
+    {
+        let facing = orientation.0.to_vector();
+        let hostile_positions =
+            visible_hostiles(faction, &targets_query, field_of_view.get(faction));
+        let hostile_distance = hostile_positions
             .iter()
-            .map(|v| {
-                // randomize movement choices
-                let mut d = rng.gen_range(-10..0);
-                if let Some(path) = &path_to_player {
-                    // however prioritze a movement if it leads to the player
-                    if path.contains(v) {
-                        d = 15
-                    }
-                }
-                PossibleAction {
-                    action: Box::new(WalkAction {
-                        entity,
-                        from: position.0,
-                        to: *v,
+            .map(|target_position| position.0.chebyshev(*target_position))
+            .min();
+
+        let spell_possible_actions = move_set
+            .usable_spells(&spell_data_lookup, &spell_data_assets, &mut reported_spells)
+            .into_iter()
+            .filter_map(|(slot, spell)| {
+                let (direction, score) = match spell.spell_type {
+                    SpellType::SelfTarget => (
+                        None,
+                        self_spell_score(&spell, health, stats, guards, hostile_distance)?,
+                    ),
+                    // The npc turns toward the first direction where the spell reaches a hostile piece
+                    _ if spell.target_alignments.foe => (
+                        Some(spell_direction(
+                            &spell,
+                            &map,
+                            position.0,
+                            facing,
+                            &hostile_positions,
+                        )?),
+                        SPELL_SCORE,
+                    ),
+                    _ => return None,
+                };
+                Some(PossibleAction {
+                    action: Box::new(SpellAction {
+                        caster: entity,
+                        spell,
+                        slot,
+                        strike: 0,
+                        direction,
                     }) as Box<dyn Action>,
-                    score: MOVE_SCORE + d,
-                }
+                    score,
+                })
             })
             .collect::<Vec<_>>();
 
-        possible_actions.0.extend(walk_possible_actions);
+        possible_actions.0.extend(spell_possible_actions);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use spell_data::{SpellHeal, SpellHitbox};
+
+    use crate::spells::test_spell_data;
+
+    use super::*;
+
+    fn self_spell(heal: Option<SpellHeal>, protection: Option<Protection>) -> Spell {
+        let mut spell =
+            Spell::from_data("self", &test_spell_data("Self", SpellHitbox::SelfTarget)).unwrap();
+        spell.hit.heal = heal;
+        spell.hit.protection = protection;
+        spell
+    }
+
+    fn health(value: i32) -> Health {
+        Health { value, max: 100 }
+    }
+
+    #[test]
+    fn heals_more_eagerly_the_lower_the_health() {
+        let recover = self_spell(
+            Some(SpellHeal {
+                numerator: 1,
+                denominator: 2,
+                affect_target: false,
+            }),
+            None,
+        );
+        let score = |value| self_spell_score(&recover, &health(value), None, (false, false), None);
+
+        assert_eq!(score(25), Some(HEAL_SCORE * 3 / 4));
+        assert!(score(75) < score(25));
+        assert_eq!(score(100), None);
+    }
+
+    #[test]
+    fn protects_when_weakened_next_to_a_hostile() {
+        let protect = self_spell(None, Some(Protection::Protect));
+        let score = |value, is_protected, hostile_distance| {
+            self_spell_score(
+                &protect,
+                &health(value),
+                None,
+                (is_protected, false),
+                hostile_distance,
+            )
+        };
+
+        assert_eq!(score(50, false, Some(1)), Some(PROTECT_SCORE));
+        assert_eq!(score(80, false, Some(1)), None);
+        assert_eq!(score(50, false, Some(2)), None);
+        assert_eq!(score(50, true, Some(1)), None);
+    }
+
+    #[test]
+    fn makes_a_single_substitute_ahead_of_the_fight() {
+        let substitute = self_spell(None, Some(Protection::Substitute));
+        let score = |value, has_substitute, hostile_distance| {
+            self_spell_score(
+                &substitute,
+                &health(value),
+                None,
+                (false, has_substitute),
+                hostile_distance,
+            )
+        };
+
+        assert_eq!(score(100, false, Some(4)), Some(SUPPORT_SCORE));
+        assert_eq!(score(100, false, None), None);
+        assert_eq!(score(100, true, Some(4)), None);
+        // Too weak to pay for it
+        assert_eq!(score(substitute_cost(100), false, Some(4)), None);
     }
 }
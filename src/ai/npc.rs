@@ -2,21 +2,31 @@ use bevy::prelude::*;
 use char_animation::orientation::Orientation;
 
 use crate::{
+    evolution::BattleRecord,
     faction::Faction,
     map::Position,
+    move_set::MoveSet,
     pieces::{Actor, FacingOrientation, Occupier, Piece, PieceKind},
     pokemons::Pokemon,
+    stats::{Experience, Level},
+    status::StatusConditions,
 };
 
-use super::{PossibleActions, AI};
+use super::{PossibleActions, TargetMemory, AI};
 
 #[derive(Bundle)]
 pub struct NPCBundle {
     actor: Actor,
     name: Name,
     pokemon: Pokemon,
+    level: Level,
+    experience: Experience,
+    battle_record: BattleRecord,
+    status_conditions: StatusConditions,
+    move_set: MoveSet,
     ai: AI,
     possible_actions: PossibleActions,
+    target_memory: TargetMemory,
     occupier: Occupier,
     piece: Piece,
     position: Position,
@@ -33,8 +43,14 @@ impl Default for NPCBundle {
                 id: 1,
                 form_index: 0,
             },
+            level: Level(5),
+            experience: Experience::default(),
+            battle_record: BattleRecord::default(),
+            status_conditions: StatusConditions::default(),
+            move_set: MoveSet::default(),
             ai: AI,
             possible_actions: PossibleActions::default(),
+            target_memory: TargetMemory::default(),
             occupier: Occupier,
             piece: Piece {
                 kind: PieceKind::Npc,
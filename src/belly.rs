@@ -0,0 +1,193 @@
+//! Hunger of the pokemons, the belly empties turn after turn and is filled up by the food items
+
+use bevy::prelude::*;
+
+use crate::{
+    actions::{
+        indirect_damage_action::{IndirectDamageAction, IndirectDamageCause},
+        ActionQueue, QueuedAction,
+    },
+    player::PlayerActionEvent,
+    pokemons::Ability,
+    turn::turn_system,
+    GamePlayingSet,
+};
+
+const DEFAULT_BELLY: u32 = 100;
+// Turns needed to empty one point of belly, like in PMD
+const BELLY_DRAIN_TURNS: u32 = 10;
+// Health lost each turn once the belly is empty
+const STARVING_DAMAGE: i32 = 1;
+/// Running drains the belly this many times faster during the turn
+pub const RUNNING_DRAIN_MULTIPLIER: u32 = 2;
+/// Belly values under which the pokemon complains about its hunger
+pub const HUNGER_THRESHOLDS: [u32; 3] = [20, 10, 0];
+
+pub struct BellyPlugin;
+
+impl Plugin for BellyPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<Belly>()
+            .add_event::<HungerWarningEvent>()
+            .add_systems(
+                Update,
+                belly_turn_system
+                    .after(turn_system)
+                    .in_set(GamePlayingSet::TurnLogics),
+            );
+    }
+}
+
+#[derive(Component, Debug, Clone, Copy, Reflect)]
+#[reflect(Component)]
+pub struct Belly {
+    pub value: u32,
+    pub max: u32,
+    /// Progress toward the next point of belly lost, in drain turns
+    pub drain_progress: u32,
+    /// Multiplies the drain of the next turn, raised while the pokemon exerts itself, ie: running
+    pub drain_multiplier: u32,
+}
+
+impl Default for Belly {
+    fn default() -> Self {
+        Self {
+            value: DEFAULT_BELLY,
+            max: DEFAULT_BELLY,
+            drain_progress: 0,
+            drain_multiplier: 1,
+        }
+    }
+}
+
+impl Belly {
+    pub fn restore(&mut self, value: u32) {
+        self.value = self.value.saturating_add(value).min(self.max);
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.value == 0
+    }
+
+    /// Drain faster during the next turn, the multiplier is reset once drained
+    pub fn exert(&mut self, multiplier: u32) {
+        self.drain_multiplier = self.drain_multiplier.max(multiplier);
+    }
+
+    /// Drain the belly for one turn, returns the threshold crossed if any
+    pub fn drain(&mut self, rate: u32) -> Option<u32> {
+        self.drain_progress += rate * self.drain_multiplier;
+        self.drain_multiplier = 1;
+        let lost = self.drain_progress / BELLY_DRAIN_TURNS;
+        self.drain_progress %= BELLY_DRAIN_TURNS;
+
+        let previous = self.value;
+        self.value = self.value.saturating_sub(lost);
+
+        HUNGER_THRESHOLDS
+            .into_iter()
+            .find(|threshold| previous > *threshold && self.value <= *threshold)
+    }
+}
+
+/// Sent when the belly of the pokemon goes down to one of the `HUNGER_THRESHOLDS`
+#[derive(Event, Debug)]
+pub struct HungerWarningEvent {
+    pub entity: Entity,
+    pub threshold: u32,
+}
+
+/// Drain turns spent each turn, the gluttons get hungry twice as fast
+fn drain_rate(ability: Option<&Ability>) -> u32 {
+    match ability.map(|ability| ability.0.as_str()) {
+        Some("gluttony") => 2,
+        _ => 1,
+    }
+}
+
+/// Empty the bellies once the player took its turn, a starving pokemon loses health instead
+fn belly_turn_system(
+    mut query: Query<(Entity, &mut Belly, Option<&Ability>)>,
+    mut action_queue: ResMut<ActionQueue>,
+    mut ev_player_action: EventReader<PlayerActionEvent>,
+    mut ev_hunger_warning: EventWriter<HungerWarningEvent>,
+) {
+    if ev_player_action.read().count() == 0 {
+        return;
+    }
+
+    for (entity, mut belly, ability) in query.iter_mut() {
+        if belly.is_empty() {
+            action_queue.0.push_back(QueuedAction {
+                entity,
+                performable_actions: vec![Box::new(IndirectDamageAction {
+                    entity,
+                    value: STARVING_DAMAGE,
+                    cause: IndirectDamageCause::Hunger,
+                })],
+            });
+            continue;
+        }
+
+        if let Some(threshold) = belly.drain(drain_rate(ability)) {
+            ev_hunger_warning.send(HungerWarningEvent { entity, threshold });
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn drains_one_point_every_drain_turns() {
+        let mut belly = Belly::default();
+
+        for _ in 0..BELLY_DRAIN_TURNS - 1 {
+            assert_eq!(belly.drain(1), None);
+        }
+        assert_eq!(belly.value, DEFAULT_BELLY);
+
+        belly.drain(1);
+        assert_eq!(belly.value, DEFAULT_BELLY - 1);
+    }
+
+    #[test]
+    fn exerting_multiplies_the_next_drain_only() {
+        let mut belly = Belly::default();
+
+        belly.exert(RUNNING_DRAIN_MULTIPLIER);
+        belly.drain(BELLY_DRAIN_TURNS / 2);
+        assert_eq!(belly.value, DEFAULT_BELLY - 1);
+        assert_eq!(belly.drain_progress, 0);
+
+        belly.drain(BELLY_DRAIN_TURNS / 2);
+        assert_eq!(belly.value, DEFAULT_BELLY - 1);
+        assert_eq!(belly.drain_progress, BELLY_DRAIN_TURNS / 2);
+    }
+
+    #[test]
+    fn warns_once_per_hunger_threshold() {
+        let mut belly = Belly {
+            value: 21,
+            ..default()
+        };
+
+        // Each drain below loses exactly one point
+        fn drain(belly: &mut Belly) -> Option<u32> {
+            belly.exert(RUNNING_DRAIN_MULTIPLIER);
+            belly.drain(BELLY_DRAIN_TURNS / RUNNING_DRAIN_MULTIPLIER)
+        }
+
+        assert_eq!(drain(&mut belly), Some(20));
+        assert_eq!(drain(&mut belly), None);
+
+        belly.value = 11;
+        assert_eq!(drain(&mut belly), Some(10));
+
+        belly.value = 1;
+        assert_eq!(drain(&mut belly), Some(0));
+        assert!(belly.is_empty());
+        assert_eq!(drain(&mut belly), None);
+    }
+}
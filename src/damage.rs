@@ -0,0 +1,422 @@
+//! Damage formula, based on the main series one
+//! https://bulbapedia.bulbagarden.net/wiki/Damage
+
+use bevy::prelude::*;
+use common::element::{Effectiveness, Element};
+use rand::Rng;
+use spell_data::SpellCategory;
+
+use crate::{
+    actions::damage_action::DamageAction,
+    map_status::{weather_multiplier, MapStatuses},
+    pokemons::Elements,
+    rng::GameRng,
+    stats::{Level, Stats, MAX_STAGE, MIN_STAGE},
+    status::{StatusCondition, StatusConditions},
+};
+
+pub const MIN_DAMAGE_ROLL: f32 = 0.85;
+pub const MAX_DAMAGE_ROLL: f32 = 1.;
+
+// Same type attack bonus
+const STAB_MULTIPLIER: f32 = 1.5;
+// A burned attacker deals half the physical damage
+const BURN_MULTIPLIER: f32 = 0.5;
+// Level used for pieces without a level
+const DEFAULT_LEVEL: u32 = 1;
+// One hit out of 16 is critical, like in PMD
+const CRITICAL_CHANCE: f64 = 1. / 16.;
+pub const CRITICAL_MULTIPLIER: f32 = 1.5;
+
+#[derive(Debug, Clone)]
+pub struct DamageCalculation<'a> {
+    pub attacker: &'a Stats,
+    pub defender: &'a Stats,
+    pub power: u32,
+    pub category: SpellCategory,
+    /// Level of the attacker
+    pub level: u32,
+    /// Type effectiveness, same type attack bonus, burn and weather
+    pub multiplier: f32,
+    /// Random factor between [`MIN_DAMAGE_ROLL`] and [`MAX_DAMAGE_ROLL`]
+    pub roll: f32,
+}
+
+impl<'a> DamageCalculation<'a> {
+    pub fn compute(&self) -> i32 {
+        let (attack, defense) = match self.category {
+            SpellCategory::Physical => {
+                (self.attacker.attack.value(), self.defender.defense.value())
+            }
+            SpellCategory::Special => (
+                self.attacker.special_attack.value(),
+                self.defender.special_defense.value(),
+            ),
+            SpellCategory::Status | SpellCategory::None => return 0,
+        };
+
+        if self.power == 0 || self.multiplier <= 0. {
+            return 0;
+        }
+
+        let attack = attack.max(1) as f32;
+        let defense = defense.max(1) as f32;
+        let level = self.level.max(1) as f32;
+
+        let base = ((2. * level / 5. + 2.) * self.power as f32 * attack / defense) / 50. + 2.;
+        let roll = self.roll.clamp(MIN_DAMAGE_ROLL, MAX_DAMAGE_ROLL);
+        let damage = (base * self.multiplier * roll).floor() as i32;
+
+        // A hit that is not resisted completely always deals some damage
+        damage.max(1)
+    }
+}
+
+pub fn random_roll(rng: &mut impl Rng) -> f32 {
+    rng.gen_range(MIN_DAMAGE_ROLL..=MAX_DAMAGE_ROLL)
+}
+
+/// Multiplier of the hit chance for the difference between the accuracy and the evasion stages
+pub fn accuracy_multiplier(stage: i32) -> f32 {
+    let stage = stage.clamp(MIN_STAGE, MAX_STAGE) as f32;
+    if stage >= 0. {
+        (3. + stage) / 3.
+    } else {
+        3. / (3. - stage)
+    }
+}
+
+/// Whether a hit with the given hit rate in percent lands, a hit without hit rate never misses
+pub fn roll_hit(rng: &mut impl Rng, hit_rate: Option<u32>, accuracy: i32, evasion: i32) -> bool {
+    let Some(hit_rate) = hit_rate else {
+        return true;
+    };
+    let chance = hit_rate as f64 / 100. * accuracy_multiplier(accuracy - evasion) as f64;
+    rng.gen_bool(chance.clamp(0., 1.))
+}
+
+pub fn roll_critical(rng: &mut impl Rng) -> bool {
+    rng.gen_bool(CRITICAL_CHANCE)
+}
+
+/// Roll whether the hit of the attacker lands on the target, using their accuracy and evasion stages
+pub fn hit_lands(
+    world: &mut World,
+    attacker: Entity,
+    target: Entity,
+    hit_rate: Option<u32>,
+) -> bool {
+    let accuracy = world
+        .get::<Stats>(attacker)
+        .map_or(0, |stats| stats.accuracy.stage());
+    let evasion = world
+        .get::<Stats>(target)
+        .map_or(0, |stats| stats.evasion.stage());
+    roll_hit(
+        &mut world.resource_mut::<GameRng>().0,
+        hit_rate,
+        accuracy,
+        evasion,
+    )
+}
+
+/// Multiplier of the element against the elements of the target
+pub fn type_multiplier(world: &World, element: Element, target: Entity) -> f32 {
+    world.get::<Elements>(target).map_or(1., |elements| {
+        element.multiplier_against_all(&elements.as_array())
+    })
+}
+
+/// Build the [`DamageAction`] of a hit from the attacker to the target
+pub fn hit_damage_action(
+    world: &mut World,
+    attacker: Entity,
+    target: Entity,
+    power: u32,
+    category: SpellCategory,
+    element: Element,
+) -> DamageAction {
+    let mut rng = world.resource_mut::<GameRng>();
+    let critical = roll_critical(&mut rng.0);
+    let roll = random_roll(&mut rng.0);
+    let critical_multiplier = if critical { CRITICAL_MULTIPLIER } else { 1. };
+
+    let type_multiplier = type_multiplier(world, element, target);
+    let stab_multiplier = match world.get::<Elements>(attacker) {
+        Some(elements) if elements.contains(element) => STAB_MULTIPLIER,
+        _ => 1.,
+    };
+    let burn_multiplier = match world.get::<StatusConditions>(attacker) {
+        Some(status_conditions)
+            if category == SpellCategory::Physical
+                && status_conditions.has(StatusCondition::Burn) =>
+        {
+            BURN_MULTIPLIER
+        }
+        _ => 1.,
+    };
+
+    let weather_multiplier = world
+        .get_resource::<MapStatuses>()
+        .map_or(1., |map_statuses| {
+            weather_multiplier(map_statuses.weather(), element)
+        });
+
+    let value = match (world.get::<Stats>(attacker), world.get::<Stats>(target)) {
+        (Some(attacker_stats), Some(defender_stats)) => DamageCalculation {
+            attacker: attacker_stats,
+            defender: defender_stats,
+            power,
+            category,
+            level: world
+                .get::<Level>(attacker)
+                .map_or(DEFAULT_LEVEL, |level| level.0),
+            multiplier: type_multiplier
+                * stab_multiplier
+                * burn_multiplier
+                * weather_multiplier
+                * critical_multiplier,
+            roll,
+        }
+        .compute(),
+        _ => 0,
+    };
+
+    DamageAction {
+        attacker,
+        target,
+        value,
+        effectiveness: Effectiveness::from_multiplier(type_multiplier),
+        critical,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::rngs::mock::StepRng;
+
+    use crate::stats::Stat;
+
+    use super::*;
+
+    fn stats(attack: i32, defense: i32) -> Stats {
+        Stats {
+            attack: Stat::new(attack),
+            defense: Stat::new(defense),
+            special_attack: Stat::new(attack),
+            special_defense: Stat::new(defense),
+            ..default()
+        }
+    }
+
+    fn calculation<'a>(attacker: &'a Stats, defender: &'a Stats) -> DamageCalculation<'a> {
+        DamageCalculation {
+            attacker,
+            defender,
+            power: 50,
+            category: SpellCategory::Physical,
+            level: 10,
+            multiplier: 1.,
+            roll: MAX_DAMAGE_ROLL,
+        }
+    }
+
+    #[test]
+    fn computes_the_base_formula() {
+        let attacker = stats(50, 50);
+        let defender = stats(50, 50);
+
+        // ((2 * 10 / 5 + 2) * 50 * 50 / 50) / 50 + 2 = 8
+        assert_eq!(calculation(&attacker, &defender).compute(), 8);
+    }
+
+    #[test]
+    fn uses_the_stats_of_the_category() {
+        let attacker = Stats {
+            attack: Stat::new(100),
+            special_attack: Stat::new(10),
+            ..stats(0, 50)
+        };
+        let defender = stats(50, 50);
+
+        let physical = calculation(&attacker, &defender).compute();
+        let special = DamageCalculation {
+            category: SpellCategory::Special,
+            ..calculation(&attacker, &defender)
+        }
+        .compute();
+
+        assert!(physical > special);
+    }
+
+    #[test]
+    fn status_spells_deal_no_damage() {
+        let attacker = stats(50, 50);
+        let defender = stats(50, 50);
+
+        let damage = DamageCalculation {
+            category: SpellCategory::Status,
+            ..calculation(&attacker, &defender)
+        }
+        .compute();
+
+        assert_eq!(damage, 0);
+    }
+
+    #[test]
+    fn immune_targets_take_no_damage() {
+        let attacker = stats(50, 50);
+        let defender = stats(50, 50);
+
+        let damage = DamageCalculation {
+            multiplier: 0.,
+            ..calculation(&attacker, &defender)
+        }
+        .compute();
+
+        assert_eq!(damage, 0);
+    }
+
+    #[test]
+    fn deals_at_least_one_damage() {
+        let attacker = stats(1, 1);
+        let defender = stats(999, 999);
+
+        let damage = DamageCalculation {
+            power: 1,
+            level: 1,
+            multiplier: 0.25,
+            roll: MIN_DAMAGE_ROLL,
+            ..calculation(&attacker, &defender)
+        }
+        .compute();
+
+        assert_eq!(damage, 1);
+    }
+
+    #[test]
+    fn handles_zero_stats_and_level() {
+        let attacker = stats(0, 0);
+        let defender = stats(0, 0);
+
+        let damage = DamageCalculation {
+            level: 0,
+            ..calculation(&attacker, &defender)
+        }
+        .compute();
+
+        // Every value is clamped to 1: ((2 * 1 / 5 + 2) * 50 * 1 / 1) / 50 + 2 = 4.4
+        assert_eq!(damage, 4);
+    }
+
+    #[test]
+    fn matches_a_main_series_example() {
+        // Level 75 Glaceon using Ice Fang against Garchomp, before the modifiers
+        let attacker = stats(123, 123);
+        let defender = stats(163, 163);
+
+        let damage = DamageCalculation {
+            power: 65,
+            level: 75,
+            ..calculation(&attacker, &defender)
+        }
+        .compute();
+
+        // ((2 * 75 / 5 + 2) * 65 * 123 / 163) / 50 + 2 = 33.39
+        assert_eq!(damage, 33);
+    }
+
+    #[test]
+    fn clamps_the_random_roll() {
+        let attacker = stats(50, 50);
+        let defender = stats(50, 50);
+
+        let max = calculation(&attacker, &defender).compute();
+        let too_high = DamageCalculation {
+            roll: 2.,
+            ..calculation(&attacker, &defender)
+        }
+        .compute();
+        let too_low = DamageCalculation {
+            roll: 0.,
+            ..calculation(&attacker, &defender)
+        }
+        .compute();
+        let min = DamageCalculation {
+            roll: MIN_DAMAGE_ROLL,
+            ..calculation(&attacker, &defender)
+        }
+        .compute();
+
+        assert_eq!(too_high, max);
+        assert_eq!(too_low, min);
+        assert!(min <= max);
+    }
+
+    #[test]
+    fn applies_the_multiplier() {
+        let attacker = stats(50, 50);
+        let defender = stats(50, 50);
+
+        let neutral = calculation(&attacker, &defender).compute();
+        let super_effective = DamageCalculation {
+            multiplier: 2.,
+            ..calculation(&attacker, &defender)
+        }
+        .compute();
+
+        assert_eq!(super_effective, neutral * 2);
+    }
+
+    #[test]
+    fn uses_the_stat_stages() {
+        let mut attacker = stats(50, 50);
+        let defender = stats(50, 50);
+        let neutral = calculation(&attacker, &defender).compute();
+
+        // +2 doubles the attack, the stage can't go past the limit
+        assert_eq!(attacker.attack.change_stage(2), 2);
+        assert_eq!(attacker.attack.value(), 100);
+        assert!(calculation(&attacker, &defender).compute() > neutral);
+        assert_eq!(attacker.attack.change_stage(10), MAX_STAGE - 2);
+
+        attacker.reset_stages();
+        assert_eq!(calculation(&attacker, &defender).compute(), neutral);
+    }
+
+    #[test]
+    fn forces_a_miss() {
+        // The highest roll fails any chance below 100%
+        let mut rng = StepRng::new(u64::MAX, 0);
+
+        assert!(!roll_hit(&mut rng, Some(90), 0, 0));
+        assert!(roll_hit(&mut rng, None, 0, 0));
+    }
+
+    #[test]
+    fn forces_a_critical_hit() {
+        assert!(roll_critical(&mut StepRng::new(0, 0)));
+        assert!(!roll_critical(&mut StepRng::new(u64::MAX, 0)));
+    }
+
+    #[test]
+    fn scales_the_hit_chance_with_the_stages() {
+        assert_eq!(accuracy_multiplier(0), 1.);
+        assert_eq!(accuracy_multiplier(MAX_STAGE), 3.);
+        assert_eq!(accuracy_multiplier(MIN_STAGE), 1. / 3.);
+        assert_eq!(accuracy_multiplier(12), accuracy_multiplier(MAX_STAGE));
+
+        // A high enough accuracy can't miss
+        assert!(roll_hit(&mut StepRng::new(u64::MAX, 0), Some(50), 3, 0));
+    }
+
+    #[test]
+    fn replays_the_rolls_of_a_seed() {
+        let mut first = GameRng::seeded(42);
+        let mut second = GameRng::seeded(42);
+
+        for _ in 0..10 {
+            assert_eq!(random_roll(&mut first.0), random_roll(&mut second.0));
+        }
+    }
+}
@@ -0,0 +1,74 @@
+use std::collections::HashMap;
+
+use bevy::{asset::LoadedFolder, prelude::*};
+use item_data::ItemData;
+
+use crate::{loading::AssetsLoading, utils::get_path_from_handle, GameState};
+
+const ITEM_DATA_PATH: &str = "data/items";
+
+pub struct ItemDataPlugin;
+
+impl Plugin for ItemDataPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<ItemDataAssetsFolder>()
+            .init_resource::<ItemDataLookup>()
+            .add_systems(OnEnter(GameState::Loading), load_assets_folder)
+            .add_systems(OnEnter(GameState::AssetsLoaded), process_item_data_assets);
+    }
+}
+
+#[derive(Default, Resource)]
+struct ItemDataAssetsFolder(Handle<LoadedFolder>);
+
+/// Item data handles by item ID (the file stem of the data file, ie: `oran_berry`)
+#[derive(Resource, Debug, Default)]
+pub struct ItemDataLookup(pub HashMap<String, Handle<ItemData>>);
+
+fn load_assets_folder(
+    asset_server: Res<AssetServer>,
+    mut loading: ResMut<AssetsLoading>,
+    mut item_data_assets_folder: ResMut<ItemDataAssetsFolder>,
+) {
+    info!("item data assets loading...");
+
+    let item_data_folder = asset_server.load_folder(ITEM_DATA_PATH);
+    loading.0.push(item_data_folder.clone().untyped());
+    item_data_assets_folder.0 = item_data_folder;
+}
+
+fn process_item_data_assets(
+    item_data_assets_folder: Res<ItemDataAssetsFolder>,
+    mut item_data_lookup: ResMut<ItemDataLookup>,
+    loaded_folder_assets: Res<Assets<LoadedFolder>>,
+    mut commands: Commands,
+) {
+    let folder: &LoadedFolder = match loaded_folder_assets.get(&item_data_assets_folder.0) {
+        Some(folder) => folder,
+        None => {
+            error!("Couldn't load the item data folder");
+            return;
+        }
+    };
+
+    let item_data = folder
+        .handles
+        .iter()
+        .filter_map(|handle| {
+            let path = get_path_from_handle(handle)?;
+            let file_name = path.file_name().and_then(|n| n.to_str())?;
+            let (id, _) = file_name.split_once('.')?;
+
+            let Ok(data) = handle.clone().try_typed::<ItemData>() else {
+                warn!("Failed to get item data for {id}");
+                return None;
+            };
+
+            Some((id.to_string(), data))
+        })
+        .collect::<HashMap<_, _>>();
+
+    item_data_lookup.0 = item_data;
+
+    commands.remove_resource::<ItemDataAssetsFolder>();
+}
@@ -1,7 +1,11 @@
+pub mod item_data;
 pub mod pokemon_data;
+pub mod spell_data;
 pub mod text_data;
 
+use self::item_data::ItemDataPlugin;
 use self::pokemon_data::PokemonDataPlugin;
+use self::spell_data::SpellDataPlugin;
 use self::text_data::TextDataPlugin;
 use bevy::prelude::*;
 
@@ -9,6 +13,11 @@ pub struct DataAssetsPlugin;
 
 impl Plugin for DataAssetsPlugin {
     fn build(&self, app: &mut App) {
-        app.add_plugins((PokemonDataPlugin, TextDataPlugin));
+        app.add_plugins((
+            PokemonDataPlugin,
+            SpellDataPlugin,
+            ItemDataPlugin,
+            TextDataPlugin,
+        ));
     }
 }
@@ -0,0 +1,74 @@
+use std::collections::HashMap;
+
+use bevy::{asset::LoadedFolder, prelude::*};
+use spell_data::SpellData;
+
+use crate::{loading::AssetsLoading, utils::get_path_from_handle, GameState};
+
+const SPELL_DATA_PATH: &str = "data/spells";
+
+pub struct SpellDataPlugin;
+
+impl Plugin for SpellDataPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<SpellDataAssetsFolder>()
+            .init_resource::<SpellDataLookup>()
+            .add_systems(OnEnter(GameState::Loading), load_assets_folder)
+            .add_systems(OnEnter(GameState::AssetsLoaded), process_spell_data_assets);
+    }
+}
+
+#[derive(Default, Resource)]
+struct SpellDataAssetsFolder(Handle<LoadedFolder>);
+
+/// Spell data handles by spell ID (the file stem of the data file, ie: `flamethrower`)
+#[derive(Resource, Debug, Default)]
+pub struct SpellDataLookup(pub HashMap<String, Handle<SpellData>>);
+
+fn load_assets_folder(
+    asset_server: Res<AssetServer>,
+    mut loading: ResMut<AssetsLoading>,
+    mut spell_data_assets_folder: ResMut<SpellDataAssetsFolder>,
+) {
+    info!("spell data assets loading...");
+
+    let spell_data_folder = asset_server.load_folder(SPELL_DATA_PATH);
+    loading.0.push(spell_data_folder.clone().untyped());
+    spell_data_assets_folder.0 = spell_data_folder;
+}
+
+fn process_spell_data_assets(
+    spell_data_assets_folder: Res<SpellDataAssetsFolder>,
+    mut spell_data_lookup: ResMut<SpellDataLookup>,
+    loaded_folder_assets: Res<Assets<LoadedFolder>>,
+    mut commands: Commands,
+) {
+    let folder: &LoadedFolder = match loaded_folder_assets.get(&spell_data_assets_folder.0) {
+        Some(folder) => folder,
+        None => {
+            error!("Couldn't load the spell data folder");
+            return;
+        }
+    };
+
+    let spell_data = folder
+        .handles
+        .iter()
+        .filter_map(|handle| {
+            let path = get_path_from_handle(handle)?;
+            let file_name = path.file_name().and_then(|n| n.to_str())?;
+            let (id, _) = file_name.split_once('.')?;
+
+            let Ok(data) = handle.clone().try_typed::<SpellData>() else {
+                warn!("Failed to get spell data for {id}");
+                return None;
+            };
+
+            Some((id.to_string(), data))
+        })
+        .collect::<HashMap<_, _>>();
+
+    spell_data_lookup.0 = spell_data;
+
+    commands.remove_resource::<SpellDataAssetsFolder>();
+}
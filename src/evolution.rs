@@ -0,0 +1,336 @@
+use std::{collections::HashSet, str::FromStr};
+
+use bevy::prelude::*;
+use common::element::Element;
+use pokemon_data::{PokemonData, Promotion, PromotionDetail};
+use spell_data::SpellData;
+
+use crate::{
+    belly::Belly,
+    data::assets::{
+        pokemon_data::{PokemonConversion, PokemonDataLookup},
+        spell_data::SpellDataLookup,
+    },
+    map_status::MapStatuses,
+    move_set::MoveSet,
+    pokemons::Pokemon,
+    stats::{Level, Stats},
+    GamePlayingSet,
+};
+
+pub struct EvolutionPlugin;
+
+impl Plugin for EvolutionPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<BattleRecord>()
+            .add_event::<PokemonEvolvedEvent>()
+            .add_systems(
+                Update,
+                check_evolution_system.in_set(GamePlayingSet::LateLogics),
+            );
+    }
+}
+
+/// What a pokemon went through, used by some evolution conditions
+#[derive(Component, Debug, Default, Clone, Copy, Reflect)]
+#[reflect(Component)]
+pub struct BattleRecord {
+    pub kill_count: u32,
+    pub damage_taken: u32,
+}
+
+#[derive(Event, Debug)]
+pub struct PokemonEvolvedEvent {
+    pub entity: Entity,
+    /// Name of the species the pokemon evolved into, ie: `Charmeleon`
+    pub species: String,
+}
+
+/// State of the pokemon checked against the promotion conditions
+pub struct EvolutionContext<'a> {
+    pub pokemon: &'a Pokemon,
+    pub level: u32,
+    pub stats: Option<&'a Stats>,
+    pub move_set: Option<&'a MoveSet>,
+    pub move_elements: Vec<Element>,
+    pub battle_record: BattleRecord,
+    pub belly: Option<Belly>,
+    pub map_statuses: &'a MapStatuses,
+}
+
+/// The condition exists in the data but can't be evaluated by the game yet
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct UnsupportedCondition(pub &'static str);
+
+impl<'a> EvolutionContext<'a> {
+    pub fn is_met(&self, detail: &PromotionDetail) -> Result<bool, UnsupportedCondition> {
+        let is_met = match detail {
+            PromotionDetail::Level { level } => self.level >= *level,
+            PromotionDetail::Form { req_form } => self.pokemon.form_index == *req_form as usize,
+            PromotionDetail::Move { move_num } => self
+                .move_set
+                .is_some_and(|move_set| move_set.0.iter().flatten().any(|m| m.id == *move_num)),
+            PromotionDetail::MoveElement { move_element } => Element::from_str(move_element)
+                .is_ok_and(|element| self.move_elements.contains(&element)),
+            PromotionDetail::Stats { atk_def_comparison } => {
+                let Some(stats) = self.stats else {
+                    return Ok(false);
+                };
+                let comparison = stats.attack.value().cmp(&stats.defense.value()) as i32;
+                comparison == atk_def_comparison.signum()
+            }
+            PromotionDetail::KillCount { amount } => self.battle_record.kill_count >= *amount,
+            PromotionDetail::TookDamage { amount } => self.battle_record.damage_taken >= *amount,
+            PromotionDetail::Weather { weather } => self.map_statuses.is_active(*weather),
+            // Hungry evolutions need an empty belly, the others a full one
+            PromotionDetail::Hunger { hungry } => self.belly.is_some_and(|belly| {
+                if *hungry {
+                    belly.is_empty()
+                } else {
+                    belly.value >= belly.max
+                }
+            }),
+            // Only picks the form of the evolution, it never prevents it
+            PromotionDetail::SetForm { .. } => true,
+            PromotionDetail::Item { .. } => return Err(UnsupportedCondition("Item")),
+            PromotionDetail::Friendship { .. } => return Err(UnsupportedCondition("Friendship")),
+            PromotionDetail::StatBoost { .. } => return Err(UnsupportedCondition("StatBoost")),
+            PromotionDetail::FormDusk { .. } => return Err(UnsupportedCondition("FormDusk")),
+            PromotionDetail::Walk => return Err(UnsupportedCondition("Walk")),
+            PromotionDetail::MoveUse { .. } => return Err(UnsupportedCondition("MoveUse")),
+            PromotionDetail::Gender { .. } => return Err(UnsupportedCondition("Gender")),
+            PromotionDetail::Location { .. } => return Err(UnsupportedCondition("Location")),
+            PromotionDetail::Personality { .. } => return Err(UnsupportedCondition("Personality")),
+            PromotionDetail::FormCream => return Err(UnsupportedCondition("FormCream")),
+            PromotionDetail::LocOrigin => return Err(UnsupportedCondition("LocOrigin")),
+            PromotionDetail::Rescue => return Err(UnsupportedCondition("Rescue")),
+            PromotionDetail::PartnerElement { .. } => {
+                return Err(UnsupportedCondition("PartnerElement"))
+            }
+            PromotionDetail::Crits { .. } => return Err(UnsupportedCondition("Crits")),
+            PromotionDetail::Money { .. } => return Err(UnsupportedCondition("Money")),
+            PromotionDetail::Partner { .. } => return Err(UnsupportedCondition("Partner")),
+            PromotionDetail::FormScroll => return Err(UnsupportedCondition("FormScroll")),
+            PromotionDetail::Shed { .. } => return Err(UnsupportedCondition("Shed")),
+        };
+
+        Ok(is_met)
+    }
+
+    /// Form index of the evolution when all the conditions of the promotion are met
+    pub fn evaluate(&self, promotion: &Promotion) -> Result<Option<usize>, UnsupportedCondition> {
+        let mut form_index = 0;
+
+        for detail in promotion.details.iter() {
+            if let PromotionDetail::SetForm { conditions, form } = detail {
+                if self.all_met(conditions)? {
+                    form_index = *form as usize;
+                }
+                continue;
+            }
+            if !self.is_met(detail)? {
+                return Ok(None);
+            }
+        }
+
+        Ok(Some(form_index))
+    }
+
+    fn all_met(&self, details: &[PromotionDetail]) -> Result<bool, UnsupportedCondition> {
+        for detail in details.iter() {
+            if !self.is_met(detail)? {
+                return Ok(false);
+            }
+        }
+        Ok(true)
+    }
+}
+
+/// Evolve the pokemons meeting the conditions of one of their promotions
+#[allow(clippy::type_complexity, clippy::too_many_arguments)]
+fn check_evolution_system(
+    mut query: Query<(
+        Entity,
+        &mut Pokemon,
+        Ref<Level>,
+        &Handle<PokemonData>,
+        Option<&Stats>,
+        Option<&MoveSet>,
+        Option<Ref<BattleRecord>>,
+        Option<Ref<Belly>>,
+    )>,
+    pokemon_data: Res<Assets<PokemonData>>,
+    pokemon_data_lookup: Res<PokemonDataLookup>,
+    pokemon_conversion: Res<PokemonConversion>,
+    spell_data_lookup: Res<SpellDataLookup>,
+    spell_data: Res<Assets<SpellData>>,
+    map_statuses: Res<MapStatuses>,
+    mut reported: Local<HashSet<(String, UnsupportedCondition)>>,
+    mut ev_evolved: EventWriter<PokemonEvolvedEvent>,
+) {
+    for (entity, mut pokemon, level, pokemon_data_handle, stats, move_set, battle_record, belly) in
+        query.iter_mut()
+    {
+        // The weather and the belly are conditions too, not only the level and the battles
+        let has_changed = level.is_changed()
+            || battle_record.as_ref().is_some_and(Ref::is_changed)
+            || belly.as_ref().is_some_and(Ref::is_changed)
+            || map_statuses.is_changed();
+        if !has_changed {
+            continue;
+        }
+
+        let Some(data) = pokemon_data.get(pokemon_data_handle) else {
+            warn!("Unable to retrieve pokemon data for evolution");
+            continue;
+        };
+
+        let move_elements = move_set
+            .map(|move_set| {
+                move_set
+                    .0
+                    .iter()
+                    .flatten()
+                    .filter_map(|m| spell_data_lookup.0.get(&m.id))
+                    .filter_map(|handle| spell_data.get(handle))
+                    .map(|spell| spell.element)
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        let context = EvolutionContext {
+            pokemon: &pokemon,
+            level: level.0,
+            stats,
+            move_set,
+            move_elements,
+            battle_record: battle_record.as_deref().copied().unwrap_or_default(),
+            belly: belly.as_deref().copied(),
+            map_statuses: &map_statuses,
+        };
+
+        let mut evolution = None;
+        for promotion in data.promotions.iter() {
+            match context.evaluate(promotion) {
+                Ok(Some(form_index)) => {
+                    evolution = Some((promotion.result.clone(), form_index));
+                    break;
+                }
+                Ok(None) => {}
+                Err(condition) => {
+                    if reported.insert((promotion.result.clone(), condition)) {
+                        warn!(
+                            "Unsupported evolution condition {} for {}",
+                            condition.0, promotion.result
+                        );
+                    }
+                }
+            }
+        }
+
+        let Some((species, form_index)) = evolution else {
+            continue;
+        };
+        let Some(id) = pokemon_conversion.0.get_by_right(&species) else {
+            warn!("Failed to find pokemon ID for: {species}");
+            continue;
+        };
+
+        let Some(evolution_data) = pokemon_data_lookup
+            .0
+            .get(id)
+            .and_then(|handle| pokemon_data.get(handle))
+        else {
+            warn!("Unable to retrieve pokemon data for evolution: {species}");
+            continue;
+        };
+
+        pokemon.id = *id;
+        // Fallback to the default form when the evolution doesn't have the same forms
+        pokemon.form_index = if form_index < evolution_data.forms.len() {
+            form_index
+        } else {
+            0
+        };
+        ev_evolved.send(PokemonEvolvedEvent {
+            entity,
+            species: evolution_data.name.default_text.clone(),
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn context<'a>(pokemon: &'a Pokemon, map_statuses: &'a MapStatuses) -> EvolutionContext<'a> {
+        EvolutionContext {
+            pokemon,
+            level: 16,
+            stats: None,
+            move_set: None,
+            move_elements: vec![],
+            battle_record: BattleRecord::default(),
+            belly: None,
+            map_statuses,
+        }
+    }
+
+    #[test]
+    fn checks_the_level_condition() {
+        let pokemon = Pokemon {
+            id: 4,
+            form_index: 0,
+        };
+        let map_statuses = MapStatuses::default();
+        let context = context(&pokemon, &map_statuses);
+
+        assert_eq!(
+            context.is_met(&PromotionDetail::Level { level: 16 }),
+            Ok(true)
+        );
+        assert_eq!(
+            context.is_met(&PromotionDetail::Level { level: 36 }),
+            Ok(false)
+        );
+    }
+
+    #[test]
+    fn picks_the_form_of_the_evolution() {
+        let pokemon = Pokemon {
+            id: 4,
+            form_index: 1,
+        };
+        let map_statuses = MapStatuses::default();
+        let context = context(&pokemon, &map_statuses);
+        let promotion = |req_form| Promotion {
+            result: "Charmeleon".to_string(),
+            details: vec![
+                PromotionDetail::Level { level: 16 },
+                PromotionDetail::SetForm {
+                    conditions: vec![PromotionDetail::Form { req_form }],
+                    form: 1,
+                },
+            ],
+        };
+
+        // The form of the evolution follows the current one, the default form otherwise
+        assert_eq!(context.evaluate(&promotion(1)), Ok(Some(1)));
+        assert_eq!(context.evaluate(&promotion(2)), Ok(Some(0)));
+    }
+
+    #[test]
+    fn rejects_an_unsupported_condition() {
+        let pokemon = Pokemon {
+            id: 4,
+            form_index: 0,
+        };
+        let map_statuses = MapStatuses::default();
+        let context = context(&pokemon, &map_statuses);
+
+        assert_eq!(
+            context.is_met(&PromotionDetail::Walk),
+            Err(UnsupportedCondition("Walk"))
+        );
+    }
+}
@@ -0,0 +1,108 @@
+//! Experience curves, based on the main series ones
+//! https://bulbapedia.bulbagarden.net/wiki/Experience
+
+use strum::EnumString;
+
+pub const MAX_LEVEL: u32 = 100;
+
+/// Growth rate of a pokemon, matches `PokemonData.exp_table`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, EnumString)]
+#[strum(serialize_all = "snake_case")]
+pub enum GrowthRate {
+    Erratic,
+    Fast,
+    #[default]
+    MediumFast,
+    MediumSlow,
+    Slow,
+    Fluctuating,
+}
+
+impl GrowthRate {
+    /// Total amount of experience needed to reach the level
+    pub fn total_exp(&self, level: u32) -> u32 {
+        if level <= 1 {
+            return 0;
+        }
+
+        let n = level.min(MAX_LEVEL) as i64;
+        let cube = n * n * n;
+
+        // Every curve multiplies before dividing, the only early truncations are the
+        // floors of the main series formulas: `(1911 - 10n) / 3`, `(n + 1) / 3` and `n / 2`
+        let exp = match self {
+            GrowthRate::Erratic => match n {
+                ..=49 => cube * (100 - n) / 50,
+                50..=67 => cube * (150 - n) / 100,
+                68..=97 => cube * ((1911 - 10 * n) / 3) / 500,
+                _ => cube * (160 - n) / 100,
+            },
+            GrowthRate::Fast => 4 * cube / 5,
+            GrowthRate::MediumFast => cube,
+            GrowthRate::MediumSlow => 6 * cube / 5 - 15 * n * n + 100 * n - 140,
+            GrowthRate::Slow => 5 * cube / 4,
+            GrowthRate::Fluctuating => match n {
+                ..=14 => cube * ((n + 1) / 3 + 24) / 50,
+                15..=35 => cube * (n + 14) / 50,
+                _ => cube * (n / 2 + 32) / 50,
+            },
+        };
+
+        exp.max(0) as u32
+    }
+
+    /// Amount of experience needed to go from the level to the next one
+    pub fn exp_to_next_level(&self, level: u32) -> u32 {
+        self.total_exp(level + 1)
+            .saturating_sub(self.total_exp(level))
+            .max(1)
+    }
+}
+
+/// Experience given by defeating a pokemon with the `exp_yield` of its form
+pub fn defeat_exp(exp_yield: i32, level: u32) -> u32 {
+    ((exp_yield.max(0) as u32 * level) / 7).max(1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn matches_the_erratic_table() {
+        // One level in each bracket of the curve, 68 and 97 check the floored term
+        let table = [
+            (2, 15),
+            (30, 37_800),
+            (50, 125_000),
+            (67, 249_633),
+            (68, 257_834),
+            (97, 571_333),
+            (98, 583_539),
+            (100, 600_000),
+        ];
+
+        for (level, exp) in table {
+            assert_eq!(GrowthRate::Erratic.total_exp(level), exp, "level {level}");
+        }
+    }
+
+    #[test]
+    fn matches_the_other_tables() {
+        let table = [
+            (GrowthRate::Fast, 800_000),
+            (GrowthRate::MediumFast, 1_000_000),
+            (GrowthRate::MediumSlow, 1_059_860),
+            (GrowthRate::Slow, 1_250_000),
+            (GrowthRate::Fluctuating, 1_640_000),
+        ];
+
+        for (growth_rate, exp) in table {
+            assert_eq!(growth_rate.total_exp(MAX_LEVEL), exp, "{growth_rate:?}");
+        }
+        // The floored `(n + 1) / 3` of the fluctuating curve
+        assert_eq!(GrowthRate::Fluctuating.total_exp(13), 1_230);
+        // The level 1 of the medium slow curve would be negative
+        assert_eq!(GrowthRate::MediumSlow.total_exp(1), 0);
+    }
+}
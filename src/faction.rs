@@ -1,9 +1,129 @@
 use bevy::prelude::*;
+use spell_data::TargetAlignments;
 
-#[derive(Debug, Component)]
+#[derive(Debug, Component, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum Faction {
     None,
     Player,
     Friend,
     Foe,
 }
+
+impl Faction {
+    /// Hostility table, neutral pieces are hostile to nobody
+    pub fn is_hostile_to(&self, other: &Faction) -> bool {
+        matches!(
+            (self, other),
+            (Faction::Player | Faction::Friend, Faction::Foe)
+                | (Faction::Foe, Faction::Player | Faction::Friend)
+        )
+    }
+
+    pub fn is_allied_to(&self, other: &Faction) -> bool {
+        matches!(
+            (self, other),
+            (
+                Faction::Player | Faction::Friend,
+                Faction::Player | Faction::Friend
+            ) | (Faction::Foe, Faction::Foe)
+        )
+    }
+}
+
+/// Check if a move of the caster can affect the target with the given alignments
+pub fn is_targetable(
+    world: &World,
+    caster: Entity,
+    target: Entity,
+    alignments: &TargetAlignments,
+) -> bool {
+    if caster == target {
+        return alignments.caster;
+    }
+
+    let (Some(caster_faction), Some(target_faction)) =
+        (world.get::<Faction>(caster), world.get::<Faction>(target))
+    else {
+        // A piece outside of the factions, ie: a spawned decoy, is fair game for any attack
+        return alignments.foe;
+    };
+
+    if caster_faction.is_hostile_to(target_faction) {
+        alignments.foe
+    } else if caster_faction.is_allied_to(target_faction) {
+        alignments.friend
+    } else {
+        false
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FACTIONS: [Faction; 4] = [
+        Faction::None,
+        Faction::Player,
+        Faction::Friend,
+        Faction::Foe,
+    ];
+
+    #[test]
+    fn matches_the_hostility_table() {
+        // Rows and columns follow `FACTIONS`, `H` hostile, `A` allied, `.` neither
+        let table = ["....", ".AAH", ".AAH", ".HHA"];
+
+        for (faction, row) in FACTIONS.iter().zip(table) {
+            for (other, cell) in FACTIONS.iter().zip(row.chars()) {
+                assert_eq!(
+                    faction.is_hostile_to(other),
+                    cell == 'H',
+                    "{faction:?} to {other:?}"
+                );
+                assert_eq!(
+                    faction.is_allied_to(other),
+                    cell == 'A',
+                    "{faction:?} to {other:?}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn filters_the_targets_by_alignment() {
+        let mut world = World::new();
+        let caster = world.spawn(Faction::Player).id();
+        let friend = world.spawn(Faction::Friend).id();
+        let foe = world.spawn(Faction::Foe).id();
+        let neutral = world.spawn(Faction::None).id();
+        let outsider = world.spawn_empty().id();
+        let alignments = |caster, friend, foe| TargetAlignments {
+            caster,
+            friend,
+            foe,
+        };
+        let targets = |alignments: TargetAlignments| {
+            [caster, friend, foe, neutral, outsider]
+                .map(|target| is_targetable(&world, caster, target, &alignments))
+        };
+
+        assert_eq!(
+            targets(alignments(true, false, false)),
+            [true, false, false, false, false]
+        );
+        assert_eq!(
+            targets(alignments(false, true, false)),
+            [false, true, false, false, false]
+        );
+        // The pieces without faction are hit like the foes
+        assert_eq!(
+            targets(alignments(false, false, true)),
+            [false, false, true, false, true]
+        );
+        // The neutral pieces are never targeted
+        assert_eq!(
+            targets(alignments(true, true, true)),
+            [true, true, true, false, true]
+        );
+    }
+}
@@ -0,0 +1,237 @@
+// Recursive shadowcasting: https://www.roguebasin.com/index.php/FOV_using_recursive_shadowcasting
+
+use std::collections::{HashMap, HashSet};
+
+use bevy::prelude::*;
+use common::map_status::MapStatus;
+
+use crate::{
+    faction::Faction,
+    map::{FloorChangedEvent, GameMap, Position, TerrainType},
+    map_status::MapStatuses,
+    GamePlayingSet,
+};
+
+pub const FOV_RADIUS: i32 = 8;
+// The fog reduces the view of every piece
+const FOG_FOV_RADIUS: i32 = 3;
+
+// Transforms the coordinates of the first octant into the coordinates of each octant
+const OCTANT_MULTIPLIERS: [[i32; 4]; 8] = [
+    [1, 0, 0, 1],
+    [0, 1, 1, 0],
+    [0, -1, 1, 0],
+    [-1, 0, 0, 1],
+    [-1, 0, 0, -1],
+    [0, -1, -1, 0],
+    [0, 1, -1, 0],
+    [1, 0, 0, -1],
+];
+
+pub struct FovPlugin;
+
+impl Plugin for FovPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<FieldOfView>().add_systems(
+            Update,
+            (clear_explored_tiles, update_field_of_view)
+                .chain()
+                .in_set(GamePlayingSet::LateLogics),
+        );
+    }
+}
+
+/// Tiles seen by the pieces of a faction
+#[derive(Debug, Default)]
+pub struct Vision {
+    /// Tiles currently in the field of view
+    pub visible: HashSet<IVec2>,
+    /// Tiles seen at least once on this floor
+    pub explored: HashSet<IVec2>,
+}
+
+impl Vision {
+    pub fn is_visible(&self, position: &IVec2) -> bool {
+        self.visible.contains(position)
+    }
+
+    pub fn is_explored(&self, position: &IVec2) -> bool {
+        self.explored.contains(position)
+    }
+}
+
+/// Vision shared by all the pieces of the same faction
+#[derive(Resource, Debug, Default)]
+pub struct FieldOfView(pub HashMap<Faction, Vision>);
+
+impl FieldOfView {
+    pub fn get(&self, faction: &Faction) -> Option<&Vision> {
+        self.0.get(faction)
+    }
+}
+
+/// All the tiles visible from the origin, the walls block the view but are visible themselves
+pub fn compute_fov(origin: IVec2, radius: i32, map: &GameMap) -> HashSet<IVec2> {
+    // Out of the map tiles block the view too
+    let is_opaque = |position: IVec2| {
+        map.tiles
+            .get(&position)
+            .is_none_or(|tile| tile.r#type == TerrainType::Wall)
+    };
+
+    let mut visible = HashSet::from([origin]);
+    for multipliers in OCTANT_MULTIPLIERS {
+        cast_light(
+            origin,
+            radius,
+            1,
+            (1., 0.),
+            multipliers,
+            &is_opaque,
+            &mut visible,
+        );
+    }
+    visible
+}
+
+/// Scan the rows of an octant between the start and end slopes
+fn cast_light(
+    origin: IVec2,
+    radius: i32,
+    row: i32,
+    (mut start, end): (f32, f32),
+    [xx, xy, yx, yy]: [i32; 4],
+    is_opaque: &impl Fn(IVec2) -> bool,
+    visible: &mut HashSet<IVec2>,
+) {
+    if start < end {
+        return;
+    }
+
+    let mut new_start = 0.;
+    for distance in row..=radius {
+        let dy = -distance;
+        let mut blocked = false;
+
+        for dx in -distance..=0 {
+            let left_slope = (dx as f32 - 0.5) / (dy as f32 + 0.5);
+            let right_slope = (dx as f32 + 0.5) / (dy as f32 - 0.5);
+            if start < right_slope {
+                continue;
+            }
+            if end > left_slope {
+                break;
+            }
+
+            let position = origin + IVec2::new(dx * xx + dy * xy, dx * yx + dy * yy);
+            if dx * dx + dy * dy <= radius * radius {
+                visible.insert(position);
+            }
+
+            if blocked {
+                if is_opaque(position) {
+                    new_start = right_slope;
+                } else {
+                    blocked = false;
+                    start = new_start;
+                }
+            } else if is_opaque(position) && distance < radius {
+                // The rest of the row is scanned after this wall, the tiles behind it are in its shadow
+                blocked = true;
+                cast_light(
+                    origin,
+                    radius,
+                    distance + 1,
+                    (start, left_slope),
+                    [xx, xy, yx, yy],
+                    is_opaque,
+                    visible,
+                );
+                new_start = right_slope;
+            }
+        }
+
+        if blocked {
+            break;
+        }
+    }
+}
+
+fn clear_explored_tiles(
+    mut ev_floor_changed: EventReader<FloorChangedEvent>,
+    mut field_of_view: ResMut<FieldOfView>,
+) {
+    if ev_floor_changed.read().count() > 0 {
+        field_of_view.0.clear();
+    }
+}
+
+fn update_field_of_view(
+    query: Query<(&Position, &Faction)>,
+    query_moved: Query<(), Changed<Position>>,
+    map: Res<GameMap>,
+    map_statuses: Res<MapStatuses>,
+    mut field_of_view: ResMut<FieldOfView>,
+) {
+    if query_moved.is_empty() && !map.is_changed() && !map_statuses.is_changed() {
+        return;
+    }
+
+    let radius = if map_statuses.is_active(MapStatus::Fog) {
+        FOG_FOV_RADIUS
+    } else {
+        FOV_RADIUS
+    };
+
+    for vision in field_of_view.0.values_mut() {
+        vision.visible.clear();
+    }
+
+    for (position, faction) in query.iter() {
+        let visible = compute_fov(position.0, radius, &map);
+        let vision = field_of_view.0.entry(*faction).or_default();
+        vision.explored.extend(visible.iter().copied());
+        vision.visible.extend(visible);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn walls_block_the_view() {
+        let map = GameMap::from_rows(&[
+            "#########",
+            "#.......#",
+            "#...#...#",
+            "#.......#",
+            "#########",
+        ]);
+
+        let visible = compute_fov(IVec2::new(2, 2), FOV_RADIUS, &map);
+
+        // The wall itself is visible but not the tiles in its shadow
+        assert!(visible.contains(&IVec2::new(4, 2)));
+        assert!(!visible.contains(&IVec2::new(5, 2)));
+        assert!(!visible.contains(&IVec2::new(7, 2)));
+        assert!(visible.contains(&IVec2::new(7, 1)));
+        assert!(visible.contains(&IVec2::new(7, 3)));
+    }
+
+    #[test]
+    fn limits_the_view_to_the_radius() {
+        let row = ".".repeat(21);
+        let map = GameMap::from_rows(&[row.as_str(); 21]);
+        let origin = IVec2::new(10, 10);
+
+        let visible = compute_fov(origin, 3, &map);
+
+        // Every tile within the circle of the radius is visible on an open floor
+        assert_eq!(visible.len(), 29);
+        assert!(visible.contains(&IVec2::new(13, 10)));
+        assert!(!visible.contains(&IVec2::new(14, 10)));
+        assert!(visible.contains(&IVec2::new(12, 12)));
+        assert!(!visible.contains(&IVec2::new(13, 12)));
+    }
+}
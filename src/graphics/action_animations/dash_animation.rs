@@ -0,0 +1,115 @@
+use bevy::prelude::*;
+
+use crate::{
+    actions::{dash_action::DashAction, RunningAction},
+    constants::GAME_SPEED,
+    graphics::{
+        animations::Animator, get_world_position, pokemons::PokemonAnimationState, POKEMON_Z,
+    },
+};
+
+use super::{
+    ActionAnimation, ActionAnimationFinishedEvent, ActionAnimationNextEvent,
+    ActionAnimationPlayingEvent, ActionAnimationSet, AnimationHolder,
+};
+
+// Speed of the rush, in tiles per second
+const RUSH_SPEED: f32 = 8.;
+
+pub struct DashAnimationPlugin;
+
+impl Plugin for DashAnimationPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            Update,
+            (init_dash_animation).in_set(ActionAnimationSet::Prepare),
+        )
+        .add_systems(
+            Update,
+            (dash_animation).in_set(ActionAnimationSet::PlayAnimations),
+        );
+    }
+}
+
+#[derive(Clone)]
+pub struct DashAnimation {
+    pub from: Vec3,
+    pub to: Vec3,
+    /// Distance of the rush, in tiles
+    pub distance: f32,
+    /// The caster starts moving at the rush frame of its animation
+    pub rushing: bool,
+    pub next_send: bool,
+    t: f32,
+}
+
+fn init_dash_animation(
+    query: Query<(Entity, &RunningAction), Added<RunningAction>>,
+    mut ev_animation_playing: EventWriter<ActionAnimationPlayingEvent>,
+    mut commands: Commands,
+) {
+    for (entity, running_action) in query.iter() {
+        let action = running_action.0.as_any();
+        let Some(dash_action) = action.downcast_ref::<DashAction>() else {
+            continue;
+        };
+
+        ev_animation_playing.send(ActionAnimationPlayingEvent);
+
+        commands.entity(entity).insert((
+            AnimationHolder(ActionAnimation::Dash(DashAnimation {
+                from: get_world_position(&dash_action.from, POKEMON_Z),
+                to: get_world_position(&dash_action.to, POKEMON_Z),
+                distance: dash_action
+                    .from
+                    .as_vec2()
+                    .distance(dash_action.to.as_vec2()),
+                rushing: false,
+                next_send: false,
+                t: 0.,
+            })),
+            PokemonAnimationState(dash_action.animation),
+        ));
+    }
+}
+
+fn dash_animation(
+    mut query: Query<(Entity, &mut AnimationHolder, &mut Transform, &Animator)>,
+    time: Res<Time>,
+    mut ev_animation_playing: EventWriter<ActionAnimationPlayingEvent>,
+    mut ev_animation_finished: EventWriter<ActionAnimationFinishedEvent>,
+    mut ev_animation_next: EventWriter<ActionAnimationNextEvent>,
+) {
+    for (entity, mut animation, mut transform, animator) in query.iter_mut() {
+        let AnimationHolder(ActionAnimation::Dash(animation)) = animation.as_mut() else {
+            continue;
+        };
+
+        if animator.is_rush_frame() || animator.is_finished() {
+            animation.rushing = true;
+        }
+
+        if animation.rushing && animation.t < 1. {
+            animation.t = if animation.distance > 0. {
+                (animation.t + RUSH_SPEED * time.delta_seconds() * GAME_SPEED / animation.distance)
+                    .clamp(0., 1.)
+            } else {
+                1.
+            };
+            transform.translation = animation.from.lerp(animation.to, animation.t);
+        }
+
+        // The target is hit once the caster stopped in front of it
+        if animation.t >= 1. && !animation.next_send {
+            animation.next_send = true;
+            ev_animation_next.send(ActionAnimationNextEvent(entity));
+        }
+
+        if animation.next_send && animator.is_finished() {
+            ev_animation_finished.send(ActionAnimationFinishedEvent(entity));
+            continue;
+        }
+
+        ev_animation_playing.send(ActionAnimationPlayingEvent);
+    }
+}
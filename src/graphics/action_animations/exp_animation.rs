@@ -0,0 +1,65 @@
+use bevy::prelude::*;
+
+use crate::{
+    actions::{gain_exp_action::GainExpAction, RunningAction},
+    graphics::{
+        pokemons::offsets::PokemonHeadOffset,
+        world_number::{WorldNumber, WorldNumberType},
+    },
+};
+
+use super::{ActionAnimationFinishedEvent, ActionAnimationNextEvent, ActionAnimationSet};
+
+pub struct ExpAnimationPlugin;
+
+impl Plugin for ExpAnimationPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            Update,
+            (init_exp_animation).in_set(ActionAnimationSet::Prepare),
+        );
+    }
+}
+
+/// The exp number animates on its own, the action doesn't wait for it
+fn init_exp_animation(
+    query: Query<(Entity, &RunningAction), Added<RunningAction>>,
+    query_children: Query<&Children>,
+    query_head_offset: Query<Entity, With<PokemonHeadOffset>>,
+    mut ev_animation_finished: EventWriter<ActionAnimationFinishedEvent>,
+    mut ev_animation_next: EventWriter<ActionAnimationNextEvent>,
+    mut commands: Commands,
+) {
+    for (entity, running_action) in query.iter() {
+        let action = running_action.0.as_any();
+        let Some(gain_exp_action) = action.downcast_ref::<GainExpAction>() else {
+            continue;
+        };
+
+        let target_entity_text_exp =
+            query_children
+                .get(gain_exp_action.entity)
+                .map_or(gain_exp_action.entity, |children| {
+                    children
+                        .iter()
+                        .find_map(|&child| query_head_offset.get(child).ok())
+                        .unwrap_or(gain_exp_action.entity)
+                });
+
+        commands
+            .entity(target_entity_text_exp)
+            .with_children(|parent| {
+                parent.spawn((
+                    Name::new("Text_Exp"),
+                    WorldNumber {
+                        value: gain_exp_action.value as i32,
+                        r#type: WorldNumberType::Exp,
+                    },
+                    SpatialBundle::default(),
+                ));
+            });
+
+        ev_animation_finished.send(ActionAnimationFinishedEvent(entity));
+        ev_animation_next.send(ActionAnimationNextEvent(entity));
+    }
+}
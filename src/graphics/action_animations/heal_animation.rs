@@ -0,0 +1,65 @@
+use bevy::prelude::*;
+
+use crate::{
+    actions::{heal_action::HealAction, RunningAction},
+    graphics::{
+        pokemons::offsets::PokemonHeadOffset,
+        world_number::{WorldNumber, WorldNumberType},
+    },
+};
+
+use super::{ActionAnimationFinishedEvent, ActionAnimationNextEvent, ActionAnimationSet};
+
+pub struct HealAnimationPlugin;
+
+impl Plugin for HealAnimationPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            Update,
+            (init_heal_animation).in_set(ActionAnimationSet::Prepare),
+        );
+    }
+}
+
+/// The heal number animates on its own, the action doesn't wait for it
+fn init_heal_animation(
+    query: Query<(Entity, &RunningAction), Added<RunningAction>>,
+    query_children: Query<&Children>,
+    query_head_offset: Query<Entity, With<PokemonHeadOffset>>,
+    mut ev_animation_finished: EventWriter<ActionAnimationFinishedEvent>,
+    mut ev_animation_next: EventWriter<ActionAnimationNextEvent>,
+    mut commands: Commands,
+) {
+    for (entity, running_action) in query.iter() {
+        let action = running_action.0.as_any();
+        let Some(heal_action) = action.downcast_ref::<HealAction>() else {
+            continue;
+        };
+
+        let target_entity_text_heal =
+            query_children
+                .get(heal_action.entity)
+                .map_or(heal_action.entity, |children| {
+                    children
+                        .iter()
+                        .find_map(|&child| query_head_offset.get(child).ok())
+                        .unwrap_or(heal_action.entity)
+                });
+
+        commands
+            .entity(target_entity_text_heal)
+            .with_children(|parent| {
+                parent.spawn((
+                    Name::new("Text_Heal"),
+                    WorldNumber {
+                        value: heal_action.value,
+                        r#type: WorldNumberType::Heal,
+                    },
+                    SpatialBundle::default(),
+                ));
+            });
+
+        ev_animation_finished.send(ActionAnimationFinishedEvent(entity));
+        ev_animation_next.send(ActionAnimationNextEvent(entity));
+    }
+}
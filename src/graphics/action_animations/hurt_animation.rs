@@ -2,7 +2,11 @@ use bevy::prelude::*;
 use char_animation::{anim_key::AnimKey, orientation::Orientation};
 
 use crate::{
-    actions::{damage_action::DamageAction, RunningAction},
+    actions::{
+        damage_action::DamageAction, indirect_damage_action::IndirectDamageAction,
+        protected_action::ProtectedAction, substitute_hit_action::SubstituteHitAction,
+        RunningAction,
+    },
     graphics::{
         animations::Animator,
         pokemons::{
@@ -51,38 +55,60 @@ fn init_hurt_animation(
 ) {
     for (entity, running_action) in query.iter() {
         let action = running_action.0.as_any();
-        let Some(damage_action) = action.downcast_ref::<DamageAction>() else {
+        // The damage of a status, the weather or the terrain has no attacker, the entity waits for its own animation
+        let (attacker, target, value) = if let Some(damage_action) =
+            action.downcast_ref::<DamageAction>()
+        {
+            (
+                damage_action.attacker,
+                damage_action.target,
+                Some(damage_action.value),
+            )
+        } else if let Some(indirect_damage_action) = action.downcast_ref::<IndirectDamageAction>() {
+            (
+                entity,
+                indirect_damage_action.entity,
+                Some(indirect_damage_action.value),
+            )
+        } else if let Some(substitute_hit_action) = action.downcast_ref::<SubstituteHitAction>() {
+            // The substitute takes the hit, the damage is shown on the target holding it
+            (
+                substitute_hit_action.attacker,
+                substitute_hit_action.target,
+                Some(substitute_hit_action.value),
+            )
+        } else if let Some(protected_action) = action.downcast_ref::<ProtectedAction>() {
+            // The target flinches but the hit doesn't land, no effect nor damage
+            (protected_action.attacker, protected_action.target, None)
+        } else {
             continue;
         };
 
         ev_animation_playing.send(ActionAnimationPlayingEvent);
 
-        let target_entity_hurt_effect =
-            query_children
-                .get(damage_action.target)
-                .map_or(damage_action.target, |children| {
-                    children
-                        .iter()
-                        .find_map(|&child| query_body_offset.get(child).ok())
-                        .unwrap_or(damage_action.target)
-                });
-
-        let target_entity_text_damage =
-            query_children
-                .get(damage_action.target)
-                .map_or(damage_action.target, |children| {
-                    children
-                        .iter()
-                        .find_map(|&child| query_head_offset.get(child).ok())
-                        .unwrap_or(damage_action.target)
-                });
-
-        commands.entity(damage_action.target).insert((
-            AnimationHolder(ActionAnimation::Hurt(HurtAnimation {
-                attacker: damage_action.attacker,
-            })),
+        let target_entity_hurt_effect = query_children.get(target).map_or(target, |children| {
+            children
+                .iter()
+                .find_map(|&child| query_body_offset.get(child).ok())
+                .unwrap_or(target)
+        });
+
+        let target_entity_text_damage = query_children.get(target).map_or(target, |children| {
+            children
+                .iter()
+                .find_map(|&child| query_head_offset.get(child).ok())
+                .unwrap_or(target)
+        });
+
+        commands.entity(target).insert((
+            AnimationHolder(ActionAnimation::Hurt(HurtAnimation { attacker })),
             PokemonAnimationState(AnimKey::Hurt),
         ));
+
+        let Some(value) = value else {
+            continue;
+        };
+
         commands
             .entity(target_entity_hurt_effect)
             .with_children(|parent| {
@@ -90,7 +116,7 @@ fn init_hurt_animation(
                 parent.spawn((
                     Name::new("Hit_Neutral"),
                     VisualEffect {
-                        name: "Hit_Neutral",
+                        name: "Hit_Neutral".to_string(),
                         is_loop: false,
                     },
                     AutoDespawnEffect,
@@ -105,7 +131,7 @@ fn init_hurt_animation(
                 parent.spawn((
                     Name::new("Text_Dmg"),
                     WorldNumber {
-                        value: -damage_action.value,
+                        value: -value,
                         r#type: WorldNumberType::Damage,
                     },
                     SpatialBundle::default(),
@@ -0,0 +1,108 @@
+use bevy::prelude::*;
+
+use crate::{
+    actions::{miss_action::MissAction, RunningAction},
+    constants::GAME_SPEED,
+    graphics::{get_world_position, POKEMON_Z, TILE_SIZE},
+    ivec2::OrientationExt,
+    map::Position,
+    pieces::FacingOrientation,
+};
+
+use super::{
+    ActionAnimation, ActionAnimationFinishedEvent, ActionAnimationNextEvent,
+    ActionAnimationPlayingEvent, ActionAnimationSet, AnimationHolder,
+};
+
+// Duration of the dodge, in seconds
+const MISS_DURATION: f32 = 0.3;
+// Distance of the dodge, in tiles
+const MISS_DISTANCE: f32 = 0.25;
+
+pub struct MissAnimationPlugin;
+
+impl Plugin for MissAnimationPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            Update,
+            (init_miss_animation).in_set(ActionAnimationSet::Prepare),
+        )
+        .add_systems(
+            Update,
+            (miss_animation).in_set(ActionAnimationSet::PlayAnimations),
+        );
+    }
+}
+
+#[derive(Clone)]
+pub struct MissAnimation {
+    /// Entity running the action
+    pub entity: Entity,
+    pub origin: Vec3,
+    /// Direction of the dodge, perpendicular to the facing orientation of the target
+    pub side: Vec3,
+    pub timer: Timer,
+}
+
+fn init_miss_animation(
+    query: Query<(Entity, &RunningAction), Added<RunningAction>>,
+    query_target: Query<(&Position, &FacingOrientation)>,
+    mut ev_animation_playing: EventWriter<ActionAnimationPlayingEvent>,
+    mut ev_animation_finished: EventWriter<ActionAnimationFinishedEvent>,
+    mut ev_animation_next: EventWriter<ActionAnimationNextEvent>,
+    mut commands: Commands,
+) {
+    for (entity, running_action) in query.iter() {
+        let action = running_action.0.as_any();
+        let Some(miss_action) = action.downcast_ref::<MissAction>() else {
+            continue;
+        };
+
+        let Ok((position, orientation)) = query_target.get(miss_action.target) else {
+            ev_animation_finished.send(ActionAnimationFinishedEvent(entity));
+            ev_animation_next.send(ActionAnimationNextEvent(entity));
+            continue;
+        };
+
+        ev_animation_playing.send(ActionAnimationPlayingEvent);
+
+        let direction = orientation.0.to_vector().as_vec2().normalize_or_zero();
+        commands
+            .entity(miss_action.target)
+            .insert(AnimationHolder(ActionAnimation::Miss(MissAnimation {
+                entity,
+                origin: get_world_position(&position.0, POKEMON_Z),
+                side: direction.perp().extend(0.),
+                timer: Timer::from_seconds(MISS_DURATION / GAME_SPEED, TimerMode::Once),
+            })));
+    }
+}
+
+/// The target steps aside and comes back to its tile
+fn miss_animation(
+    time: Res<Time>,
+    mut query: Query<(Entity, &mut AnimationHolder, &mut Transform)>,
+    mut ev_animation_playing: EventWriter<ActionAnimationPlayingEvent>,
+    mut ev_animation_finished: EventWriter<ActionAnimationFinishedEvent>,
+    mut ev_animation_next: EventWriter<ActionAnimationNextEvent>,
+) {
+    for (target, mut animation, mut transform) in query.iter_mut() {
+        let AnimationHolder(ActionAnimation::Miss(miss_animation)) = animation.as_mut() else {
+            continue;
+        };
+
+        miss_animation.timer.tick(time.delta());
+        if miss_animation.timer.finished() {
+            transform.translation = miss_animation.origin;
+            ev_animation_finished.send(ActionAnimationFinishedEvent(target));
+            ev_animation_next.send(ActionAnimationNextEvent(miss_animation.entity));
+            continue;
+        }
+
+        let t = miss_animation.timer.fraction();
+        let offset = (t * std::f32::consts::PI).sin() * MISS_DISTANCE * TILE_SIZE;
+        transform.translation = miss_animation.origin + miss_animation.side * offset;
+
+        ev_animation_playing.send(ActionAnimationPlayingEvent);
+    }
+}
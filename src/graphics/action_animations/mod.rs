@@ -4,26 +4,45 @@ use bevy::prelude::*;
 use char_animation::anim_key::AnimKey;
 
 use crate::{
-    actions::{skip_action::SkipAction, RunningAction},
+    actions::{
+        apply_status_action::ApplyStatusAction, cure_status_action::CureStatusAction,
+        drop_item_action::DropItemAction, level_up_action::LevelUpAction,
+        map_status_action::MapStatusAction, next_floor_action::NextFloorAction,
+        pick_up_item_action::PickUpItemAction, plan_action::PlanAction,
+        protect_action::ProtectAction, protected_action::ProtectedAction,
+        protection_tick_action::ProtectionTickAction, restore_belly_action::RestoreBellyAction,
+        restore_pp_action::RestorePPAction, skip_action::SkipAction,
+        status_blocked_action::StatusBlockedAction, status_check_action::StatusCheckAction,
+        status_tick_action::StatusTickAction, substitute_hit_action::SubstituteHitAction,
+        throw_item_action::ThrowItemAction, use_item_action::UseItemAction, RunningAction,
+    },
     GamePlayingSet,
 };
 
 use self::{
-    attack_animation::AttackAnimationPlugin, death_animation::DeathAnimationPlugin,
-    hurt_animation::HurtAnimationPlugin, move_animation::MoveAnimationPlugin,
+    attack_animation::AttackAnimationPlugin, dash_animation::DashAnimationPlugin,
+    death_animation::DeathAnimationPlugin, exp_animation::ExpAnimationPlugin,
+    heal_animation::HealAnimationPlugin, hurt_animation::HurtAnimationPlugin,
+    miss_animation::MissAnimationPlugin, move_animation::MoveAnimationPlugin,
     projectile_animation::ProjectileAnimationPlugin,
     spell_cast_animation::SpellCastAnimationPlugin, spell_hit_animation::SpellHitAnimationPlugin,
+    stat_stage_animation::StatStageAnimationPlugin,
 };
 
 use super::pokemons::PokemonAnimationState;
 
 mod attack_animation;
+mod dash_animation;
 mod death_animation;
+mod exp_animation;
+mod heal_animation;
 mod hurt_animation;
+mod miss_animation;
 mod move_animation;
 mod projectile_animation;
 mod spell_cast_animation;
 mod spell_hit_animation;
+mod stat_stage_animation;
 
 pub struct ActionAnimationPlugin;
 
@@ -40,6 +59,11 @@ impl Plugin for ActionAnimationPlugin {
                 SpellCastAnimationPlugin,
                 SpellHitAnimationPlugin,
                 DeathAnimationPlugin,
+                ExpAnimationPlugin,
+                HealAnimationPlugin,
+                MissAnimationPlugin,
+                StatStageAnimationPlugin,
+                DashAnimationPlugin,
             ))
             .configure_sets(
                 Update,
@@ -88,8 +112,10 @@ pub enum ActionAnimation {
     SpellCast(spell_cast_animation::SpellCastAnimation),
     /* #endregion */
     Move(move_animation::MoveAnimation),
+    Dash(dash_animation::DashAnimation),
     Attack,
     Hurt(hurt_animation::HurtAnimation),
+    Miss(miss_animation::MissAnimation),
     Death(death_animation::DeathAnimation),
 }
 
@@ -125,7 +151,27 @@ fn add_action_animation(
         let action = running_action.0.as_any();
         // TODO: move somewhere else
         match action.type_id() {
-            id if id == TypeId::of::<SkipAction>() => {
+            id if id == TypeId::of::<SkipAction>()
+                || id == TypeId::of::<NextFloorAction>()
+                || id == TypeId::of::<LevelUpAction>()
+                || id == TypeId::of::<MapStatusAction>()
+                || id == TypeId::of::<ApplyStatusAction>()
+                || id == TypeId::of::<CureStatusAction>()
+                || id == TypeId::of::<StatusCheckAction>()
+                || id == TypeId::of::<StatusBlockedAction>()
+                || id == TypeId::of::<StatusTickAction>()
+                || id == TypeId::of::<PickUpItemAction>()
+                || id == TypeId::of::<UseItemAction>()
+                || id == TypeId::of::<ThrowItemAction>()
+                || id == TypeId::of::<DropItemAction>()
+                || id == TypeId::of::<RestoreBellyAction>()
+                || id == TypeId::of::<RestorePPAction>()
+                || id == TypeId::of::<PlanAction>()
+                || id == TypeId::of::<ProtectAction>()
+                || id == TypeId::of::<ProtectedAction>()
+                || id == TypeId::of::<ProtectionTickAction>()
+                || id == TypeId::of::<SubstituteHitAction>() =>
+            {
                 ev_animation_finished.send(ActionAnimationFinishedEvent(entity));
                 ev_animation_next.send(ActionAnimationNextEvent(entity));
             }
@@ -4,8 +4,8 @@ use crate::{
     actions::{spell_projectile_action::SpellProjectileAction, RunningAction},
     constants::GAME_SPEED,
     graphics::{
-        animations::Animator, get_world_position, pokemons::offsets::PokemonHeadOffset, EFFECT_Z,
-        POSITION_TOLERANCE, PROJECTILE_SPEED,
+        get_world_position, pokemons::offsets::PokemonHeadOffset, EFFECT_Z, POSITION_TOLERANCE,
+        PROJECTILE_SPEED,
     },
     map::Position,
     visual_effects::VisualEffect,
@@ -64,12 +64,8 @@ fn init_projectile_animation(
         });
         let to = get_world_position(&spell_projectile_action.target, EFFECT_Z);
 
-        commands.spawn((
-            Name::new(spell_projectile_action.projectile.visual_effect.to_string()),
-            VisualEffect {
-                name: spell_projectile_action.projectile.visual_effect,
-                is_loop: true,
-            },
+        let mut projectile = commands.spawn((
+            Name::new("Projectile"),
             SpatialBundle {
                 transform: Transform::from_translation(from),
                 ..default()
@@ -81,18 +77,28 @@ fn init_projectile_animation(
                 t: 0.,
             })),
         ));
+
+        if let Some(visual_effect) = &spell_projectile_action.projectile.visual_effect {
+            projectile.insert((
+                Name::new(visual_effect.clone()),
+                VisualEffect {
+                    name: visual_effect.clone(),
+                    is_loop: true,
+                },
+            ));
+        }
     }
 }
 
 fn projectile_animation(
-    mut query: Query<(Entity, &mut AnimationHolder, &mut Transform, &Animator), With<VisualEffect>>,
+    mut query: Query<(Entity, &mut AnimationHolder, &mut Transform)>,
     time: Res<Time>,
     mut ev_animation_playing: EventWriter<ActionAnimationPlayingEvent>,
     mut ev_animation_finished: EventWriter<ActionAnimationFinishedEvent>,
     mut ev_animation_next: EventWriter<ActionAnimationNextEvent>,
     mut commands: Commands,
 ) {
-    for (entity, mut animation, mut transform, _animator) in query.iter_mut() {
+    for (entity, mut animation, mut transform) in query.iter_mut() {
         let AnimationHolder(ActionAnimation::Projectile(projectile_animation)) = animation.as_mut()
         else {
             continue;
@@ -7,7 +7,7 @@ use crate::{
         pokemons::{offsets::PokemonHeadOffset, PokemonAnimationState},
         visual_effects::AutoDespawnEffect,
     },
-    spells::SpellCast,
+    spells::{SpellCast, SpellType},
     visual_effects::VisualEffect,
 };
 
@@ -41,6 +41,8 @@ fn init_spell_cast_animation(
     query: Query<(Entity, &RunningAction, &Children), Added<RunningAction>>,
     query_head_offset: Query<Entity, With<PokemonHeadOffset>>,
     mut ev_animation_playing: EventWriter<ActionAnimationPlayingEvent>,
+    mut ev_animation_finished: EventWriter<ActionAnimationFinishedEvent>,
+    mut ev_animation_next: EventWriter<ActionAnimationNextEvent>,
     mut commands: Commands,
 ) {
     for (entity, running_action, children) in query.iter() {
@@ -49,6 +51,13 @@ fn init_spell_cast_animation(
             continue;
         };
 
+        // The caster of a dash plays its animation while rushing
+        if let SpellType::Dash { .. } = spell_action.spell.spell_type {
+            ev_animation_finished.send(ActionAnimationFinishedEvent(entity));
+            ev_animation_next.send(ActionAnimationNextEvent(entity));
+            continue;
+        }
+
         let target_entity_cast_effect = children
             .iter()
             .filter_map(|&child| query_head_offset.get(child).ok())
@@ -65,14 +74,18 @@ fn init_spell_cast_animation(
                     spell_cast: spell_action.spell.cast.clone(),
                 },
             )));
+
+        let Some(visual_effect) = &spell_action.spell.cast.visual_effect else {
+            continue;
+        };
         commands
             .entity(target_entity_cast_effect)
             .with_children(|parent| {
                 // Visual Effect
                 parent.spawn((
-                    Name::new(spell_action.spell.cast.visual_effect.to_string()),
+                    Name::new(visual_effect.clone()),
                     VisualEffect {
-                        name: spell_action.spell.cast.visual_effect,
+                        name: visual_effect.clone(),
                         is_loop: false,
                     },
                     AutoDespawnEffect,
@@ -59,9 +59,9 @@ fn init_spell_hit_animation(
             .entity(target_entity_hist_effect)
             .with_children(|parent| {
                 parent.spawn((
-                    Name::new(spell_hit_action.hit.visual_effect.to_string()),
+                    Name::new(spell_hit_action.hit.visual_effect.clone()),
                     VisualEffect {
-                        name: spell_hit_action.hit.visual_effect,
+                        name: spell_hit_action.hit.visual_effect.clone(),
                         is_loop: false,
                     },
                     SpatialBundle {
@@ -0,0 +1,83 @@
+use bevy::prelude::*;
+
+use crate::{
+    actions::{stat_stage_action::StatStageAction, RunningAction},
+    graphics::{pokemons::offsets::PokemonBodyOffset, visual_effects::AutoDespawnEffect},
+    stats::BattleStat,
+    visual_effects::VisualEffect,
+};
+
+use super::{ActionAnimationFinishedEvent, ActionAnimationNextEvent, ActionAnimationSet};
+
+pub struct StatStageAnimationPlugin;
+
+impl Plugin for StatStageAnimationPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            Update,
+            (init_stat_stage_animation).in_set(ActionAnimationSet::Prepare),
+        );
+    }
+}
+
+/// Color of the lines of the effect, one per stat
+fn stat_effect_name(stat: BattleStat) -> &'static str {
+    match stat {
+        BattleStat::Attack => "Stat_Red_Line",
+        BattleStat::Defense => "Stat_Blue_Line",
+        BattleStat::SpecialAttack => "Stat_Pink_Line",
+        BattleStat::SpecialDefense => "Stat_Green_Line",
+        BattleStat::Speed => "Stat_Yellow_Line",
+        BattleStat::Accuracy => "Stat_White_Line",
+        BattleStat::Evasion => "Stat_Purple_Line",
+    }
+}
+
+/// Lines going up when the stat rises and down when it falls, the action doesn't wait for them
+fn init_stat_stage_animation(
+    query: Query<(Entity, &RunningAction), Added<RunningAction>>,
+    query_children: Query<&Children>,
+    query_body_offset: Query<Entity, With<PokemonBodyOffset>>,
+    mut ev_animation_finished: EventWriter<ActionAnimationFinishedEvent>,
+    mut ev_animation_next: EventWriter<ActionAnimationNextEvent>,
+    mut commands: Commands,
+) {
+    for (entity, running_action) in query.iter() {
+        let action = running_action.0.as_any();
+        let Some(stat_stage_action) = action.downcast_ref::<StatStageAction>() else {
+            continue;
+        };
+
+        let target = stat_stage_action.entity;
+        let target_entity_effect = query_children.get(target).map_or(target, |children| {
+            children
+                .iter()
+                .find_map(|&child| query_body_offset.get(child).ok())
+                .unwrap_or(target)
+        });
+
+        // The lines of the effect go up, they are flipped for a stat falling
+        let scale = if stat_stage_action.stages < 0 {
+            Vec3::new(1., -1., 1.)
+        } else {
+            Vec3::ONE
+        };
+        let name = stat_effect_name(stat_stage_action.stat);
+        commands
+            .entity(target_entity_effect)
+            .with_children(|parent| {
+                parent.spawn((
+                    Name::new(name),
+                    VisualEffect {
+                        name: name.to_string(),
+                        is_loop: false,
+                    },
+                    AutoDespawnEffect,
+                    SpatialBundle::from_transform(Transform::from_scale(scale)),
+                ));
+            });
+
+        ev_animation_finished.send(ActionAnimationFinishedEvent(entity));
+        ev_animation_next.send(ActionAnimationNextEvent(entity));
+    }
+}
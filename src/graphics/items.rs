@@ -0,0 +1,65 @@
+use bevy::prelude::*;
+
+use crate::{
+    faction::Faction, fov::FieldOfView, inventory::GroundItem, map::Position,
+    visual_effects::VisualEffect, GameState,
+};
+
+use super::{get_world_position, ITEM_Z};
+
+pub struct ItemsPlugin;
+
+impl Plugin for ItemsPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            Update,
+            (spawn_ground_item_renderer, update_ground_item_visibility)
+                .chain()
+                .run_if(in_state(GameState::Playing)),
+        );
+    }
+}
+
+fn spawn_ground_item_renderer(
+    query: Query<(Entity, &Position, &GroundItem), Added<GroundItem>>,
+    mut commands: Commands,
+) {
+    for (entity, position, ground_item) in query.iter() {
+        commands
+            .entity(entity)
+            .insert(SpatialBundle::from_transform(Transform::from_translation(
+                get_world_position(&position.0, ITEM_Z),
+            )))
+            .with_children(|parent| {
+                parent.spawn((
+                    Name::new("ItemSprite"),
+                    VisualEffect {
+                        name: ground_item.0.sprite.clone(),
+                        is_loop: true,
+                    },
+                    SpatialBundle::default(),
+                ));
+            });
+    }
+}
+
+/// Like the tiles, the items stay visible once explored
+fn update_ground_item_visibility(
+    mut query: Query<(&Position, &mut Visibility), With<GroundItem>>,
+    field_of_view: Res<FieldOfView>,
+) {
+    let Some(vision) = field_of_view.get(&Faction::Player) else {
+        return;
+    };
+
+    for (position, mut visibility) in query.iter_mut() {
+        if !(field_of_view.is_changed() || visibility.is_added()) {
+            continue;
+        }
+        *visibility = if vision.is_explored(&position.0) {
+            Visibility::Inherited
+        } else {
+            Visibility::Hidden
+        };
+    }
+}
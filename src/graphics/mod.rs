@@ -2,22 +2,26 @@ use bevy::prelude::*;
 
 use self::{
     action_animations::ActionAnimationPlugin, animations::AnimationsPlugin,
-    assets::GraphicAssetsPlugin, pokemons::PokemonPlugin, tiles::TilesPlugin, ui::UIPlugin,
-    visual_effects::VisualEffectsPlugin, world_number::WorldNumberPlugin,
+    assets::GraphicAssetsPlugin, items::ItemsPlugin, pokemons::PokemonPlugin, tiles::TilesPlugin,
+    ui::UIPlugin, visual_effects::VisualEffectsPlugin, weather::WeatherPlugin,
+    world_number::WorldNumberPlugin,
 };
 
 pub mod action_animations;
 pub mod animations;
 pub mod assets;
+mod items;
 pub mod pokemons;
 pub mod tile_sprite_index;
 mod tiles;
 pub mod ui;
 mod visual_effects;
+mod weather;
 pub mod world_number;
 
 pub const TILE_Z: f32 = 0.;
 pub const TILE_SIZE: f32 = 24.;
+pub const ITEM_Z: f32 = 5.;
 
 pub const POKEMON_Z: f32 = 10.;
 pub const EFFECT_Z: f32 = 15.;
@@ -42,6 +46,8 @@ impl Plugin for GraphicsPlugin {
             VisualEffectsPlugin,
             UIPlugin,
             WorldNumberPlugin,
+            WeatherPlugin,
+            ItemsPlugin,
         ));
     }
 }
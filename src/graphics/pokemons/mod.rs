@@ -1,12 +1,18 @@
 pub mod offsets;
 mod pokemon_animator;
 mod shadow;
+mod status_icon;
 
 use bevy::prelude::*;
 use char_animation::{anim_key::AnimKey, CharAnimation};
 
 use crate::{
-    map::Position, pieces::FacingOrientation, pokemons::Pokemon, GamePlayingSet, GameState,
+    faction::Faction,
+    fov::FieldOfView,
+    map::{FloorChangedEvent, Position},
+    pieces::FacingOrientation,
+    pokemons::Pokemon,
+    GamePlayingSet, GameState,
 };
 
 use self::{
@@ -16,6 +22,7 @@ use self::{
     },
     pokemon_animator::get_pokemon_animator,
     shadow::{spawn_shadow_renderer, update_shadow_offsets, PokemonShadow},
+    status_icon::update_status_icons,
 };
 
 use super::{
@@ -32,9 +39,17 @@ impl Plugin for PokemonPlugin {
             .add_event::<AnimatorUpdatedEvent>()
             .add_systems(
                 Update,
-                (spawn_pokemon_renderer, spawn_shadow_renderer)
+                (
+                    spawn_pokemon_renderer,
+                    spawn_shadow_renderer,
+                    update_pokemon_renderer,
+                )
                     .run_if(in_state(GameState::Playing)),
             )
+            .add_systems(
+                Update,
+                snap_to_position.run_if(on_event::<FloorChangedEvent>()),
+            )
             .add_systems(
                 Update,
                 (
@@ -50,7 +65,13 @@ impl Plugin for PokemonPlugin {
             )
             .add_systems(
                 Update,
-                (update_offsets, update_shadow_offsets).after(GamePlayingSet::LateLogics),
+                (
+                    update_offsets,
+                    update_shadow_offsets,
+                    update_pokemon_visibility,
+                    update_status_icons,
+                )
+                    .after(GamePlayingSet::LateLogics),
             );
         #[cfg(debug_assertions)]
         {
@@ -160,3 +181,60 @@ fn spawn_pokemon_renderer(
             });
     }
 }
+
+/// Swap the char animation of a pokemon which changed species or form (e.g. after an evolution)
+fn update_pokemon_renderer(
+    mut query: Query<
+        (
+            &Pokemon,
+            &mut Handle<CharAnimation>,
+            &mut PokemonAnimationState,
+        ),
+        Changed<Pokemon>,
+    >,
+    pokemon_char_assets: Res<PokemonCharaAssets>,
+) {
+    for (pokemon, mut char_animation_handle, mut animation_state) in query.iter_mut() {
+        let Some(pokemon_animation_handle) = pokemon_char_assets.0.get(&pokemon.id) else {
+            warn!(
+                "Failed to find char animation for pokemon ID: {}",
+                pokemon.id
+            );
+            continue;
+        };
+        if *char_animation_handle != *pokemon_animation_handle {
+            *char_animation_handle = pokemon_animation_handle.clone();
+        }
+        // Force the animator to be rebuilt, a form-only evolution keeps the char animation
+        animation_state.set_changed();
+    }
+}
+
+/// Hide the pokemons out of the field of view of the player
+fn update_pokemon_visibility(
+    mut query: Query<(&Position, &Faction, &mut Visibility), With<Pokemon>>,
+    field_of_view: Res<FieldOfView>,
+) {
+    let Some(vision) = field_of_view.get(&Faction::Player) else {
+        return;
+    };
+
+    for (position, faction, mut visibility) in query.iter_mut() {
+        // Also check the pokemons which just got their renderer
+        if *faction == Faction::Player || !(field_of_view.is_changed() || visibility.is_added()) {
+            continue;
+        }
+        *visibility = if vision.is_visible(&position.0) {
+            Visibility::Inherited
+        } else {
+            Visibility::Hidden
+        };
+    }
+}
+
+/// Place the pokemons on their grid position without animation (e.g. after a floor change)
+fn snap_to_position(mut query: Query<(&Position, &mut Transform), With<Pokemon>>) {
+    for (position, mut transform) in query.iter_mut() {
+        transform.translation = super::get_world_position(&position.0, POKEMON_Z);
+    }
+}
@@ -26,11 +26,13 @@ impl PokemonShadow {
         terrain: &TerrainData,
     ) -> Vec<AnimationFrame> {
         let row = match terrain.r#type {
-            crate::map::TerrainType::Ground => 1,
+            crate::map::TerrainType::Ground | crate::map::TerrainType::Stairs => 1,
             crate::map::TerrainType::Wall => 0,
             crate::map::TerrainType::Environment(env_type) => match env_type {
                 crate::map::EnvironmentType::Water => 3,
                 crate::map::EnvironmentType::Lava => 4,
+                // Nothing to cast a shadow on
+                crate::map::EnvironmentType::Abyss => 0,
             },
         };
         let faction = match faction {
@@ -0,0 +1,59 @@
+use bevy::prelude::*;
+
+use crate::{
+    status::{StatusCondition, StatusConditions},
+    visual_effects::VisualEffect,
+};
+
+use super::offsets::PokemonHeadOffset;
+
+/// Visual effect over the head of a pokemon while it has the status
+#[derive(Component)]
+pub struct StatusIcon(pub StatusCondition);
+
+pub fn update_status_icons(
+    query: Query<(Entity, &StatusConditions), Changed<StatusConditions>>,
+    query_children: Query<&Children>,
+    query_head_offset: Query<Entity, With<PokemonHeadOffset>>,
+    query_icons: Query<&StatusIcon>,
+    mut commands: Commands,
+) {
+    for (entity, status_conditions) in query.iter() {
+        let Some(head_offset) = query_children.get(entity).ok().and_then(|children| {
+            children
+                .iter()
+                .find_map(|&child| query_head_offset.get(child).ok())
+        }) else {
+            continue;
+        };
+
+        let mut displayed = vec![];
+        for &child in query_children.get(head_offset).into_iter().flatten() {
+            let Ok(icon) = query_icons.get(child) else {
+                continue;
+            };
+            if status_conditions.has(icon.0) {
+                displayed.push(icon.0);
+            } else {
+                commands.entity(child).despawn_recursive();
+            }
+        }
+
+        commands.entity(head_offset).with_children(|parent| {
+            for status in status_conditions.0.iter() {
+                if displayed.contains(&status.condition) {
+                    continue;
+                }
+                parent.spawn((
+                    Name::new("StatusIcon"),
+                    StatusIcon(status.condition),
+                    VisualEffect {
+                        name: status.condition.icon().to_string(),
+                        is_loop: true,
+                    },
+                    SpatialBundle::default(),
+                ));
+            }
+        });
+    }
+}
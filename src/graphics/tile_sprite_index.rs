@@ -2,7 +2,7 @@ use std::collections::HashMap;
 
 use bevy::prelude::*;
 
-use crate::map::TerrainData;
+use crate::map::{TerrainData, TerrainType};
 
 const ROW: usize = 21;
 
@@ -84,10 +84,19 @@ static PATTERNS: &[([[i8; 3]; 3], usize); 47] = &[
     ([[O, O, X], [O, O, O], [X, O, O]], 1 + ROW * 23),
 ];
 
+/// Terrain types sharing the same tile set, the stairs are drawn on top of the ground
+fn tile_set(terrain_data: &TerrainData) -> TerrainType {
+    match terrain_data.r#type {
+        TerrainType::Stairs => TerrainType::Ground,
+        terrain_type => terrain_type,
+    }
+}
+
 /// Check if the position match any pattern in PATTERNS (first tuple element) then returns the associated index (present in the second of the tuple).
 /// Pattern encoding, 0 = no element or terrain_data different from the tested one, 1 = same tile type
 pub fn find_sprite_index_tile(position: &IVec2, map: &HashMap<IVec2, TerrainData>) -> usize {
     let terrain_data = map.get(position).unwrap();
+    let terrain_tile_set = tile_set(terrain_data);
 
     for (pattern, index) in PATTERNS {
         let mut pattern_match = true;
@@ -98,10 +107,10 @@ pub fn find_sprite_index_tile(position: &IVec2, map: &HashMap<IVec2, TerrainData
                     y: position.y - dy as i32 + 1, // axis is inverted on bevy
                 };
 
-                let neighbor_type = map.get(&neighbor_position);
+                let neighbor_type = map.get(&neighbor_position).map(tile_set);
 
-                if value != U && (value == O && neighbor_type != Some(&terrain_data))
-                    || (value == X && neighbor_type == Some(&terrain_data))
+                if value != U && (value == O && neighbor_type != Some(terrain_tile_set))
+                    || (value == X && neighbor_type == Some(terrain_tile_set))
                 {
                     pattern_match = false;
                     break;
@@ -130,10 +139,10 @@ pub fn find_sprite_index_tile(position: &IVec2, map: &HashMap<IVec2, TerrainData
                 y: position.y + dy - 1,
             };
 
-            let neighbor_type = map.get(&neighbor_position);
+            let neighbor_type = map.get(&neighbor_position).map(tile_set);
 
             // X: No neighbor
-            if neighbor_type.is_none() || neighbor_type != Some(&terrain_data) {
+            if neighbor_type.is_none() || neighbor_type != Some(terrain_tile_set) {
                 print!("X ");
                 continue;
             }
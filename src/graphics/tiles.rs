@@ -1,12 +1,18 @@
 use bevy::prelude::*;
 
 use crate::{
+    faction::Faction,
+    fov::FieldOfView,
     map::{GameMap, Position, TerrainType, Tile},
+    visual_effects::VisualEffect,
     GameState,
 };
 
 use super::{assets::TileAssets, tile_sprite_index::find_sprite_index_tile, TILE_SIZE, TILE_Z};
 
+// Color of the explored tiles which are out of the field of view of the player
+const REMEMBERED_TILE_COLOR: Color = Color::rgb(0.45, 0.45, 0.5);
+
 pub struct TilesPlugin;
 
 impl Plugin for TilesPlugin {
@@ -20,15 +26,41 @@ impl Plugin for TilesPlugin {
     }
 }
 
+/// Unexplored tiles are hidden and the explored ones out of view are dimmed
+fn tile_fog(position: &IVec2, field_of_view: &FieldOfView) -> (Color, Visibility) {
+    let Some(vision) = field_of_view.get(&Faction::Player) else {
+        return (Color::WHITE, Visibility::Hidden);
+    };
+
+    if vision.is_visible(position) {
+        (Color::WHITE, Visibility::Inherited)
+    } else if vision.is_explored(position) {
+        (REMEMBERED_TILE_COLOR, Visibility::Inherited)
+    } else {
+        (Color::WHITE, Visibility::Hidden)
+    }
+}
+
 pub fn update_tile_render(
     query: Query<(Entity, &Position, Ref<Tile>)>,
     mut query_tile: Query<(&Tile, &mut TextureAtlas)>,
+    mut query_fog: Query<(&Position, &mut Sprite, &mut Visibility), With<Tile>>,
     map: Res<GameMap>,
+    field_of_view: Res<FieldOfView>,
     mut commands: Commands,
     assets: Res<TileAssets>,
 ) {
+    if field_of_view.is_changed() {
+        for (position, mut sprite, mut visibility) in query_fog.iter_mut() {
+            let (color, fog_visibility) = tile_fog(&position.0, &field_of_view);
+            sprite.color = color;
+            *visibility = fog_visibility;
+        }
+    }
+
     for (entity, position, tile) in query.iter() {
         if tile.is_added() || tile.is_changed() {
+            let (color, visibility) = tile_fog(&position.0, &field_of_view);
             let sprite_index = get_tile_map_index(&position.0, &tile.0, &map);
             let atlas = TextureAtlas {
                 index: sprite_index,
@@ -39,13 +71,28 @@ pub fn update_tile_render(
             commands.entity(entity).insert(SpriteSheetBundle {
                 sprite: Sprite {
                     custom_size: Some(Vec2::splat(TILE_SIZE)),
+                    color,
                     ..default()
                 },
                 atlas,
                 texture: assets.amp_plains_texture.clone(),
                 transform: Transform::from_translation(v),
+                visibility,
                 ..Default::default()
             });
+
+            if tile.is_added() && tile.0.r#type == TerrainType::Stairs {
+                commands.entity(entity).with_children(|parent| {
+                    parent.spawn((
+                        Name::new("Stairs"),
+                        VisualEffect {
+                            name: "Stair_Sensor_Arrow".to_string(),
+                            is_loop: true,
+                        },
+                        SpatialBundle::from_transform(Transform::from_xyz(0., 0., 1.)),
+                    ));
+                });
+            }
         }
         if !tile.is_added() && tile.is_changed() {
             // If a tile is changed we need to update their neighbors
@@ -69,7 +116,9 @@ pub fn update_tile_render(
 
 fn get_tile_map_index(position: &IVec2, terrain_type: &TerrainType, map: &GameMap) -> usize {
     match terrain_type {
-        TerrainType::Ground => find_sprite_index_tile(position, &map.tiles) + 4 * 3,
+        TerrainType::Ground | TerrainType::Stairs => {
+            find_sprite_index_tile(position, &map.tiles) + 4 * 3
+        }
         TerrainType::Wall => find_sprite_index_tile(position, &map.tiles) + 3,
         TerrainType::Environment(_) => find_sprite_index_tile(position, &map.tiles) + 8 * 3,
     }
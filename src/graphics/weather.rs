@@ -0,0 +1,60 @@
+use bevy::prelude::*;
+use common::map_status::MapStatus;
+
+use crate::{map_status::MapStatuses, GameState};
+
+pub struct WeatherPlugin;
+
+impl Plugin for WeatherPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(OnEnter(GameState::Initializing), spawn_weather_overlay)
+            .add_systems(
+                Update,
+                update_weather_overlay.run_if(in_state(GameState::Playing)),
+            );
+    }
+}
+
+/// Full screen tint showing the current weather
+#[derive(Component)]
+struct WeatherOverlay;
+
+fn weather_color(weather: MapStatus) -> Color {
+    match weather {
+        MapStatus::Rain | MapStatus::HeavyRain => Color::rgba(0.1, 0.2, 0.5, 0.2),
+        MapStatus::Sunny | MapStatus::HarshSun => Color::rgba(1., 0.8, 0.3, 0.15),
+        MapStatus::Sandstorm => Color::rgba(0.7, 0.5, 0.2, 0.25),
+        MapStatus::Hail | MapStatus::Snow | MapStatus::Blizzard => Color::rgba(0.8, 0.9, 1., 0.2),
+        MapStatus::Fog | MapStatus::Cloudy => Color::rgba(0.6, 0.6, 0.6, 0.3),
+        _ => Color::NONE,
+    }
+}
+
+fn spawn_weather_overlay(mut commands: Commands) {
+    commands.spawn((
+        Name::new("WeatherOverlay"),
+        WeatherOverlay,
+        NodeBundle {
+            style: Style {
+                position_type: PositionType::Absolute,
+                width: Val::Percent(100.0),
+                height: Val::Percent(100.0),
+                ..default()
+            },
+            background_color: Color::NONE.into(),
+            ..default()
+        },
+    ));
+}
+
+fn update_weather_overlay(
+    map_statuses: Res<MapStatuses>,
+    mut query: Query<&mut BackgroundColor, With<WeatherOverlay>>,
+) {
+    if !map_statuses.is_changed() {
+        return;
+    }
+    for mut background_color in query.iter_mut() {
+        background_color.0 = weather_color(map_statuses.weather());
+    }
+}
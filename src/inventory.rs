@@ -0,0 +1,407 @@
+//! Items lying on the floor and the bag of the player
+
+use std::ops::RangeInclusive;
+
+use bevy::prelude::*;
+use item_data::{ItemData, ItemEffect};
+use rand::{rngs::StdRng, seq::SliceRandom, Rng, SeedableRng};
+
+use crate::{
+    actions::{
+        cure_status_action::CureStatusAction, heal_action::clamped_heal_action,
+        pick_up_item_action::PickUpItemAction, restore_belly_action::RestoreBellyAction,
+        restore_pp_action::RestorePPAction, Action, ActionQueue, QueuedAction,
+    },
+    data::assets::item_data::ItemDataLookup,
+    map::{Dungeon, FloorChangedEvent, GameMap, Position, TerrainType},
+    status::StatusConditions,
+    GamePlayingSet, GameState,
+};
+
+/// Maximum number of items in the bag
+pub const BAG_CAPACITY: usize = 16;
+// Number of items lying on each floor
+const FLOOR_ITEMS: RangeInclusive<usize> = 3..=5;
+const FLOOR_ITEM_POOL: [&str; 4] = ["oran_berry", "apple", "heal_seed", "elixir"];
+// Keeps the item rolls apart from the layout of the floor
+const ITEMS_SEED_SALT: u64 = 2;
+
+pub struct InventoryPlugin;
+
+impl Plugin for InventoryPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<BagMenu>()
+            .add_event::<BagFullEvent>()
+            .add_systems(OnEnter(GameState::Playing), spawn_ground_items)
+            .add_systems(
+                Update,
+                spawn_ground_items.run_if(on_event::<FloorChangedEvent>()),
+            )
+            .add_systems(
+                Update,
+                pick_up_ground_items.in_set(GamePlayingSet::LateLogics),
+            );
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Item {
+    pub id: String,
+    pub name: String,
+    pub sprite: String,
+    pub effect: ItemEffect,
+}
+
+impl Item {
+    /// Build an item from the loaded item data with the given ID
+    pub fn load(id: &str, lookup: &ItemDataLookup, assets: &Assets<ItemData>) -> Option<Self> {
+        let Some(data) = lookup.0.get(id).and_then(|handle| assets.get(handle)) else {
+            warn!("Failed to find item data for {id}");
+            return None;
+        };
+
+        Some(Self {
+            id: id.to_string(),
+            name: data.name.default_text.clone(),
+            sprite: data.sprite.clone(),
+            effect: data.effect,
+        })
+    }
+}
+
+/// Items carried by the piece, at most `BAG_CAPACITY`
+#[derive(Component, Debug, Default, Clone)]
+pub struct Inventory(pub Vec<Item>);
+
+impl Inventory {
+    pub fn is_full(&self) -> bool {
+        self.0.len() >= BAG_CAPACITY
+    }
+
+    /// Put the item in the bag, it is given back when the bag is full
+    pub fn add(&mut self, item: Item) -> Result<(), Item> {
+        if self.is_full() {
+            return Err(item);
+        }
+        self.0.push(item);
+        Ok(())
+    }
+
+    /// Take the item out of the bag slot
+    pub fn remove(&mut self, slot: usize) -> Option<Item> {
+        (slot < self.0.len()).then(|| self.0.remove(slot))
+    }
+}
+
+/// Sent when a piece walks onto an item without any room left in its bag
+#[derive(Event, Debug)]
+pub struct BagFullEvent {
+    pub entity: Entity,
+    pub item: Item,
+}
+
+/// Item lying on the tile of its `Position`
+#[derive(Component, Debug, Clone)]
+pub struct GroundItem(pub Item);
+
+/// State of the bag menu of the player, the directions select an item while it is open
+#[derive(Resource, Debug, Default)]
+pub struct BagMenu {
+    pub is_open: bool,
+    pub selected: usize,
+}
+
+/// Actions applying the effect of the item to the target, executed one after the other
+pub fn item_effect_actions(
+    world: &World,
+    effect: ItemEffect,
+    target: Entity,
+) -> Vec<Box<dyn Action>> {
+    match effect {
+        ItemEffect::Heal { value } => clamped_heal_action(world, target, value)
+            .map(|action| Box::new(action) as Box<dyn Action>)
+            .into_iter()
+            .collect(),
+        ItemEffect::Food { belly } => vec![Box::new(RestoreBellyAction {
+            entity: target,
+            value: belly,
+        })],
+        ItemEffect::CureStatus => world
+            .get::<StatusConditions>(target)
+            .map(|status_conditions| {
+                status_conditions
+                    .0
+                    .iter()
+                    .map(|status| {
+                        Box::new(CureStatusAction {
+                            entity: target,
+                            condition: status.condition,
+                        }) as Box<dyn Action>
+                    })
+                    .collect()
+            })
+            .unwrap_or_default(),
+        ItemEffect::RestorePP { value } => vec![Box::new(RestorePPAction {
+            entity: target,
+            value,
+        })],
+    }
+}
+
+/// Queue the effect of the item right after the current action
+pub fn queue_item_effect(world: &mut World, effect: ItemEffect, target: Entity) {
+    let actions = item_effect_actions(world, effect, target);
+    let mut action_queue = world.resource_mut::<ActionQueue>();
+    for action in actions.into_iter().rev() {
+        action_queue.0.push_front(QueuedAction {
+            entity: target,
+            performable_actions: vec![action],
+        });
+    }
+}
+
+/// Components of an item lying on the floor
+fn ground_item_bundle(item: Item, position: IVec2) -> (Name, GroundItem, Position) {
+    (
+        Name::new(format!("Item {}", item.name)),
+        GroundItem(item),
+        Position(position),
+    )
+}
+
+pub fn spawn_ground_item(world: &mut World, item: Item, position: IVec2) {
+    world.spawn(ground_item_bundle(item, position));
+}
+
+/// Scatter a few items in the rooms of the floor, never under the player
+fn spawn_ground_items(
+    dungeon: Res<Dungeon>,
+    map: Res<GameMap>,
+    item_data_lookup: Res<ItemDataLookup>,
+    item_data_assets: Res<Assets<ItemData>>,
+    mut commands: Commands,
+) {
+    let mut rng = StdRng::seed_from_u64(dungeon.floor_sub_seed(ITEMS_SEED_SALT));
+    let player_spawn = map.player_spawn();
+
+    let mut positions = map
+        .rooms
+        .iter()
+        .flat_map(|room| room.tiles())
+        .filter(|position| *position != player_spawn)
+        .filter(|position| {
+            map.tiles
+                .get(position)
+                .is_some_and(|tile| tile.r#type == TerrainType::Ground)
+        })
+        .collect::<Vec<_>>();
+    positions.shuffle(&mut rng);
+
+    let count = rng.gen_range(FLOOR_ITEMS);
+    for position in positions.into_iter().take(count) {
+        let Some(item) = FLOOR_ITEM_POOL
+            .choose(&mut rng)
+            .and_then(|id| Item::load(id, &item_data_lookup, &item_data_assets))
+        else {
+            continue;
+        };
+        commands.spawn(ground_item_bundle(item, position));
+    }
+}
+
+/// Walking onto an item puts it in the bag once the walk is over, it stays on the ground when
+/// the bag is full
+fn pick_up_ground_items(
+    query: Query<(Entity, &Position, &Inventory), Changed<Position>>,
+    query_items: Query<(Entity, &Position, &GroundItem)>,
+    mut action_queue: ResMut<ActionQueue>,
+    mut ev_bag_full: EventWriter<BagFullEvent>,
+) {
+    for (entity, position, inventory) in query.iter() {
+        let Some((item_entity, _, ground_item)) = query_items
+            .iter()
+            .find(|(_, item_position, _)| item_position.0 == position.0)
+        else {
+            continue;
+        };
+        if inventory.is_full() {
+            ev_bag_full.send(BagFullEvent {
+                entity,
+                item: ground_item.0.clone(),
+            });
+            continue;
+        }
+        action_queue.0.push_front(QueuedAction {
+            entity,
+            performable_actions: vec![Box::new(PickUpItemAction {
+                entity,
+                ground_item: item_entity,
+                item: ground_item.0.clone(),
+            })],
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use char_animation::orientation::Orientation;
+
+    use crate::{
+        actions::{
+            drop_item_action::DropItemAction, throw_item_action::ThrowItemAction,
+            use_item_action::UseItemAction,
+        },
+        pieces::FacingOrientation,
+        stats::Health,
+    };
+
+    use super::*;
+
+    fn item(id: &str) -> Item {
+        Item {
+            id: id.to_string(),
+            name: id.to_string(),
+            sprite: String::new(),
+            effect: ItemEffect::Food { belly: 50 },
+        }
+    }
+
+    /// A piece at the left end of a corridor facing right, with the items in its bag
+    fn setup(items: Vec<Item>) -> (World, Entity) {
+        let mut world = World::new();
+        world.insert_resource(ActionQueue::default());
+        world.insert_resource(GameMap::from_rows(&["#######", "#.....#", "#######"]));
+        let entity = world
+            .spawn((
+                Inventory(items),
+                Position(IVec2::new(1, 1)),
+                FacingOrientation(Orientation::Est),
+            ))
+            .id();
+        (world, entity)
+    }
+
+    fn ground_items(world: &mut World) -> Vec<(String, IVec2)> {
+        world
+            .query::<(&GroundItem, &Position)>()
+            .iter(world)
+            .map(|(ground_item, position)| (ground_item.0.id.clone(), position.0))
+            .collect()
+    }
+
+    /// Target of the effect queued first
+    fn restored_belly(world: &World) -> Option<(Entity, u32)> {
+        world
+            .resource::<ActionQueue>()
+            .0
+            .front()
+            .and_then(|queued| {
+                queued.performable_actions[0]
+                    .as_any()
+                    .downcast_ref::<RestoreBellyAction>()
+                    .map(|action| (action.entity, action.value))
+            })
+    }
+
+    #[test]
+    fn fills_and_empties_the_bag() {
+        let mut inventory = Inventory::default();
+        for _ in 0..BAG_CAPACITY {
+            assert!(inventory.add(item("apple")).is_ok());
+        }
+
+        assert!(inventory.is_full());
+        // The item is given back when there is no room left
+        assert_eq!(inventory.add(item("elixir")).unwrap_err().id, "elixir");
+        assert_eq!(inventory.remove(0).unwrap().id, "apple");
+        assert!(!inventory.is_full());
+        assert!(inventory.remove(BAG_CAPACITY).is_none());
+    }
+
+    #[test]
+    fn uses_the_item_on_the_piece() {
+        let (mut world, entity) = setup(vec![item("apple")]);
+
+        // The slot must still hold the same item
+        assert!(!UseItemAction {
+            entity,
+            slot: 0,
+            item: item("elixir"),
+        }
+        .can_execute(&mut world));
+
+        UseItemAction {
+            entity,
+            slot: 0,
+            item: item("apple"),
+        }
+        .execute(&mut world)
+        .unwrap();
+
+        assert!(world.get::<Inventory>(entity).unwrap().0.is_empty());
+        assert_eq!(restored_belly(&world), Some((entity, 50)));
+    }
+
+    #[test]
+    fn drops_a_single_item_per_tile() {
+        let (mut world, entity) = setup(vec![item("apple"), item("elixir")]);
+
+        DropItemAction {
+            entity,
+            slot: 0,
+            item: item("apple"),
+        }
+        .execute(&mut world)
+        .unwrap();
+
+        assert_eq!(
+            ground_items(&mut world),
+            vec![("apple".to_string(), IVec2::new(1, 1))]
+        );
+        assert!(!DropItemAction {
+            entity,
+            slot: 0,
+            item: item("elixir"),
+        }
+        .can_execute(&mut world));
+    }
+
+    #[test]
+    fn throws_the_item_to_the_first_pokemon() {
+        let (mut world, entity) = setup(vec![item("apple")]);
+        let target = world
+            .spawn((Health { value: 10, max: 10 }, Position(IVec2::new(4, 1))))
+            .id();
+
+        ThrowItemAction {
+            entity,
+            slot: 0,
+            item: item("apple"),
+        }
+        .execute(&mut world)
+        .unwrap();
+
+        assert!(world.get::<Inventory>(entity).unwrap().0.is_empty());
+        assert_eq!(restored_belly(&world), Some((target, 50)));
+        assert!(ground_items(&mut world).is_empty());
+    }
+
+    #[test]
+    fn lands_the_thrown_item_before_the_wall() {
+        let (mut world, entity) = setup(vec![item("apple")]);
+
+        ThrowItemAction {
+            entity,
+            slot: 0,
+            item: item("apple"),
+        }
+        .execute(&mut world)
+        .unwrap();
+
+        assert!(restored_belly(&world).is_none());
+        assert_eq!(
+            ground_items(&mut world),
+            vec![("apple".to_string(), IVec2::new(5, 1))]
+        );
+    }
+}
@@ -6,28 +6,37 @@ use std::{
 use bevy::{log::warn, math::IVec2};
 use char_animation::orientation::Orientation;
 
+/// 8-way A* path finder, a diagonal step is skipped when `is_corner_blocked` rejects it
 pub fn find_path(
     start: IVec2,
     end: IVec2,
     tiles: &HashSet<IVec2>,
     blockers: &HashSet<IVec2>,
+    is_corner_blocked: impl Fn(IVec2, IVec2) -> bool,
 ) -> Option<VecDeque<IVec2>> {
     let mut queue = BinaryHeap::new();
-    queue.push(Node { v: start, cost: 0 });
+    queue.push(Node {
+        v: start,
+        cost: start.chebyshev(end) as u32,
+    });
     let mut visited = HashMap::new();
     visited.insert(start, 0);
     let mut came_from = HashMap::new();
 
-    while let Some(Node { v, cost }) = queue.pop() {
+    while let Some(Node { v, .. }) = queue.pop() {
         if v == end {
             break;
         }
-        for dir in ORTHO_DIRECTIONS {
+        let cost = visited[&v];
+        for dir in DIRECTIONS {
             let n = v + dir;
             let new_cost = cost + 1;
             if !tiles.contains(&n) {
                 continue;
             }
+            if dir.is_diagonal() && is_corner_blocked(v, n) {
+                continue;
+            }
             // we allow the target to be a blocker
             if blockers.contains(&n) && n != end {
                 continue;
@@ -36,9 +45,10 @@ pub fn find_path(
                 Some(c) if *c <= new_cost => (),
                 _ => {
                     visited.insert(n, new_cost);
+                    // The chebyshev distance never overestimates the remaining steps
                     queue.push(Node {
                         v: n,
-                        cost: new_cost,
+                        cost: new_cost + n.chebyshev(end) as u32,
                     });
                     came_from.insert(n, v);
                 }
@@ -86,6 +96,9 @@ pub trait IVec2Ext {
     fn partial_cmp(&self, other: &Self) -> Option<Ordering>;
     fn cmp(&self, other: &Self) -> Ordering;
     fn manhattan(&self, other: IVec2) -> i32;
+    /// Number of steps between two positions when moving in 8 directions
+    fn chebyshev(&self, other: IVec2) -> i32;
+    fn is_diagonal(&self) -> bool;
 
     const UP: IVec2 = IVec2 { x: 0, y: 1 };
     const DOWN: IVec2 = IVec2 { x: 0, y: -1 };
@@ -130,6 +143,14 @@ impl IVec2Ext for IVec2 {
     fn manhattan(&self, other: IVec2) -> i32 {
         (self.x - other.x).abs() + (self.y - other.y).abs()
     }
+
+    fn chebyshev(&self, other: IVec2) -> i32 {
+        (self.x - other.x).abs().max((self.y - other.y).abs())
+    }
+
+    fn is_diagonal(&self) -> bool {
+        self.x != 0 && self.y != 0
+    }
 }
 
 pub trait OrientationExt {
@@ -139,7 +160,7 @@ pub trait OrientationExt {
 
 impl OrientationExt for Orientation {
     fn from_vector(direction: IVec2) -> Self {
-        match direction.normalize() {
+        match direction.clamp(IVec2::NEG_ONE, IVec2::ONE) {
             IVec2 { x: 0, y: -1 } => Orientation::South,
             IVec2 { x: 1, y: -1 } => Orientation::SouthEst,
             IVec2 { x: 1, y: 0 } => Orientation::Est,
@@ -169,4 +190,81 @@ impl OrientationExt for Orientation {
     }
 }
 
-pub const ORTHO_DIRECTIONS: [IVec2; 4] = [IVec2::UP, IVec2::DOWN, IVec2::LEFT, IVec2::RIGHT];
+/// Orthogonal directions first, then the diagonal ones
+pub const DIRECTIONS: [IVec2; 8] = [
+    IVec2::UP,
+    IVec2::DOWN,
+    IVec2::LEFT,
+    IVec2::RIGHT,
+    IVec2 { x: 1, y: 1 },
+    IVec2 { x: 1, y: -1 },
+    IVec2 { x: -1, y: 1 },
+    IVec2 { x: -1, y: -1 },
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn grid(width: i32, height: i32) -> HashSet<IVec2> {
+        (0..width)
+            .flat_map(|x| (0..height).map(move |y| IVec2::new(x, y)))
+            .collect()
+    }
+
+    #[test]
+    fn finds_the_shortest_path() {
+        let cases = [
+            (
+                "straight line",
+                grid(4, 1),
+                HashSet::new(),
+                IVec2::new(3, 0),
+                Some(vec![IVec2::new(1, 0), IVec2::new(2, 0), IVec2::new(3, 0)]),
+            ),
+            (
+                "diagonal shortcut",
+                grid(3, 3),
+                HashSet::new(),
+                IVec2::new(2, 2),
+                Some(vec![IVec2::new(1, 1), IVec2::new(2, 2)]),
+            ),
+            (
+                "blocked corner",
+                grid(2, 2)
+                    .into_iter()
+                    .filter(|tile| *tile != IVec2::new(1, 0))
+                    .collect(),
+                HashSet::new(),
+                IVec2::new(1, 1),
+                Some(vec![IVec2::new(0, 1), IVec2::new(1, 1)]),
+            ),
+            (
+                "blocker as target",
+                grid(3, 1),
+                HashSet::from([IVec2::new(2, 0)]),
+                IVec2::new(2, 0),
+                Some(vec![IVec2::new(1, 0), IVec2::new(2, 0)]),
+            ),
+            (
+                "no path",
+                grid(3, 1),
+                HashSet::from([IVec2::new(1, 0)]),
+                IVec2::new(2, 0),
+                None,
+            ),
+        ];
+
+        for (name, tiles, blockers, end, expected) in cases {
+            // A missing tile acts as a wall
+            let is_corner_blocked = |from: IVec2, to: IVec2| {
+                !tiles.contains(&IVec2::new(to.x, from.y))
+                    || !tiles.contains(&IVec2::new(from.x, to.y))
+            };
+
+            let path = find_path(IVec2::ZERO, end, &tiles, &blockers, is_corner_blocked);
+
+            assert_eq!(path.map(Vec::from), expected, "{name}");
+        }
+    }
+}
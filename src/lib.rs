@@ -2,6 +2,7 @@ use actions::ActionsPlugin;
 use ai::AIPlugin;
 use bevy::app::App;
 
+use belly::BellyPlugin;
 use bevy::prelude::*;
 use bevy_egui::{EguiPlugin, EguiSettings};
 #[cfg(debug_assertions)]
@@ -10,37 +11,62 @@ use bitmap_font::BitmapFontPlugin;
 use camera::CameraPlugin;
 use char_animation::CharAnimationPlugin;
 use data::DataPlugin;
+use evolution::EvolutionPlugin;
+use fov::FovPlugin;
 use graphics::GraphicsPlugin;
+use inventory::InventoryPlugin;
+use item_data::ItemDataPlugin;
 use loading::LoadingPlugin;
 use pokemon_data::PokemonDataPlugin;
 use pokemons::PokemonsPlugin;
+use protection::ProtectionPlugin;
+use regeneration::RegenerationPlugin;
+use spell_data::SpellDataPlugin;
 use stats::StatsPlugin;
+use status::StatusPlugin;
+use terrain::TerrainPlugin;
 use test::TestPlugin;
 use ui::UIPlugin;
 use visual_effects::VisualEffectsPlugin;
 
 use map::MapPlugin;
+use map_status::MapStatusPlugin;
 use menu::MenuPlugin;
+use move_set::MoveSetPlugin;
 use pieces::PiecesPlugin;
 use player::{PlayerActionEvent, PlayerPlugin};
 use turn::TurnPlugin;
 
 mod actions;
 mod ai;
+mod belly;
 mod camera;
 mod constants;
+mod damage;
 mod data;
+mod evolution;
+mod experience;
 mod faction;
+mod fov;
 mod graphics;
+mod inventory;
 mod ivec2;
 pub mod loading;
 mod map;
+mod map_status;
 mod menu;
+mod move_set;
 mod pieces;
 mod player;
 mod pokemons;
+mod protection;
+mod regeneration;
+mod rng;
+mod room;
 pub mod spells;
 mod stats;
+mod status;
+mod terrain;
 mod test;
 mod turn;
 mod ui;
@@ -114,7 +140,17 @@ impl Plugin for GamePlugin {
                 TestPlugin,
             ))
             .add_plugins((
+                SpellDataPlugin,
+                ItemDataPlugin,
                 StatsPlugin,
+                StatusPlugin,
+                MoveSetPlugin,
+                EvolutionPlugin,
+                FovPlugin,
+                MapStatusPlugin,
+                TerrainPlugin,
+                InventoryPlugin,
+                (BellyPlugin, RegenerationPlugin, ProtectionPlugin),
                 DataPlugin,
                 LoadingPlugin,
                 PokemonsPlugin,
@@ -1,52 +1,138 @@
 use bevy::prelude::*;
-use std::collections::HashMap;
+use rand::{thread_rng, Rng};
+use std::collections::{HashMap, HashSet};
 
-use crate::GameState;
+use crate::{
+    rng::GameRng,
+    room::{FloorGenerator, Room},
+    terrain::Mobility,
+    GameState,
+};
+
+// Environment variable replaying a run with a fixed seed
+const SEED_ENV_VAR: &str = "DUNGEON_SEED";
 
 pub struct MapPlugin;
 
 impl Plugin for MapPlugin {
     fn build(&self, app: &mut App) {
-        app.insert_resource(GameMap::new())
+        let seed = run_seed();
+        info!("Dungeon seed: {seed}");
+
+        let dungeon = Dungeon::new(seed);
+
+        app.insert_resource(GameMap::generate(dungeon.floor_seed()))
+            .insert_resource(dungeon)
+            .insert_resource(GameRng::seeded(seed))
+            .add_event::<FloorChangedEvent>()
             .add_systems(OnEnter(GameState::Playing), spawn_map);
     }
 }
 
+/// Seed of the run read from the environment, a random one if it is missing or invalid
+fn run_seed() -> u64 {
+    let Ok(value) = std::env::var(SEED_ENV_VAR) else {
+        return thread_rng().gen();
+    };
+    value.trim().parse().unwrap_or_else(|_| {
+        warn!("Invalid {SEED_ENV_VAR} value: {value}, using a random seed");
+        thread_rng().gen()
+    })
+}
+
+/// Current dungeon run, every floor seed is derived from the run seed
+#[derive(Resource, Debug)]
+pub struct Dungeon {
+    pub seed: u64,
+    pub floor: u32,
+}
+
+impl Dungeon {
+    pub fn new(seed: u64) -> Self {
+        Self { seed, floor: 1 }
+    }
+
+    pub fn floor_seed(&self) -> u64 {
+        self.seed ^ (self.floor as u64).wrapping_mul(0x9E37_79B9_7F4A_7C15)
+    }
+
+    /// Seed of a random roll of the floor other than its layout, each salt gets its own numbers
+    pub fn floor_sub_seed(&self, salt: u64) -> u64 {
+        mix_seed(mix_seed(self.floor_seed()) ^ salt)
+    }
+}
+
+/// SplitMix64 finalizer, close inputs give unrelated seeds
+fn mix_seed(seed: u64) -> u64 {
+    let mut z = seed;
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    z ^ (z >> 31)
+}
+
+/// Sent once the next floor has been generated and spawned
+#[derive(Event, Debug)]
+pub struct FloorChangedEvent {
+    pub floor: u32,
+}
+
 #[derive(Default, Resource)]
 pub struct GameMap {
     pub tiles: HashMap<IVec2, TerrainData>,
     pub tiles_lookup: HashMap<IVec2, Entity>,
+    pub rooms: Vec<Room>,
 }
 
 impl GameMap {
-    pub fn new() -> Self {
-        let mut tiles: HashMap<IVec2, TerrainData> = HashMap::new();
-
-        for x in 4..20 {
-            for y in 1..20 {
-                let position = IVec2::new(x, y);
-                tiles.insert(
-                    position,
-                    TerrainData {
-                        r#type: TerrainType::Ground,
-                    },
-                );
-            }
-        }
-
-        for x in 0..11 {
-            for y in 0..22 {
-                let position = IVec2::new(x, y);
-                tiles.entry(position).or_insert(TerrainData {
-                    r#type: TerrainType::Wall,
-                });
-            }
-        }
+    /// Generate a new floor layout, the same seed always produces the same floor
+    pub fn generate(seed: u64) -> Self {
+        let floor = FloorGenerator::new(seed).generate();
 
         GameMap {
-            tiles,
+            tiles: floor.tiles,
             tiles_lookup: HashMap::new(),
+            rooms: floor.rooms,
+        }
+    }
+
+    /// Position where the player should start the floor
+    pub fn player_spawn(&self) -> IVec2 {
+        self.rooms.first().map_or_else(
+            || {
+                // Without any room, start on the first ground tile instead of a wall
+                self.tiles
+                    .iter()
+                    .filter(|(_, terrain)| terrain.r#type == TerrainType::Ground)
+                    .map(|(position, _)| *position)
+                    .min_by_key(|position| (position.y, position.x))
+                    .unwrap_or(IVec2::ZERO)
+            },
+            Room::center,
+        )
+    }
+
+    /// All the tiles a piece with the mobility can safely stand on
+    pub fn walkable_tiles(&self, mobility: &Mobility) -> HashSet<IVec2> {
+        self.tiles
+            .iter()
+            .filter(|(_, terrain)| mobility.is_safe(&terrain.r#type))
+            .map(|(position, _)| *position)
+            .collect()
+    }
+
+    /// A diagonal move can't cut the corner of a wall
+    pub fn is_corner_blocked(&self, from: IVec2, to: IVec2) -> bool {
+        if from.x == to.x || from.y == to.y {
+            return false;
         }
+
+        [IVec2::new(to.x, from.y), IVec2::new(from.x, to.y)]
+            .iter()
+            .any(|corner| {
+                self.tiles
+                    .get(corner)
+                    .is_none_or(|tile| tile.r#type == TerrainType::Wall)
+            })
     }
 
     pub fn get_neighbors(&self, position: &IVec2) -> HashMap<IVec2, TerrainData> {
@@ -71,6 +157,28 @@ impl GameMap {
     pub fn associate_entity_to_tile(&mut self, entity: Entity, position: &IVec2) {
         self.tiles_lookup.insert(*position, entity);
     }
+
+    /// Hand-built map for the tests, the row index is the `y` and each character a tile:
+    /// `#` wall, `.` ground, `~` water, `^` lava, `_` abyss and `>` stairs
+    #[cfg(test)]
+    pub fn from_rows(rows: &[&str]) -> Self {
+        let mut tiles = HashMap::new();
+        for (y, row) in rows.iter().enumerate() {
+            for (x, char) in row.chars().enumerate() {
+                let r#type = match char {
+                    '#' => TerrainType::Wall,
+                    '~' => TerrainType::Environment(EnvironmentType::Water),
+                    '^' => TerrainType::Environment(EnvironmentType::Lava),
+                    '_' => TerrainType::Environment(EnvironmentType::Abyss),
+                    '>' => TerrainType::Stairs,
+                    _ => TerrainType::Ground,
+                };
+                tiles.insert(IVec2::new(x as i32, y as i32), TerrainData { r#type });
+            }
+        }
+
+        GameMap { tiles, ..default() }
+    }
 }
 
 #[derive(Component, Debug)]
@@ -83,13 +191,15 @@ pub struct Tilemap;
 pub enum EnvironmentType {
     Water,
     Lava,
+    Abyss,
 }
 
 #[derive(Copy, Clone, Debug, Ord, PartialOrd, PartialEq, Eq, Hash)]
 pub enum TerrainType {
     Ground,
     Wall,
-    Environment(EnvironmentType), // Water / Lava
+    Environment(EnvironmentType), // Water / Lava / Abyss
+    Stairs,
 }
 
 #[derive(Copy, Clone, Debug, Ord, PartialOrd, PartialEq, Eq, Hash, Deref, DerefMut)]
@@ -100,7 +210,7 @@ pub struct TerrainData {
 #[derive(Component, Debug)]
 pub struct Tile(pub TerrainData);
 
-fn spawn_map(mut commands: Commands, mut current_map: ResMut<GameMap>) {
+pub fn spawn_map(mut commands: Commands, mut current_map: ResMut<GameMap>) {
     let tilemap = commands
         .spawn((Tilemap, Name::new("Tilemap"), SpatialBundle { ..default() }))
         .id();
@@ -117,3 +227,61 @@ fn spawn_map(mut commands: Commands, mut current_map: ResMut<GameMap>) {
         current_map.associate_entity_to_tile(tile, &position);
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ivec2::find_path;
+
+    #[test]
+    fn stairs_are_reachable_from_the_player_spawn() {
+        for seed in [0, 1, 42, 1337, u64::MAX] {
+            let map = GameMap::generate(seed);
+            let stairs = map
+                .tiles
+                .iter()
+                .find(|(_, terrain)| terrain.r#type == TerrainType::Stairs)
+                .map(|(position, _)| *position)
+                .expect("every floor has stairs");
+
+            let path = find_path(
+                map.player_spawn(),
+                stairs,
+                &map.walkable_tiles(&Mobility::default()),
+                &HashSet::new(),
+                |from, to| map.is_corner_blocked(from, to),
+            );
+
+            assert!(path.is_some(), "seed {seed} has unreachable stairs");
+        }
+    }
+
+    #[test]
+    fn spawns_on_ground_without_any_room() {
+        let map = GameMap {
+            tiles: HashMap::from([
+                (
+                    IVec2::ZERO,
+                    TerrainData {
+                        r#type: TerrainType::Wall,
+                    },
+                ),
+                (
+                    IVec2::new(2, 1),
+                    TerrainData {
+                        r#type: TerrainType::Ground,
+                    },
+                ),
+                (
+                    IVec2::new(1, 1),
+                    TerrainData {
+                        r#type: TerrainType::Ground,
+                    },
+                ),
+            ]),
+            ..default()
+        };
+
+        assert_eq!(map.player_spawn(), IVec2::new(1, 1));
+    }
+}
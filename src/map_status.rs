@@ -0,0 +1,341 @@
+//! Statuses affecting the whole floor, ie: the weather or Trick Room
+
+use bevy::prelude::*;
+use common::{element::Element, map_status::MapStatus};
+use rand::{rngs::StdRng, seq::SliceRandom, Rng, SeedableRng};
+
+use crate::{
+    actions::{
+        indirect_damage_action::{IndirectDamageAction, IndirectDamageCause},
+        ActionQueue, QueuedAction,
+    },
+    map::{Dungeon, FloorChangedEvent},
+    player::PlayerActionEvent,
+    pokemons::Elements,
+    stats::Health,
+    turn::turn_system,
+    GamePlayingSet, GameState,
+};
+
+/// Number of turns a map status given by a spell lasts
+pub const MAP_STATUS_TURNS: u32 = 10;
+// Sandstorm and hail deal this fraction of the max health each turn
+const WEATHER_DAMAGE_DIVISOR: i32 = 16;
+const WEATHER_BOOST_MULTIPLIER: f32 = 1.5;
+const WEATHER_WEAKEN_MULTIPLIER: f32 = 0.5;
+// Chance of a floor to start with a weather
+const FLOOR_WEATHER_CHANCE: f64 = 0.3;
+// Keeps the weather roll apart from the layout of the floor
+const WEATHER_SEED_SALT: u64 = 1;
+const FLOOR_WEATHERS: [MapStatus; 5] = [
+    MapStatus::Rain,
+    MapStatus::Sunny,
+    MapStatus::Sandstorm,
+    MapStatus::Hail,
+    MapStatus::Fog,
+];
+
+pub struct MapStatusPlugin;
+
+impl Plugin for MapStatusPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<MapStatuses>()
+            .add_event::<MapStatusChangedEvent>()
+            .add_systems(OnEnter(GameState::Playing), roll_floor_weather)
+            .add_systems(
+                Update,
+                roll_floor_weather.run_if(on_event::<FloorChangedEvent>()),
+            )
+            .add_systems(
+                Update,
+                map_status_turn_system
+                    .after(turn_system)
+                    .in_set(GamePlayingSet::TurnLogics),
+            );
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct ActiveMapStatus {
+    pub status: MapStatus,
+    /// Remaining turns, `None` if the status lasts the whole floor
+    pub turns: Option<u32>,
+}
+
+#[derive(Resource, Debug, Default)]
+pub struct MapStatuses(pub Vec<ActiveMapStatus>);
+
+impl MapStatuses {
+    /// Current weather of the floor, only one weather is active at a time.
+    /// A weather started by a spell covers the weather of the floor until it ends.
+    pub fn weather(&self) -> MapStatus {
+        let mut weathers = self.0.iter().filter(|active| is_weather(&active.status));
+        weathers
+            .clone()
+            .find(|active| active.turns.is_some())
+            .or_else(|| weathers.next())
+            .map_or(MapStatus::Clear, |active| active.status)
+    }
+
+    pub fn is_active(&self, status: MapStatus) -> bool {
+        if is_weather(&status) || status == MapStatus::Clear {
+            return self.weather() == status;
+        }
+        self.0.iter().any(|active| active.status == status)
+    }
+
+    /// Start the status, replacing the current weather if the status is one, `Clear` only ends it.
+    /// A timed weather keeps the weather of the floor aside, it comes back once the timed one ends.
+    pub fn start(&mut self, status: MapStatus, turns: Option<u32>) {
+        let replaces_floor_weather = status == MapStatus::Clear || turns.is_none();
+        let replaces_weather = is_weather(&status) || status == MapStatus::Clear;
+        self.0.retain(|active| {
+            if replaces_weather && is_weather(&active.status) {
+                return active.turns.is_none() && !replaces_floor_weather;
+            }
+            active.status != status
+        });
+        if status != MapStatus::Clear {
+            self.0.push(ActiveMapStatus { status, turns });
+        }
+    }
+}
+
+#[derive(Event, Debug)]
+pub struct MapStatusChangedEvent {
+    pub status: MapStatus,
+    /// The status started, otherwise it ended
+    pub started: bool,
+}
+
+pub fn is_weather(status: &MapStatus) -> bool {
+    matches!(
+        status,
+        MapStatus::Rain
+            | MapStatus::Sunny
+            | MapStatus::Sandstorm
+            | MapStatus::Hail
+            | MapStatus::Fog
+            | MapStatus::Cloudy
+            | MapStatus::Snow
+            | MapStatus::Blizzard
+            | MapStatus::HeavyRain
+            | MapStatus::HarshSun
+            | MapStatus::Wind
+    )
+}
+
+/// Damage multiplier of the weather on the spells of the element
+pub fn weather_multiplier(weather: MapStatus, element: Element) -> f32 {
+    match (weather, element) {
+        (MapStatus::Rain | MapStatus::HeavyRain, Element::Water)
+        | (MapStatus::Sunny | MapStatus::HarshSun, Element::Fire) => WEATHER_BOOST_MULTIPLIER,
+        (MapStatus::Rain | MapStatus::HeavyRain, Element::Fire)
+        | (MapStatus::Sunny | MapStatus::HarshSun, Element::Water) => WEATHER_WEAKEN_MULTIPLIER,
+        _ => 1.,
+    }
+}
+
+/// Damage taken each turn because of the weather
+pub fn weather_damage(weather: MapStatus, elements: &Elements, max_health: i32) -> Option<i32> {
+    let is_immune = match weather {
+        MapStatus::Sandstorm => [Element::Rock, Element::Ground, Element::Steel]
+            .into_iter()
+            .any(|element| elements.contains(element)),
+        MapStatus::Hail => elements.contains(Element::Ice),
+        _ => return None,
+    };
+
+    (!is_immune).then_some((max_health / WEATHER_DAMAGE_DIVISOR).max(1))
+}
+
+/// Each floor may start with a weather lasting until the stairs are taken
+fn roll_floor_weather(
+    dungeon: Res<Dungeon>,
+    mut map_statuses: ResMut<MapStatuses>,
+    mut ev_map_status_changed: EventWriter<MapStatusChangedEvent>,
+) {
+    map_statuses.0.clear();
+
+    let mut rng = StdRng::seed_from_u64(dungeon.floor_sub_seed(WEATHER_SEED_SALT));
+    if !rng.gen_bool(FLOOR_WEATHER_CHANCE) {
+        return;
+    }
+    let Some(weather) = FLOOR_WEATHERS.choose(&mut rng) else {
+        return;
+    };
+
+    map_statuses.start(*weather, None);
+    ev_map_status_changed.send(MapStatusChangedEvent {
+        status: *weather,
+        started: true,
+    });
+}
+
+/// Apply the weather damage once all the actors took their turn and count down the statuses
+fn map_status_turn_system(
+    query: Query<(Entity, &Health, &Elements)>,
+    mut map_statuses: ResMut<MapStatuses>,
+    mut action_queue: ResMut<ActionQueue>,
+    mut ev_player_action: EventReader<PlayerActionEvent>,
+    mut ev_map_status_changed: EventWriter<MapStatusChangedEvent>,
+) {
+    if ev_player_action.read().count() == 0 {
+        return;
+    }
+
+    let weather = map_statuses.weather();
+    for (entity, health, elements) in query.iter() {
+        let Some(value) = weather_damage(weather, elements, health.max) else {
+            continue;
+        };
+        action_queue.0.push_back(QueuedAction {
+            entity,
+            performable_actions: vec![Box::new(IndirectDamageAction {
+                entity,
+                value,
+                cause: IndirectDamageCause::Weather(weather),
+            })],
+        });
+    }
+
+    for active in map_statuses.0.iter_mut() {
+        if let Some(turns) = active.turns.as_mut() {
+            *turns = turns.saturating_sub(1);
+            if *turns == 0 {
+                ev_map_status_changed.send(MapStatusChangedEvent {
+                    status: active.status,
+                    started: false,
+                });
+            }
+        }
+    }
+    map_statuses.0.retain(|active| active.turns != Some(0));
+
+    // The weather of the floor comes back once the weather of a spell ended
+    let restored_weather = map_statuses.weather();
+    if restored_weather != weather && restored_weather != MapStatus::Clear {
+        ev_map_status_changed.send(MapStatusChangedEvent {
+            status: restored_weather,
+            started: true,
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn elements(primary: Element) -> Elements {
+        Elements {
+            primary,
+            secondary: Element::None,
+        }
+    }
+
+    #[test]
+    fn boosts_and_weakens_the_spells_with_the_weather() {
+        assert_eq!(
+            weather_multiplier(MapStatus::Rain, Element::Water),
+            WEATHER_BOOST_MULTIPLIER
+        );
+        assert_eq!(
+            weather_multiplier(MapStatus::Rain, Element::Fire),
+            WEATHER_WEAKEN_MULTIPLIER
+        );
+        assert_eq!(
+            weather_multiplier(MapStatus::HarshSun, Element::Fire),
+            WEATHER_BOOST_MULTIPLIER
+        );
+        assert_eq!(
+            weather_multiplier(MapStatus::Sunny, Element::Water),
+            WEATHER_WEAKEN_MULTIPLIER
+        );
+        assert_eq!(weather_multiplier(MapStatus::Sunny, Element::Grass), 1.);
+        assert_eq!(weather_multiplier(MapStatus::Clear, Element::Water), 1.);
+    }
+
+    #[test]
+    fn damages_the_pokemons_without_immunity() {
+        assert_eq!(
+            weather_damage(MapStatus::Sandstorm, &elements(Element::Fire), 160),
+            Some(10)
+        );
+        // At least one point of damage
+        assert_eq!(
+            weather_damage(MapStatus::Hail, &elements(Element::Fire), 10),
+            Some(1)
+        );
+        assert_eq!(
+            weather_damage(MapStatus::Sandstorm, &elements(Element::Steel), 160),
+            None
+        );
+        assert_eq!(
+            weather_damage(MapStatus::Hail, &elements(Element::Ice), 160),
+            None
+        );
+        assert_eq!(
+            weather_damage(MapStatus::Rain, &elements(Element::Fire), 160),
+            None
+        );
+    }
+
+    #[test]
+    fn replaces_the_current_weather() {
+        let mut map_statuses = MapStatuses::default();
+        assert!(map_statuses.is_active(MapStatus::Clear));
+
+        map_statuses.start(MapStatus::Rain, None);
+        map_statuses.start(MapStatus::TrickRoom, Some(MAP_STATUS_TURNS));
+        map_statuses.start(MapStatus::Sunny, Some(MAP_STATUS_TURNS));
+
+        // Only one weather at a time, the other statuses are kept
+        assert!(map_statuses.is_active(MapStatus::Sunny));
+        assert!(!map_statuses.is_active(MapStatus::Rain));
+        assert!(!map_statuses.is_active(MapStatus::Clear));
+        assert!(map_statuses.is_active(MapStatus::TrickRoom));
+        // The rain of the floor is kept aside
+        assert_eq!(map_statuses.0.len(), 3);
+
+        // Another timed weather replaces the timed one only
+        map_statuses.start(MapStatus::Hail, Some(MAP_STATUS_TURNS));
+        assert!(map_statuses.is_active(MapStatus::Hail));
+        assert_eq!(map_statuses.0.len(), 3);
+
+        // Clearing the weather ends it without adding a status
+        map_statuses.start(MapStatus::Clear, None);
+        assert!(map_statuses.is_active(MapStatus::Clear));
+        assert!(map_statuses.is_active(MapStatus::TrickRoom));
+        assert_eq!(map_statuses.0.len(), 1);
+    }
+
+    #[test]
+    fn restores_the_floor_weather_after_a_timed_weather() {
+        let mut map_statuses = MapStatuses::default();
+
+        map_statuses.start(MapStatus::Rain, None);
+        map_statuses.start(MapStatus::Rain, Some(1));
+        map_statuses.start(MapStatus::Sunny, Some(1));
+        assert_eq!(map_statuses.weather(), MapStatus::Sunny);
+
+        // The timed weather runs out
+        map_statuses.0.retain(|active| active.turns.is_none());
+        assert_eq!(map_statuses.weather(), MapStatus::Rain);
+
+        // A new floor weather replaces the old one
+        map_statuses.start(MapStatus::Hail, None);
+        assert_eq!(map_statuses.weather(), MapStatus::Hail);
+        assert_eq!(map_statuses.0.len(), 1);
+    }
+
+    #[test]
+    fn restarts_the_same_status() {
+        let mut map_statuses = MapStatuses::default();
+
+        map_statuses.start(MapStatus::TrickRoom, Some(2));
+        map_statuses.start(MapStatus::TrickRoom, Some(MAP_STATUS_TURNS));
+
+        assert_eq!(map_statuses.0.len(), 1);
+        assert_eq!(map_statuses.0[0].turns, Some(MAP_STATUS_TURNS));
+    }
+}
@@ -0,0 +1,369 @@
+use std::collections::HashSet;
+
+use bevy::prelude::*;
+use pokemon_data::{PokemonData, PokemonForm};
+use spell_data::SpellData;
+
+use crate::{
+    data::assets::spell_data::SpellDataLookup, pokemons::Pokemon, spells::Spell, stats::Level,
+    GameState,
+};
+
+pub const MOVE_SET_SIZE: usize = 4;
+/// Used when all the moves of the pokemon are out of PP
+pub const STRUGGLE_ID: &str = "struggle";
+
+pub struct MoveSetPlugin;
+
+impl Plugin for MoveSetPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<MoveSet>()
+            .add_event::<MoveOutOfPPEvent>()
+            .add_systems(
+                Update,
+                update_move_set_system.run_if(in_state(GameState::Playing)),
+            );
+    }
+}
+
+#[derive(Debug, Clone, Reflect)]
+pub struct Move {
+    /// ID of the spell data, ie: `flamethrower`
+    pub id: String,
+    /// Remaining uses of the move
+    pub pp: u32,
+    pub max_pp: u32,
+}
+
+impl Move {
+    pub fn restore_pp(&mut self, value: u32) {
+        self.pp = self.pp.saturating_add(value).min(self.max_pp);
+    }
+}
+
+/// Sent when the player tries to use a move without PP left
+#[derive(Event, Debug)]
+pub struct MoveOutOfPPEvent {
+    /// Name of the move, ie: `Ember`
+    pub name: String,
+}
+
+/// The moves a pokemon can use, one per spell slot
+#[derive(Component, Debug, Default, Clone, Reflect)]
+#[reflect(Component)]
+pub struct MoveSet(pub [Option<Move>; MOVE_SET_SIZE]);
+
+impl MoveSet {
+    /// Fill the move set with the last moves learned by leveling up, with their max PP from `max_pp`
+    pub fn from_level_skills(form: &PokemonForm, level: u32, max_pp: impl Fn(&str) -> u32) -> Self {
+        let mut learned: Vec<&str> = Vec::new();
+
+        for level_skill in form.level_skills.iter() {
+            if level_skill.level < 0 || level_skill.level as u32 > level {
+                continue;
+            }
+            // A move learned again moves back to the top of the list
+            learned.retain(|id| *id != level_skill.skill);
+            learned.push(&level_skill.skill);
+        }
+
+        let mut move_set = Self::default();
+        let first_move = learned.len().saturating_sub(MOVE_SET_SIZE);
+        for (slot, id) in move_set.0.iter_mut().zip(&learned[first_move..]) {
+            let max_pp = max_pp(id);
+            *slot = Some(Move {
+                id: id.to_string(),
+                pp: max_pp,
+                max_pp,
+            });
+        }
+
+        move_set
+    }
+
+    pub fn get(&self, slot: usize) -> Option<&Move> {
+        self.0.get(slot).and_then(|m| m.as_ref())
+    }
+
+    pub fn get_mut(&mut self, slot: usize) -> Option<&mut Move> {
+        self.0.get_mut(slot).and_then(|m| m.as_mut())
+    }
+
+    /// Struggle is used once none of the moves have PP left
+    pub fn is_out_of_pp(&self) -> bool {
+        self.0.iter().flatten().all(|m| m.pp == 0)
+    }
+
+    /// The moves that can be used with their slot, Struggle without slot once they are all out of PP
+    pub fn usable_spells(
+        &self,
+        lookup: &SpellDataLookup,
+        assets: &Assets<SpellData>,
+        reported: &mut HashSet<String>,
+    ) -> Vec<(Option<usize>, Spell)> {
+        if self.is_out_of_pp() {
+            return Spell::load(STRUGGLE_ID, lookup, assets, reported)
+                .map(|spell| vec![(None, spell)])
+                .unwrap_or_default();
+        }
+
+        self.0
+            .iter()
+            .enumerate()
+            .filter_map(|(slot, m)| m.as_ref().map(|m| (slot, m)))
+            .filter(|(_, m)| m.pp > 0)
+            .filter_map(|(slot, m)| {
+                Some((Some(slot), Spell::load(&m.id, lookup, assets, reported)?))
+            })
+            .collect()
+    }
+}
+
+/// Refresh the move set when the pokemon data or the level changes
+#[allow(clippy::type_complexity)]
+fn update_move_set_system(
+    mut query: Query<
+        (&Pokemon, &Level, &Handle<PokemonData>, &mut MoveSet),
+        Or<(
+            Changed<Handle<PokemonData>>,
+            Changed<Level>,
+            Changed<Pokemon>,
+        )>,
+    >,
+    pokemon_data: Res<Assets<PokemonData>>,
+    spell_data_lookup: Res<SpellDataLookup>,
+    spell_data_assets: Res<Assets<SpellData>>,
+) {
+    for (pokemon, level, pokemon_data_handle, mut move_set) in query.iter_mut() {
+        let Some(data) = pokemon_data.get(pokemon_data_handle) else {
+            warn!("Unable to retrieve pokemon data for move set");
+            continue;
+        };
+
+        let Some(pokemon_form) = data.forms.get(pokemon.form_index) else {
+            warn!(
+                "Unable to retrieve pokemon form {} for move set",
+                pokemon.form_index
+            );
+            continue;
+        };
+
+        let max_pp = |id: &str| {
+            spell_data_lookup
+                .0
+                .get(id)
+                .and_then(|handle| spell_data_assets.get(handle))
+                .map_or(0, |spell| spell.base_charges)
+        };
+        let mut new_move_set = MoveSet::from_level_skills(pokemon_form, level.0, max_pp);
+
+        // The moves already known keep their remaining PP
+        for new_move in new_move_set.0.iter_mut().flatten() {
+            if let Some(known_move) = move_set.0.iter().flatten().find(|m| m.id == new_move.id) {
+                new_move.pp = known_move.pp.min(new_move.max_pp);
+            }
+        }
+
+        *move_set = new_move_set;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use char_animation::orientation::Orientation;
+    use spell_data::{AttackCoverage, SpellHitbox};
+
+    use crate::{
+        actions::{
+            restore_pp_action::RestorePPAction, spell_action::SpellAction, Action, ActionQueue,
+        },
+        map::Position,
+        pieces::FacingOrientation,
+        pokemons::test_pokemon_form,
+        spells::test_spell_data,
+        stats::Health,
+    };
+
+    use super::*;
+
+    fn move_set(pps: &[u32]) -> MoveSet {
+        let mut move_set = MoveSet::default();
+        for (slot, pp) in move_set.0.iter_mut().zip(pps) {
+            *slot = Some(Move {
+                id: "tackle".to_string(),
+                pp: *pp,
+                max_pp: 10,
+            });
+        }
+        move_set
+    }
+
+    fn pps(move_set: &MoveSet) -> Vec<u32> {
+        move_set.0.iter().flatten().map(|m| m.pp).collect()
+    }
+
+    /// Spell data of Tackle and Struggle
+    fn spell_data() -> (SpellDataLookup, Assets<SpellData>) {
+        let mut lookup = SpellDataLookup::default();
+        let mut assets = Assets::<SpellData>::default();
+        for (id, name) in [("tackle", "Tackle"), (STRUGGLE_ID, "Struggle")] {
+            let hitbox = SpellHitbox::Attack {
+                coverage: AttackCoverage::Front,
+            };
+            let handle = assets.add(test_spell_data(name, hitbox));
+            lookup.0.insert(id.to_string(), handle);
+        }
+        (lookup, assets)
+    }
+
+    fn ids(move_set: &MoveSet) -> Vec<&str> {
+        move_set.0.iter().flatten().map(|m| m.id.as_str()).collect()
+    }
+
+    #[test]
+    fn keeps_the_last_four_learned_moves() {
+        let form = test_pokemon_form(&[
+            (1, "tackle"),
+            (1, "growl"),
+            (5, "ember"),
+            (9, "smokescreen"),
+            (13, "dragon_rage"),
+        ]);
+
+        let move_set = MoveSet::from_level_skills(&form, 20, |_| 10);
+
+        assert_eq!(
+            ids(&move_set),
+            vec!["growl", "ember", "smokescreen", "dragon_rage"]
+        );
+    }
+
+    #[test]
+    fn moves_a_relearned_move_back_to_the_top() {
+        let form = test_pokemon_form(&[
+            (1, "tackle"),
+            (1, "growl"),
+            (5, "ember"),
+            (9, "smokescreen"),
+            (13, "tackle"),
+            (17, "dragon_rage"),
+        ]);
+
+        let move_set = MoveSet::from_level_skills(&form, 20, |_| 10);
+
+        assert_eq!(
+            ids(&move_set),
+            vec!["ember", "smokescreen", "tackle", "dragon_rage"]
+        );
+    }
+
+    #[test]
+    fn skips_the_skills_out_of_reach() {
+        let form = test_pokemon_form(&[(-1, "scratch"), (1, "tackle"), (13, "ember")]);
+
+        let move_set = MoveSet::from_level_skills(&form, 12, |_| 10);
+
+        assert_eq!(ids(&move_set), vec!["tackle"]);
+        assert!(move_set.get(1).is_none());
+    }
+
+    #[test]
+    fn takes_the_pp_from_the_spell_data() {
+        let form = test_pokemon_form(&[(1, "tackle"), (1, "ember")]);
+
+        let move_set =
+            MoveSet::from_level_skills(&form, 1, |id| if id == "tackle" { 35 } else { 25 });
+
+        let pps: Vec<_> = move_set
+            .0
+            .iter()
+            .flatten()
+            .map(|m| (m.pp, m.max_pp))
+            .collect();
+        assert_eq!(pps, vec![(35, 35), (25, 25)]);
+    }
+
+    #[test]
+    fn runs_out_of_pp_once_every_move_is_spent() {
+        assert!(!move_set(&[0, 3]).is_out_of_pp());
+        assert!(move_set(&[0, 0]).is_out_of_pp());
+        // Without any move only Struggle is left
+        assert!(MoveSet::default().is_out_of_pp());
+    }
+
+    #[test]
+    fn only_uses_the_moves_with_pp_left() {
+        let (lookup, assets) = spell_data();
+        let mut reported = HashSet::new();
+        let mut move_set = move_set(&[0, 3, 5]);
+        move_set.get_mut(2).unwrap().id = "unknown".to_string();
+
+        let spells = move_set.usable_spells(&lookup, &assets, &mut reported);
+
+        let slots: Vec<_> = spells.iter().map(|(slot, _)| *slot).collect();
+        assert_eq!(slots, vec![Some(1)]);
+        assert_eq!(spells[0].1.id, "tackle");
+        // The move without data is skipped and reported
+        assert!(reported.contains("unknown"));
+    }
+
+    #[test]
+    fn falls_back_to_struggle_without_pp() {
+        let (lookup, assets) = spell_data();
+
+        let spells = move_set(&[0, 0]).usable_spells(&lookup, &assets, &mut HashSet::new());
+
+        assert_eq!(spells.len(), 1);
+        assert_eq!(spells[0].0, None);
+        assert_eq!(spells[0].1.id, STRUGGLE_ID);
+    }
+
+    #[test]
+    fn spends_the_pp_on_the_first_strike_only() {
+        let mut world = World::new();
+        world.insert_resource(ActionQueue::default());
+        let caster = world
+            .spawn((
+                Health { value: 10, max: 10 },
+                move_set(&[5]),
+                Position(IVec2::ZERO),
+                FacingOrientation(Orientation::Est),
+            ))
+            .id();
+        let mut data = test_spell_data(
+            "Double Kick",
+            SpellHitbox::Attack {
+                coverage: AttackCoverage::Front,
+            },
+        );
+        data.strikes = 2;
+        let action = SpellAction {
+            caster,
+            spell: Spell::from_data("double_kick", &data).unwrap(),
+            slot: Some(0),
+            strike: 0,
+            direction: None,
+        };
+
+        action.execute(&mut world).unwrap();
+        assert_eq!(pps(world.get::<MoveSet>(caster).unwrap()), vec![4]);
+
+        // The second strike is queued right after the first one
+        let queued = world.resource_mut::<ActionQueue>().0.pop_front().unwrap();
+        queued.performable_actions[0].execute(&mut world).unwrap();
+        assert_eq!(pps(world.get::<MoveSet>(caster).unwrap()), vec![4]);
+        assert!(world.resource::<ActionQueue>().0.is_empty());
+    }
+
+    #[test]
+    fn restores_the_pp_up_to_the_max() {
+        let mut world = World::new();
+        let entity = world.spawn(move_set(&[1, 9, 10])).id();
+
+        RestorePPAction { entity, value: 5 }
+            .execute(&mut world)
+            .unwrap();
+
+        assert_eq!(pps(world.get::<MoveSet>(entity).unwrap()), vec![6, 10, 10]);
+    }
+}
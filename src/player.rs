@@ -1,26 +1,43 @@
+use std::collections::HashSet;
+
 use bevy::prelude::*;
-use char_animation::anim_key::AnimKey;
 use char_animation::orientation::Orientation;
 use leafwing_input_manager::action_state::ActionState;
 use leafwing_input_manager::input_map::InputMap;
 use leafwing_input_manager::plugin::InputManagerPlugin;
 use leafwing_input_manager::{Actionlike, InputManagerBundle};
+use spell_data::SpellData;
 
 use crate::actions::destroy_wall_action::DestroyWallAction;
+use crate::actions::drop_item_action::DropItemAction;
 use crate::actions::melee_hit_action::MeleeHitAction;
+use crate::actions::next_floor_action::NextFloorAction;
 use crate::actions::skip_action::SkipAction;
 use crate::actions::spell_action::SpellAction;
+use crate::actions::throw_item_action::ThrowItemAction;
+use crate::actions::use_item_action::UseItemAction;
 use crate::actions::walk_action::WalkAction;
 use crate::actions::{Action, ProcessingActionEvent};
+use crate::belly::{Belly, RUNNING_DRAIN_MULTIPLIER};
+use crate::data::assets::spell_data::SpellDataLookup;
+use crate::evolution::BattleRecord;
 use crate::faction::Faction;
-use crate::map::Position;
+use crate::fov::FieldOfView;
+use crate::inventory::{BagMenu, GroundItem, Inventory};
+use crate::ivec2::IVec2Ext;
+use crate::map::{GameMap, Position, TerrainType};
+use crate::move_set::{MoveOutOfPPEvent, MoveSet, STRUGGLE_ID};
 use crate::pieces::{Actor, FacingOrientation, Occupier, Piece, PieceKind};
 use crate::pokemons::Pokemon;
-use crate::spells::{ProjectileSpell, Spell, SpellCast, SpellHit, SpellType};
+use crate::spells::Spell;
+use crate::stats::{Experience, Level};
+use crate::status::StatusConditions;
 use crate::{GamePlayingSet, GameState};
 
 pub struct PlayerPlugin;
 
+const PLAYER_LEVEL: u32 = 5;
+
 const DIR_KEY_MAPPING: [(PlayerAction, IVec2); 4] = [
     (PlayerAction::Up, IVec2 { x: 0, y: 1 }),
     (PlayerAction::Down, IVec2 { x: 0, y: -1 }),
@@ -28,6 +45,13 @@ const DIR_KEY_MAPPING: [(PlayerAction, IVec2); 4] = [
     (PlayerAction::Right, IVec2 { x: 1, y: 0 }),
 ];
 
+const SPELL_SLOT_KEY_MAPPING: [(PlayerAction, usize); 4] = [
+    (PlayerAction::SpellSlot1, 0),
+    (PlayerAction::SpellSlot2, 1),
+    (PlayerAction::SpellSlot3, 2),
+    (PlayerAction::SpellSlot4, 3),
+];
+
 impl Plugin for PlayerPlugin {
     fn build(&self, app: &mut App) {
         app.add_event::<PlayerActionEvent>()
@@ -49,14 +73,21 @@ pub enum PlayerAction {
     Right,
     Up,
     Down,
+    Diagonal,
+    Run,
     Skip,
+    Stairs,
     SpellSlot1,
     SpellSlot2,
     SpellSlot3,
     SpellSlot4,
+    Bag,
+    UseItem,
+    ThrowItem,
+    DropItem,
 }
 
-fn spawn_player(mut commands: Commands) {
+fn spawn_player(mut commands: Commands, map: Res<GameMap>) {
     commands.spawn((
         Name::new("Player"),
         FacingOrientation(Orientation::South),
@@ -64,6 +95,15 @@ fn spawn_player(mut commands: Commands) {
             id: 4,
             form_index: 0,
         },
+        Level(PLAYER_LEVEL),
+        Experience::default(),
+        BattleRecord::default(),
+        (
+            StatusConditions::default(),
+            Inventory::default(),
+            Belly::default(),
+        ),
+        MoveSet::default(),
         Faction::Player,
         Player,
         Occupier,
@@ -71,12 +111,15 @@ fn spawn_player(mut commands: Commands) {
         Piece {
             kind: PieceKind::Player,
         },
-        Position(IVec2::new(4, 4)),
+        Position(map.player_spawn()),
         InputManagerBundle::<PlayerAction> {
             action_state: ActionState::default(),
             input_map: InputMap::new([
                 (PlayerAction::Skip, KeyCode::Space),
                 (PlayerAction::Skip, KeyCode::Space),
+                (PlayerAction::Stairs, KeyCode::Enter),
+                (PlayerAction::Diagonal, KeyCode::ShiftLeft),
+                (PlayerAction::Run, KeyCode::ControlLeft),
                 (PlayerAction::Up, KeyCode::KeyW),
                 (PlayerAction::Up, KeyCode::ArrowUp),
                 (PlayerAction::Down, KeyCode::KeyS),
@@ -89,15 +132,40 @@ fn spawn_player(mut commands: Commands) {
                 (PlayerAction::SpellSlot2, KeyCode::Digit2),
                 (PlayerAction::SpellSlot3, KeyCode::Digit3),
                 (PlayerAction::SpellSlot4, KeyCode::Digit4),
+                (PlayerAction::Bag, KeyCode::KeyB),
+                (PlayerAction::UseItem, KeyCode::KeyE),
+                (PlayerAction::ThrowItem, KeyCode::KeyT),
+                (PlayerAction::DropItem, KeyCode::KeyX),
             ]),
         },
     ));
 }
 
+#[allow(clippy::type_complexity, clippy::too_many_arguments)]
 fn take_action(
-    player_query: Query<(Entity, &ActionState<PlayerAction>, &Position), With<Player>>,
+    player_query: Query<
+        (
+            Entity,
+            &ActionState<PlayerAction>,
+            &Position,
+            &MoveSet,
+            &Inventory,
+        ),
+        With<Player>,
+    >,
+    mut belly_query: Query<&mut Belly, With<Player>>,
+    pieces_query: Query<(&Position, &Faction), (With<Occupier>, Without<Player>)>,
+    ground_items_query: Query<&Position, With<GroundItem>>,
+    field_of_view: Res<FieldOfView>,
+    map: Res<GameMap>,
+    mut bag_menu: ResMut<BagMenu>,
+    spell_data_lookup: Res<SpellDataLookup>,
+    spell_data_assets: Res<Assets<SpellData>>,
     mut ev_processing_action: EventReader<ProcessingActionEvent>,
     mut ev_action: EventWriter<PlayerActionEvent>,
+    mut ev_move_out_of_pp: EventWriter<MoveOutOfPPEvent>,
+    mut reported_spells: Local<HashSet<String>>,
+    mut running: Local<Option<IVec2>>,
 ) {
     if ev_processing_action.read().len() > 0 {
         // info!("Player can take action");
@@ -105,63 +173,140 @@ fn take_action(
         return;
     }
 
-    let Ok((entity, action_state, position)) = player_query.get_single() else {
+    let Ok((entity, action_state, position, move_set, inventory)) = player_query.get_single()
+    else {
         return;
     };
 
-    for (key, dir) in DIR_KEY_MAPPING {
-        if !action_state.pressed(&key) {
-            continue;
+    if action_state.just_pressed(&PlayerAction::Bag) {
+        bag_menu.is_open = !bag_menu.is_open;
+    }
+    if bag_menu.is_open {
+        *running = None;
+        if let Some(action) = bag_action(entity, action_state, inventory, &mut bag_menu) {
+            bag_menu.is_open = false;
+            ev_action.send(PlayerActionEvent(vec![action]));
         }
-        let target = position.0 + dir;
+        return;
+    }
 
-        let walk_action = Box::new(WalkAction {
-            entity,
-            from: position.0,
-            to: target,
-        }) as Box<dyn Action>;
+    // Like in PMD, the run goes on by itself until a key is pressed, the path ahead is blocked
+    // or something worth stopping for shows up
+    if let Some(direction) = *running {
+        let occupied = pieces_query
+            .iter()
+            .map(|(position, _)| position.0)
+            .collect::<HashSet<_>>();
+        let sees_hostile = field_of_view.get(&Faction::Player).is_some_and(|vision| {
+            pieces_query.iter().any(|(position, faction)| {
+                Faction::Player.is_hostile_to(faction) && vision.is_visible(&position.0)
+            })
+        });
+        let is_on_item = ground_items_query
+            .iter()
+            .any(|item_position| item_position.0 == position.0);
 
-        let attack_action = Box::new(MeleeHitAction {
-            attacker: entity,
-            damage: 1,
-            target,
-        }) as Box<dyn Action>;
+        if action_state.get_just_pressed().is_empty()
+            && !sees_hostile
+            && !is_on_item
+            && can_run(&map, &occupied, position.0, direction)
+        {
+            ev_action.send(PlayerActionEvent(run_actions(
+                entity,
+                position.0,
+                direction,
+                &mut belly_query,
+            )));
+            return;
+        }
+        *running = None;
+    }
 
-        let destroy_wall = Box::new(DestroyWallAction {
-            instigator: entity,
-            target,
-        }) as Box<dyn Action>;
+    // Pressing two directions at once moves diagonally
+    let direction = DIR_KEY_MAPPING
+        .iter()
+        .filter(|(key, _)| action_state.pressed(key))
+        .fold(IVec2::ZERO, |direction, (_, dir)| direction + *dir)
+        .clamp(IVec2::NEG_ONE, IVec2::ONE);
+    // Like in PMD, holding the diagonal key ignores the orthogonal directions
+    let diagonal_only = action_state.pressed(&PlayerAction::Diagonal);
+
+    if direction != IVec2::ZERO && (!diagonal_only || direction.is_diagonal()) {
+        if action_state.pressed(&PlayerAction::Run) {
+            *running = Some(direction);
+            ev_action.send(PlayerActionEvent(run_actions(
+                entity,
+                position.0,
+                direction,
+                &mut belly_query,
+            )));
+            return;
+        }
 
         info!("Send player action event");
-        ev_action.send(PlayerActionEvent(vec![
-            walk_action,
-            attack_action,
-            destroy_wall,
-        ]));
+        ev_action.send(PlayerActionEvent(walk_actions(
+            entity, position.0, direction,
+        )));
         return;
     }
 
-    if action_state.pressed(&PlayerAction::SpellSlot1) {
+    for (key, slot) in SPELL_SLOT_KEY_MAPPING {
+        if !action_state.pressed(&key) {
+            continue;
+        }
+
+        // Any move key uses Struggle once all the moves are out of PP
+        let (slot, spell) = if move_set.is_out_of_pp() {
+            let Some(spell) = Spell::load(
+                STRUGGLE_ID,
+                &spell_data_lookup,
+                &spell_data_assets,
+                &mut reported_spells,
+            ) else {
+                continue;
+            };
+            (None, spell)
+        } else {
+            let Some(m) = move_set.get(slot) else {
+                continue;
+            };
+            let Some(spell) = Spell::load(
+                &m.id,
+                &spell_data_lookup,
+                &spell_data_assets,
+                &mut reported_spells,
+            ) else {
+                continue;
+            };
+            if m.pp == 0 {
+                // Only warn once per key press, the turn is not spent
+                if action_state.just_pressed(&key) {
+                    ev_move_out_of_pp.send(MoveOutOfPPEvent { name: spell.name });
+                }
+                return;
+            }
+            (Some(slot), spell)
+        };
+
         let action = Box::new(SpellAction {
             caster: entity,
-            spell: Spell {
-                name: "Flamethrower",
-                range: 1..=3,
-                spell_type: SpellType::Projectile(ProjectileSpell {
-                    visual_effect: "Flamethrower_2",
-                }),
-                hit: SpellHit {
-                    visual_effect: "Flamethrower",
-                    damage: 1,
-                },
-                cast: SpellCast {
-                    visual_effect: "Circle_Small_Blue_In",
-                    animation: AnimKey::Shoot,
-                }, // Damage visual effect: Hit_Neutral
-                   // Cast visual effect: Circle_Small_Blue_Out
-            },
+            spell,
+            slot,
+            strike: 0,
+            direction: None,
         });
         ev_action.send(PlayerActionEvent(vec![action]));
+        return;
+    }
+
+    let is_on_stairs = map
+        .tiles
+        .get(&position.0)
+        .is_some_and(|tile| tile.r#type == TerrainType::Stairs);
+
+    if is_on_stairs && action_state.just_pressed(&PlayerAction::Stairs) {
+        let action = Box::new(NextFloorAction { entity });
+        ev_action.send(PlayerActionEvent(vec![action]));
     }
 
     if action_state.pressed(&PlayerAction::Skip) {
@@ -169,3 +314,82 @@ fn take_action(
         ev_action.send(PlayerActionEvent(vec![action]));
     }
 }
+
+/// Step in the direction, or attack the piece or break the wall in the way
+fn walk_actions(entity: Entity, from: IVec2, direction: IVec2) -> Vec<Box<dyn Action>> {
+    let target = from + direction;
+
+    vec![
+        Box::new(WalkAction {
+            entity,
+            from,
+            to: target,
+        }),
+        Box::new(MeleeHitAction {
+            attacker: entity,
+            target,
+        }),
+        Box::new(DestroyWallAction {
+            instigator: entity,
+            target,
+        }),
+    ]
+}
+
+/// A step of the run, running tires the pokemon so its belly drains faster this turn
+fn run_actions(
+    entity: Entity,
+    from: IVec2,
+    direction: IVec2,
+    belly_query: &mut Query<&mut Belly, With<Player>>,
+) -> Vec<Box<dyn Action>> {
+    if let Ok(mut belly) = belly_query.get_single_mut() {
+        belly.exert(RUNNING_DRAIN_MULTIPLIER);
+    }
+    walk_actions(entity, from, direction)
+}
+
+/// The run stops in front of anything but a free floor tile, and on the stairs
+fn can_run(map: &GameMap, occupied: &HashSet<IVec2>, from: IVec2, direction: IVec2) -> bool {
+    let to = from + direction;
+    let is_on_stairs = map
+        .tiles
+        .get(&from)
+        .is_some_and(|tile| tile.r#type == TerrainType::Stairs);
+    let is_free_ground = map
+        .tiles
+        .get(&to)
+        .is_some_and(|tile| matches!(tile.r#type, TerrainType::Ground | TerrainType::Stairs));
+
+    !is_on_stairs && is_free_ground && !occupied.contains(&to) && !map.is_corner_blocked(from, to)
+}
+
+/// While the bag is open, the directions select an item and the item keys act on it
+fn bag_action(
+    entity: Entity,
+    action_state: &ActionState<PlayerAction>,
+    inventory: &Inventory,
+    bag_menu: &mut BagMenu,
+) -> Option<Box<dyn Action>> {
+    if action_state.just_pressed(&PlayerAction::Down) {
+        bag_menu.selected += 1;
+    }
+    if action_state.just_pressed(&PlayerAction::Up) {
+        bag_menu.selected = bag_menu.selected.saturating_sub(1);
+    }
+    bag_menu.selected = bag_menu.selected.min(inventory.0.len().saturating_sub(1));
+
+    let slot = bag_menu.selected;
+    let item = inventory.0.get(slot)?.clone();
+
+    if action_state.just_pressed(&PlayerAction::UseItem) {
+        return Some(Box::new(UseItemAction { entity, slot, item }));
+    }
+    if action_state.just_pressed(&PlayerAction::ThrowItem) {
+        return Some(Box::new(ThrowItemAction { entity, slot, item }));
+    }
+    if action_state.just_pressed(&PlayerAction::DropItem) {
+        return Some(Box::new(DropItemAction { entity, slot, item }));
+    }
+    None
+}
@@ -1,10 +1,19 @@
+use std::str::FromStr;
+
 use bevy::prelude::*;
+use common::element::Element;
+use pokemon_data::PokemonData;
+
+use crate::GameState;
 
 pub struct PokemonsPlugin;
 
 impl Plugin for PokemonsPlugin {
     fn build(&self, app: &mut App) {
-        app.register_type::<Pokemon>();
+        app.register_type::<Pokemon>().add_systems(
+            Update,
+            update_elements_system.run_if(in_state(GameState::Playing)),
+        );
     }
 }
 
@@ -13,3 +22,113 @@ pub struct Pokemon {
     pub id: u32,
     pub form_index: usize,
 }
+
+/// Elements of the current form of the pokemon
+#[derive(Component, Debug, Clone, Copy)]
+pub struct Elements {
+    pub primary: Element,
+    pub secondary: Element,
+}
+
+impl Elements {
+    pub fn as_array(&self) -> [Element; 2] {
+        [self.primary, self.secondary]
+    }
+
+    pub fn contains(&self, element: Element) -> bool {
+        element != Element::None && (self.primary == element || self.secondary == element)
+    }
+}
+
+/// Ability of the pokemon, the first intrinsic of its form, ie: `blaze`
+#[derive(Component, Debug, Clone)]
+pub struct Ability(pub String);
+
+#[allow(clippy::type_complexity)]
+fn update_elements_system(
+    query: Query<
+        (Entity, &Pokemon, &Handle<PokemonData>),
+        Or<(Changed<Handle<PokemonData>>, Changed<Pokemon>)>,
+    >,
+    pokemon_data: Res<Assets<PokemonData>>,
+    mut commands: Commands,
+) {
+    for (entity, pokemon, pokemon_data_handle) in query.iter() {
+        let Some(data) = pokemon_data.get(pokemon_data_handle) else {
+            warn!("Unable to retrieve pokemon data for elements");
+            continue;
+        };
+
+        let Some(pokemon_form) = data.forms.get(pokemon.form_index) else {
+            warn!(
+                "Unable to retrieve pokemon form {} for elements",
+                pokemon.form_index
+            );
+            continue;
+        };
+        let parse_element = |element: &str| Element::from_str(element).unwrap_or(Element::None);
+
+        commands.entity(entity).insert((
+            Elements {
+                primary: parse_element(&pokemon_form.element1),
+                secondary: parse_element(&pokemon_form.element2),
+            },
+            Ability(pokemon_form.intrinsic1.clone()),
+        ));
+    }
+}
+
+/// Pokemon form for the tests, 50 in every base stat and the given `(level, skill)` pairs
+#[cfg(test)]
+pub fn test_pokemon_form(level_skills: &[(i32, &str)]) -> pokemon_data::PokemonForm {
+    use pokemon_data::{LocalTexts, PokemonForm, PokemonLevelSkill, TextData};
+
+    PokemonForm {
+        released: true,
+        generation: 1,
+        genderless_weight: 0,
+        male_weight: 1,
+        female_weight: 1,
+        base_hp: 50,
+        base_atk: 50,
+        base_def: 50,
+        base_m_atk: 50,
+        base_m_def: 50,
+        base_speed: 50,
+        exp_yield: 50,
+        height: 1.,
+        weight: 10.,
+        personalities: vec![],
+        teach_skills: vec![],
+        shared_skills: vec![],
+        secret_skills: vec![],
+        form_name: TextData {
+            default_text: "Form".to_string(),
+            local_texts: LocalTexts {
+                ja: None,
+                ko: None,
+                zh_hant: None,
+                fr: None,
+                de: None,
+                es: None,
+                it: None,
+                ja_jp: None,
+                zh_hans: None,
+            },
+        },
+        temporary: false,
+        promote_form: -1,
+        element1: "normal".to_string(),
+        element2: "none".to_string(),
+        intrinsic1: String::new(),
+        intrinsic2: String::new(),
+        intrinsic3: String::new(),
+        level_skills: level_skills
+            .iter()
+            .map(|(level, skill)| PokemonLevelSkill {
+                level: *level,
+                skill: skill.to_string(),
+            })
+            .collect(),
+    }
+}
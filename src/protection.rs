@@ -0,0 +1,113 @@
+//! Protections raised by the support moves, they stop the incoming damage for a while
+
+use bevy::prelude::*;
+use strum::EnumString;
+
+// Protect only lasts until the next turn of the protected pokemon
+const PROTECT_TURNS: u32 = 1;
+// Fraction of the max health paid for a substitute
+const SUBSTITUTE_HEALTH_DIVISOR: i32 = 4;
+
+pub struct ProtectionPlugin;
+
+impl Plugin for ProtectionPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<Protected>()
+            .register_type::<Substitute>();
+    }
+}
+
+/// Protection given by a spell, matches the `StatusID` of the spell data
+#[derive(Debug, Clone, Copy, PartialEq, Eq, EnumString)]
+#[strum(serialize_all = "snake_case")]
+pub enum Protection {
+    #[strum(serialize = "protect", serialize = "detect")]
+    Protect,
+    Substitute,
+}
+
+/// Blocks all the incoming damage
+#[derive(Component, Debug, Clone, Copy, Reflect)]
+#[reflect(Component)]
+pub struct Protected {
+    /// Remaining turns before the protection fades
+    pub turns: u32,
+}
+
+impl Default for Protected {
+    fn default() -> Self {
+        Self {
+            turns: PROTECT_TURNS,
+        }
+    }
+}
+
+/// Health paid for a substitute, it gets as much health. Never free, a substitute without
+/// health would still take a whole hit
+pub fn substitute_cost(max_health: i32) -> i32 {
+    (max_health / SUBSTITUTE_HEALTH_DIVISOR).max(1)
+}
+
+/// Decoy taking the incoming damage in place of the pokemon until it breaks
+#[derive(Component, Debug, Clone, Copy, Reflect)]
+#[reflect(Component)]
+pub struct Substitute {
+    pub health: i32,
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        actions::{
+            protect_action::ProtectAction, protection_tick_action::ProtectionTickAction, Action,
+        },
+        stats::Health,
+    };
+
+    use super::*;
+
+    fn substitute(world: &mut World, entity: Entity) -> Result<Vec<Box<dyn Action>>, ()> {
+        ProtectAction {
+            entity,
+            protection: Protection::Substitute,
+        }
+        .execute(world)
+    }
+
+    #[test]
+    fn pays_a_quarter_of_the_max_health_for_a_substitute() {
+        let mut world = World::new();
+        let entity = world.spawn(Health { value: 80, max: 80 }).id();
+
+        substitute(&mut world, entity).unwrap();
+
+        assert_eq!(world.get::<Health>(entity).unwrap().value, 60);
+        assert_eq!(world.get::<Substitute>(entity).unwrap().health, 20);
+        // Only one substitute at a time
+        assert!(substitute(&mut world, entity).is_err());
+    }
+
+    #[test]
+    fn never_makes_a_free_substitute() {
+        let mut world = World::new();
+        let weak = world.spawn(Health { value: 2, max: 3 }).id();
+        let weakest = world.spawn(Health { value: 1, max: 3 }).id();
+
+        substitute(&mut world, weak).unwrap();
+        assert_eq!(world.get::<Health>(weak).unwrap().value, 1);
+        assert_eq!(world.get::<Substitute>(weak).unwrap().health, 1);
+
+        assert!(substitute(&mut world, weakest).is_err());
+        assert!(world.get::<Substitute>(weakest).is_none());
+    }
+
+    #[test]
+    fn protect_fades_at_the_next_turn() {
+        let mut world = World::new();
+        let entity = world.spawn(Protected::default()).id();
+
+        ProtectionTickAction { entity }.execute(&mut world).unwrap();
+
+        assert!(world.get::<Protected>(entity).is_none());
+    }
+}
@@ -0,0 +1,121 @@
+//! Natural health regeneration of the pokemons, turn after turn
+
+use bevy::prelude::*;
+
+use crate::{
+    actions::{heal_action::HealAction, ActionQueue, QueuedAction},
+    belly::Belly,
+    player::PlayerActionEvent,
+    stats::{Health, Level},
+    turn::turn_system,
+    GamePlayingSet,
+};
+
+// Added to the level, so low level pokemons still regenerate
+const REGENERATION_LEVEL_BONUS: u32 = 50;
+// Progress needed to restore one point of health
+const REGENERATION_THRESHOLD: u32 = 20000;
+
+pub struct RegenerationPlugin;
+
+impl Plugin for RegenerationPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<Regeneration>().add_systems(
+            Update,
+            regeneration_turn_system
+                .after(turn_system)
+                .in_set(GamePlayingSet::TurnLogics),
+        );
+    }
+}
+
+/// Progress toward the next point of health regenerated
+#[derive(Component, Debug, Default, Clone, Copy, Reflect)]
+#[reflect(Component)]
+pub struct Regeneration(pub u32);
+
+impl Regeneration {
+    /// Progress for one turn, returns the health regenerated without going past the max health.
+    /// A dead or starving pokemon doesn't regenerate.
+    pub fn regenerate(&mut self, health: &Health, level: u32, is_starving: bool) -> i32 {
+        if health.is_dead() || health.value >= health.max || is_starving {
+            self.0 = 0;
+            return 0;
+        }
+
+        self.0 += health.max as u32 * (level + REGENERATION_LEVEL_BONUS);
+        let value = (self.0 / REGENERATION_THRESHOLD) as i32;
+        self.0 %= REGENERATION_THRESHOLD;
+        value.min(health.max - health.value)
+    }
+}
+
+/// Regenerate the health once the player took its turn, faster with a high max health and level.
+/// The health is restored through a `HealAction` queued after the actions of the turn.
+fn regeneration_turn_system(
+    mut query: Query<(Entity, &Health, &Level, &mut Regeneration, Option<&Belly>)>,
+    mut action_queue: ResMut<ActionQueue>,
+    mut ev_player_action: EventReader<PlayerActionEvent>,
+) {
+    if ev_player_action.read().count() == 0 {
+        return;
+    }
+
+    for (entity, health, level, mut regeneration, belly) in query.iter_mut() {
+        let is_starving = belly.is_some_and(Belly::is_empty);
+        // Already clamped to the missing health
+        let value = regeneration.regenerate(health, level.0, is_starving);
+        if value > 0 {
+            action_queue.0.push_back(QueuedAction {
+                entity,
+                performable_actions: vec![Box::new(HealAction { entity, value })],
+            });
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn health(value: i32, max: i32) -> Health {
+        Health { value, max }
+    }
+
+    #[test]
+    fn scales_with_the_max_health_and_the_level() {
+        let mut regeneration = Regeneration::default();
+
+        // 100 * (50 + 50) is half of the threshold, one point every two turns
+        assert_eq!(regeneration.regenerate(&health(10, 100), 50, false), 0);
+        assert_eq!(regeneration.regenerate(&health(10, 100), 50, false), 1);
+        assert_eq!(regeneration.0, 0);
+
+        // A higher max health and level regenerate several points per turn
+        assert_eq!(regeneration.regenerate(&health(10, 400), 150, false), 4);
+        assert_eq!(regeneration.regenerate(&health(10, 200), 0, false), 0);
+        assert_eq!(regeneration.0, 10000);
+    }
+
+    #[test]
+    fn clamps_to_the_max_health() {
+        let mut regeneration = Regeneration::default();
+
+        assert_eq!(regeneration.regenerate(&health(399, 400), 150, false), 1);
+        assert_eq!(regeneration.regenerate(&health(400, 400), 150, false), 0);
+        assert_eq!(regeneration.0, 0);
+    }
+
+    #[test]
+    fn stops_while_dead_or_starving() {
+        let mut regeneration = Regeneration(REGENERATION_THRESHOLD - 1);
+
+        assert_eq!(regeneration.regenerate(&health(10, 400), 150, true), 0);
+        // The progress is lost, not kept for later
+        assert_eq!(regeneration.0, 0);
+
+        regeneration.0 = REGENERATION_THRESHOLD - 1;
+        assert_eq!(regeneration.regenerate(&health(0, 400), 150, false), 0);
+        assert_eq!(regeneration.0, 0);
+    }
+}
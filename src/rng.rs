@@ -0,0 +1,15 @@
+//! Random generator of the game, all the rolls of the actions come from it
+
+use bevy::prelude::*;
+use rand::{rngs::StdRng, SeedableRng};
+
+/// Seeded with the dungeon seed, the rolls of a run can be replayed
+#[derive(Resource, Debug)]
+pub struct GameRng(pub StdRng);
+
+impl GameRng {
+    /// Same seed, same rolls
+    pub fn seeded(seed: u64) -> Self {
+        Self(StdRng::seed_from_u64(seed))
+    }
+}
@@ -1,2 +1,312 @@
 // Map generation: https://docs.google.com/document/d/1HuJIEOtTYCtSHK6R-sp4LC2gk1RDL_mfoFL6Qn_wdkE/edit
 // https://www.youtube.com/watch?v=fudOO713qYo&ab_channel=TheZZAZZGlitch
+
+use std::collections::HashMap;
+
+use bevy::prelude::*;
+use rand::{rngs::StdRng, seq::SliceRandom, Rng, SeedableRng};
+
+use crate::map::{EnvironmentType, TerrainData, TerrainType};
+
+// PMD floors are 56x32 tiles
+pub const FLOOR_WIDTH: i32 = 56;
+pub const FLOOR_HEIGHT: i32 = 32;
+
+const MAX_ROOMS: usize = 8;
+const ROOM_PLACEMENT_ATTEMPTS: usize = 200;
+const MIN_ROOM_SIZE: i32 = 5;
+const MAX_ROOM_SIZE: i32 = 11;
+// Minimum amount of wall tiles between two rooms
+const ROOM_MARGIN: i32 = 2;
+const EXTRA_CORRIDOR_CHANCE: f64 = 0.3;
+const POND_CHANCE: f64 = 0.25;
+// All the ponds of a floor share the same environment, picked with these weights
+const POND_ENVIRONMENTS: [(EnvironmentType, u32); 3] = [
+    (EnvironmentType::Water, 6),
+    (EnvironmentType::Lava, 2),
+    (EnvironmentType::Abyss, 2),
+];
+
+/// A rectangular room, `min` and `max` are both inclusive
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Room {
+    pub min: IVec2,
+    pub max: IVec2,
+}
+
+impl Room {
+    pub fn new(min: IVec2, size: IVec2) -> Self {
+        Self {
+            min,
+            max: min + size - IVec2::ONE,
+        }
+    }
+
+    pub fn center(&self) -> IVec2 {
+        (self.min + self.max) / 2
+    }
+
+    pub fn intersects(&self, other: &Room, margin: i32) -> bool {
+        self.min.x - margin <= other.max.x
+            && self.max.x + margin >= other.min.x
+            && self.min.y - margin <= other.max.y
+            && self.max.y + margin >= other.min.y
+    }
+
+    pub fn tiles(&self) -> impl Iterator<Item = IVec2> + '_ {
+        (self.min.y..=self.max.y)
+            .flat_map(move |y| (self.min.x..=self.max.x).map(move |x| IVec2::new(x, y)))
+    }
+}
+
+/// Result of a floor generation
+#[derive(Debug, Default)]
+pub struct Floor {
+    pub tiles: HashMap<IVec2, TerrainData>,
+    pub rooms: Vec<Room>,
+}
+
+/// Seeded floor generator, the same seed always produces the same layout
+#[derive(Debug, Clone)]
+pub struct FloorGenerator {
+    pub seed: u64,
+    pub width: i32,
+    pub height: i32,
+    pub max_rooms: usize,
+}
+
+impl FloorGenerator {
+    pub fn new(seed: u64) -> Self {
+        Self {
+            seed,
+            width: FLOOR_WIDTH,
+            height: FLOOR_HEIGHT,
+            max_rooms: MAX_ROOMS,
+        }
+    }
+
+    pub fn generate(&self) -> Floor {
+        let mut rng = StdRng::seed_from_u64(self.seed);
+        let mut tiles: HashMap<IVec2, TerrainData> = HashMap::new();
+
+        // Start from a floor full of walls
+        for x in 0..self.width {
+            for y in 0..self.height {
+                tiles.insert(
+                    IVec2::new(x, y),
+                    TerrainData {
+                        r#type: TerrainType::Wall,
+                    },
+                );
+            }
+        }
+
+        let rooms = self.place_rooms(&mut rng);
+        let environment = POND_ENVIRONMENTS
+            .choose_weighted(&mut rng, |(_, weight)| *weight)
+            .map_or(EnvironmentType::Water, |(environment, _)| *environment);
+
+        for room in rooms.iter() {
+            for position in room.tiles() {
+                set_terrain(&mut tiles, position, TerrainType::Ground);
+            }
+            if rng.gen_bool(POND_CHANCE) {
+                place_pond(&mut tiles, room, environment, &mut rng);
+            }
+        }
+
+        // Corridors are carved after the ponds so they can never be cut by the environment
+        for (previous, next) in rooms.iter().zip(rooms.iter().skip(1)) {
+            carve_corridor(&mut tiles, previous.center(), next.center(), &mut rng);
+        }
+
+        // Add a few loops so the floor is not a single chain of rooms
+        if rooms.len() > 2 {
+            for index in 0..rooms.len() {
+                if !rng.gen_bool(EXTRA_CORRIDOR_CHANCE) {
+                    continue;
+                }
+                let other = rng.gen_range(0..rooms.len());
+                if other == index {
+                    continue;
+                }
+                carve_corridor(
+                    &mut tiles,
+                    rooms[index].center(),
+                    rooms[other].center(),
+                    &mut rng,
+                );
+            }
+        }
+
+        place_stairs(&mut tiles, &rooms, &mut rng);
+
+        Floor { tiles, rooms }
+    }
+
+    fn place_rooms(&self, rng: &mut StdRng) -> Vec<Room> {
+        let mut rooms: Vec<Room> = Vec::new();
+
+        for _ in 0..ROOM_PLACEMENT_ATTEMPTS {
+            if rooms.len() >= self.max_rooms {
+                break;
+            }
+
+            let size = IVec2::new(
+                rng.gen_range(MIN_ROOM_SIZE..=MAX_ROOM_SIZE),
+                rng.gen_range(MIN_ROOM_SIZE..=MAX_ROOM_SIZE),
+            );
+
+            // Keep at least one wall tile on the border of the floor
+            let max_position = IVec2::new(self.width, self.height) - size - IVec2::ONE;
+            if max_position.x < 1 || max_position.y < 1 {
+                continue;
+            }
+
+            let room = Room::new(
+                IVec2::new(
+                    rng.gen_range(1..=max_position.x),
+                    rng.gen_range(1..=max_position.y),
+                ),
+                size,
+            );
+
+            if rooms
+                .iter()
+                .any(|other| room.intersects(other, ROOM_MARGIN))
+            {
+                continue;
+            }
+
+            rooms.push(room);
+        }
+
+        // Connect the rooms from left to right to avoid long overlapping corridors
+        rooms.sort_by_key(|room| (room.center().x, room.center().y));
+        rooms
+    }
+}
+
+fn set_terrain(tiles: &mut HashMap<IVec2, TerrainData>, position: IVec2, r#type: TerrainType) {
+    if let Some(terrain) = tiles.get_mut(&position) {
+        terrain.r#type = r#type;
+    }
+}
+
+/// Place the stairs on a ground tile of the last room, the farthest one from the player spawn
+fn place_stairs(tiles: &mut HashMap<IVec2, TerrainData>, rooms: &[Room], rng: &mut StdRng) {
+    let Some(room) = rooms.last() else {
+        return;
+    };
+
+    let candidates = room
+        .tiles()
+        .filter(|position| *position != room.center())
+        .filter(|position| {
+            tiles
+                .get(position)
+                .is_some_and(|terrain| terrain.r#type == TerrainType::Ground)
+        })
+        .collect::<Vec<_>>();
+
+    let stairs = if candidates.is_empty() {
+        room.center()
+    } else {
+        candidates[rng.gen_range(0..candidates.len())]
+    };
+
+    set_terrain(tiles, stairs, TerrainType::Stairs);
+}
+
+/// Carve a L shaped corridor between two positions
+fn carve_corridor(
+    tiles: &mut HashMap<IVec2, TerrainData>,
+    from: IVec2,
+    to: IVec2,
+    rng: &mut StdRng,
+) {
+    let corner = if rng.gen_bool(0.5) {
+        IVec2::new(to.x, from.y)
+    } else {
+        IVec2::new(from.x, to.y)
+    };
+
+    for (start, end) in [(from, corner), (corner, to)] {
+        for x in start.x.min(end.x)..=start.x.max(end.x) {
+            for y in start.y.min(end.y)..=start.y.max(end.y) {
+                set_terrain(tiles, IVec2::new(x, y), TerrainType::Ground);
+            }
+        }
+    }
+}
+
+/// Place a small pond of water, lava or abyss in one corner of the room, away from its center row / column
+fn place_pond(
+    tiles: &mut HashMap<IVec2, TerrainData>,
+    room: &Room,
+    environment: EnvironmentType,
+    rng: &mut StdRng,
+) {
+    let center = room.center();
+    let (x_range, y_range) = match rng.gen_range(0..4) {
+        0 => (room.min.x..center.x, room.min.y..center.y),
+        1 => (center.x + 1..room.max.x + 1, room.min.y..center.y),
+        2 => (room.min.x..center.x, center.y + 1..room.max.y + 1),
+        _ => (center.x + 1..room.max.x + 1, center.y + 1..room.max.y + 1),
+    };
+
+    // Leave a ground tile between the pond and the center lines of the room
+    let width = (x_range.len() as i32 - 1).min(2);
+    let height = (y_range.len() as i32 - 1).min(2);
+    if width <= 0 || height <= 0 {
+        return;
+    }
+
+    let origin = IVec2::new(
+        if x_range.start < center.x {
+            x_range.start
+        } else {
+            x_range.end - width
+        },
+        if y_range.start < center.y {
+            y_range.start
+        } else {
+            y_range.end - height
+        },
+    );
+
+    for x in origin.x..origin.x + width {
+        for y in origin.y..origin.y + height {
+            set_terrain(
+                tiles,
+                IVec2::new(x, y),
+                TerrainType::Environment(environment),
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn stairs(floor: &Floor) -> Vec<IVec2> {
+        floor
+            .tiles
+            .iter()
+            .filter(|(_, terrain)| terrain.r#type == TerrainType::Stairs)
+            .map(|(position, _)| *position)
+            .collect()
+    }
+
+    #[test]
+    fn generates_the_same_floor_from_the_same_seed() {
+        let first = FloorGenerator::new(42).generate();
+        let second = FloorGenerator::new(42).generate();
+
+        assert_eq!(first.tiles, second.tiles);
+        assert_eq!(first.rooms, second.rooms);
+        assert_eq!(stairs(&first).len(), 1);
+        assert_eq!(stairs(&first), stairs(&second));
+    }
+}
@@ -1,34 +1,633 @@
-use std::ops::RangeInclusive;
+use std::{collections::HashSet, ops::RangeInclusive, str::FromStr};
 
+use bevy::prelude::*;
 use char_animation::anim_key::AnimKey;
+use common::{element::Element, map_status::MapStatus};
+use spell_data::{
+    AreaLimit, AttackCoverage, SpellCategory, SpellData, SpellHeal, SpellHitbox, TargetAlignments,
+};
+
+use crate::{
+    data::assets::spell_data::SpellDataLookup,
+    ivec2::{IVec2Ext, DIRECTIONS},
+    map::{GameMap, TerrainType},
+    protection::Protection,
+    room::Room,
+    stats::BattleStat,
+    status::StatusCondition,
+};
 
 #[derive(Debug, Clone)]
 pub struct ProjectileSpell {
-    pub visual_effect: &'static str,
+    pub visual_effect: Option<String>,
 }
 
 #[derive(Debug, Clone)]
 pub enum SpellType {
+    /// Hits the tile in front of the caster
+    Melee,
     Projectile(ProjectileSpell),
+    /// Hits all the tiles around the caster
+    Adjacent,
+    /// Hits the tiles in front of and beside the caster
+    Wide,
+    /// Hits the tiles in front of the caster, widening with the distance
+    Cone {
+        range: i32,
+    },
+    /// Hits the tiles on both sides of the caster, across its facing direction
+    Sides {
+        range: i32,
+    },
+    /// Goes through all the pieces in a straight line until a wall
+    Line {
+        range: i32,
+        wide: bool,
+    },
+    /// Hits all the tiles in range around the caster
+    Burst {
+        range: i32,
+    },
+    /// Hits the whole room of the caster
+    Room,
+    /// Rushes the caster in a straight line, hitting the first target in range
+    Dash {
+        range: i32,
+    },
+    /// Only affects the caster, ie: Recover or Protect
+    SelfTarget,
+}
+
+// Area spells reaching this range cover the whole room
+const ROOM_RANGE: u32 = 10;
+// Range of the room spells casted outside of a room, ie: in a corridor
+const ROOM_FALLBACK_RANGE: i32 = 2;
+
+impl SpellType {
+    /// Tiles hit by an area spell casted from `position` in the given direction, empty for the
+    /// spells hitting a single target
+    pub fn affected_tiles(&self, map: &GameMap, position: IVec2, direction: IVec2) -> Vec<IVec2> {
+        let is_wall = |tile: &IVec2| {
+            map.tiles
+                .get(tile)
+                .is_none_or(|tile| tile.r#type == TerrainType::Wall)
+        };
+
+        let tiles: Vec<IVec2> = match self {
+            SpellType::Melee
+            | SpellType::Projectile(_)
+            | SpellType::Dash { .. }
+            | SpellType::SelfTarget => return Vec::new(),
+            SpellType::Adjacent => DIRECTIONS
+                .iter()
+                .map(|direction| position + *direction)
+                .chain([position])
+                .collect(),
+            SpellType::Wide => {
+                let direction = direction.as_vec2().normalize_or_zero();
+                burst_tiles(position, 1)
+                    .filter(|tile| *tile != position)
+                    // Within 90 degrees of the facing direction
+                    .filter(|tile| {
+                        let offset = (*tile - position).as_vec2().normalize_or_zero();
+                        offset.dot(direction) >= -f32::EPSILON
+                    })
+                    .collect()
+            }
+            SpellType::Cone { range } => {
+                let direction = direction.as_vec2().normalize_or_zero();
+                burst_tiles(position, *range)
+                    .filter(|tile| *tile != position)
+                    // Within 45 degrees of the facing direction
+                    .filter(|tile| {
+                        let offset = (*tile - position).as_vec2().normalize_or_zero();
+                        offset.dot(direction) >= std::f32::consts::FRAC_1_SQRT_2 - f32::EPSILON
+                    })
+                    .collect()
+            }
+            SpellType::Sides { range } => {
+                let side = side(direction);
+                [side, -side]
+                    .into_iter()
+                    .flat_map(|side| {
+                        (1..=*range)
+                            .map(move |i| position + side * i)
+                            .take_while(|tile| !is_wall(tile))
+                    })
+                    .collect()
+            }
+            SpellType::Line { range, wide } => {
+                let side = side(direction);
+                (1..=*range)
+                    .map(|i| position + direction * i)
+                    .take_while(|tile| !is_wall(tile))
+                    .flat_map(|tile| {
+                        if *wide {
+                            vec![tile, tile + side, tile - side]
+                        } else {
+                            vec![tile]
+                        }
+                    })
+                    .collect()
+            }
+            SpellType::Burst { range } => burst_tiles(position, *range).collect(),
+            SpellType::Room => {
+                let caster_tile = Room::new(position, IVec2::ONE);
+                match map
+                    .rooms
+                    .iter()
+                    .find(|room| room.intersects(&caster_tile, 1))
+                {
+                    // The entrances of the room are hit as well
+                    Some(room) => Room {
+                        min: room.min - IVec2::ONE,
+                        max: room.max + IVec2::ONE,
+                    }
+                    .tiles()
+                    .collect(),
+                    None => burst_tiles(position, ROOM_FALLBACK_RANGE).collect(),
+                }
+            }
+        };
+
+        tiles.into_iter().filter(|tile| !is_wall(tile)).collect()
+    }
+}
+
+/// Direction a quarter turn away from the given one
+fn side(direction: IVec2) -> IVec2 {
+    IVec2::new(-direction.y, direction.x).clamp(IVec2::NEG_ONE, IVec2::ONE)
+}
+
+/// Tiles at most `range` steps away from the position, including it
+fn burst_tiles(position: IVec2, range: i32) -> impl Iterator<Item = IVec2> {
+    (-range..=range)
+        .flat_map(move |y| (-range..=range).map(move |x| position + IVec2::new(x, y)))
+        .filter(move |tile| tile.chebyshev(position) <= range)
+}
+
+/// Status given when the spell hits
+#[derive(Debug, Clone)]
+pub struct SpellHitStatus {
+    pub condition: StatusCondition,
+    /// Given to the target, otherwise to the caster
+    pub affect_target: bool,
+    /// Chance to give the status in percent
+    pub chance: u32,
+}
+
+/// Stat stages changed when the spell hits
+#[derive(Debug, Clone)]
+pub struct SpellHitStatChange {
+    pub stat: BattleStat,
+    /// Positive to raise the stat, negative to lower it
+    pub stages: i32,
+    /// Given to the target, otherwise to the caster
+    pub affect_target: bool,
+    /// Chance to change the stat in percent
+    pub chance: u32,
 }
 
 #[derive(Debug, Clone)]
 pub struct SpellHit {
-    pub visual_effect: &'static str,
-    pub damage: i32,
+    pub visual_effect: String,
+    pub power: u32,
+    pub category: SpellCategory,
+    pub element: Element,
+    /// Chance to hit in percent, `None` if the spell never misses
+    pub hit_rate: Option<u32>,
+    pub statuses: Vec<SpellHitStatus>,
+    pub stat_changes: Vec<SpellHitStatChange>,
+    pub heal: Option<SpellHeal>,
+    /// Protection raised on the caster, ie: Protect or Substitute
+    pub protection: Option<Protection>,
 }
 
 #[derive(Debug, Clone)]
 pub struct SpellCast {
-    pub visual_effect: &'static str,
+    pub visual_effect: Option<String>,
     pub animation: AnimKey,
 }
 
 #[derive(Debug, Clone)]
 pub struct Spell {
-    pub name: &'static str,
+    pub id: String,
+    pub name: String,
     pub range: RangeInclusive<i32>,
     pub spell_type: SpellType,
+    pub target_alignments: TargetAlignments,
     pub hit: SpellHit,
     pub cast: SpellCast,
+    pub map_status: Option<MapStatus>,
+    /// Number of times the spell hits per cast
+    pub strikes: u32,
+}
+
+// Used when the spell data doesn't provide any hit effect
+const DEFAULT_HIT_VISUAL_EFFECT: &str = "Hit_Neutral";
+
+impl Spell {
+    /// Build a spell from the loaded spell data with the given ID, a missing ID is only reported
+    /// once through `reported`
+    pub fn load(
+        id: &str,
+        lookup: &SpellDataLookup,
+        assets: &Assets<SpellData>,
+        reported: &mut HashSet<String>,
+    ) -> Option<Self> {
+        let Some(data) = lookup.0.get(id).and_then(|handle| assets.get(handle)) else {
+            if reported.insert(id.to_string()) {
+                warn!("Failed to find spell data for {id}");
+            }
+            return None;
+        };
+
+        let spell = Self::from_data(id, data);
+        if spell.is_none() && reported.insert(id.to_string()) {
+            warn!("The hitbox of the spell {id} is not supported yet");
+        }
+        spell
+    }
+
+    /// Build a spell from its data, returns `None` if the hitbox of the spell is not supported
+    /// yet: the throws and the offset areas
+    pub fn from_data(id: &str, data: &SpellData) -> Option<Self> {
+        let (range, spell_type) = match &data.hitbox {
+            SpellHitbox::Projectile {
+                range,
+                visual_effect,
+                ..
+            } => (
+                1..=*range as i32,
+                SpellType::Projectile(ProjectileSpell {
+                    visual_effect: visual_effect.clone(),
+                }),
+            ),
+            SpellHitbox::Attack { coverage } => (
+                1..=1,
+                match coverage {
+                    AttackCoverage::Front => SpellType::Melee,
+                    AttackCoverage::FrontAndCorners => SpellType::Cone { range: 1 },
+                    AttackCoverage::Wide => SpellType::Wide,
+                    AttackCoverage::Around => SpellType::Adjacent,
+                },
+            ),
+            SpellHitbox::Area {
+                range,
+                limit: AreaLimit::Cone,
+            } => (
+                1..=*range as i32,
+                SpellType::Cone {
+                    range: *range as i32,
+                },
+            ),
+            SpellHitbox::Area {
+                range,
+                limit: AreaLimit::Sides,
+            } => (
+                1..=*range as i32,
+                SpellType::Sides {
+                    range: *range as i32,
+                },
+            ),
+            SpellHitbox::Area {
+                range,
+                limit: AreaLimit::Full,
+            } if *range >= ROOM_RANGE => (1..=*range as i32, SpellType::Room),
+            SpellHitbox::Area {
+                range,
+                limit: AreaLimit::Full,
+            } => (
+                1..=*range as i32,
+                SpellType::Burst {
+                    range: *range as i32,
+                },
+            ),
+            SpellHitbox::WaveMotion { range, wide } => (
+                1..=*range as i32,
+                SpellType::Line {
+                    range: *range as i32,
+                    wide: *wide,
+                },
+            ),
+            // `stop_at_hit` and `stop_at_wall` are ignored, the caster can't end its rush inside a
+            // wall or on the tile of another piece so the rush always stops before them
+            SpellHitbox::Dash { range, .. } => (
+                1..=*range as i32,
+                SpellType::Dash {
+                    range: *range as i32,
+                },
+            ),
+            SpellHitbox::SelfTarget => (0..=0, SpellType::SelfTarget),
+            _ => return None,
+        };
+
+        Some(Self {
+            id: id.to_string(),
+            name: data.name.default_text.clone(),
+            range,
+            spell_type,
+            target_alignments: data.target_alignments,
+            hit: SpellHit {
+                visual_effect: data
+                    .hit_visual_effect
+                    .clone()
+                    .unwrap_or(DEFAULT_HIT_VISUAL_EFFECT.to_string()),
+                power: data.power.unwrap_or(0),
+                category: data.category,
+                element: data.element,
+                hit_rate: data.hit_rate,
+                // The statuses not supported yet are ignored
+                statuses: data
+                    .statuses
+                    .iter()
+                    .filter_map(|status| {
+                        Some(SpellHitStatus {
+                            condition: StatusCondition::from_str(&status.status_id).ok()?,
+                            affect_target: status.affect_target,
+                            chance: status.chance,
+                        })
+                    })
+                    .collect(),
+                stat_changes: data
+                    .stat_changes
+                    .iter()
+                    .filter_map(|stat_change| {
+                        Some(SpellHitStatChange {
+                            stat: BattleStat::from_str(&stat_change.stat_id).ok()?,
+                            stages: stat_change.stages,
+                            affect_target: stat_change.affect_target,
+                            chance: stat_change.chance,
+                        })
+                    })
+                    .collect(),
+                heal: data.heal,
+                protection: data
+                    .statuses
+                    .iter()
+                    .find_map(|status| Protection::from_str(&status.status_id).ok()),
+            },
+            cast: SpellCast {
+                visual_effect: data.cast_visual_effect.clone(),
+                animation: data.cast_animation,
+            },
+            map_status: data.map_status,
+            strikes: data.strikes.max(1),
+        })
+    }
+}
+
+/// Spell data for the tests, a 40 power physical normal attack with 10 PP hitting the foes
+#[cfg(test)]
+pub fn test_spell_data(name: &str, hitbox: SpellHitbox) -> SpellData {
+    use common::text_data::{LocalTexts, TextData};
+
+    let text = |text: &str| TextData {
+        default_text: text.to_string(),
+        local_texts: LocalTexts {
+            ja: None,
+            ko: None,
+            zh_hant: None,
+            fr: None,
+            de: None,
+            es: None,
+            it: None,
+            ja_jp: None,
+            zh_hans: None,
+        },
+    };
+
+    SpellData {
+        name: text(name),
+        desc: text(""),
+        released: true,
+        comment: String::new(),
+        index_num: 0,
+        base_charges: 10,
+        strikes: 1,
+        element: Element::Normal,
+        category: SpellCategory::Physical,
+        hit_rate: None,
+        power: Some(40),
+        target_alignments: TargetAlignments {
+            caster: false,
+            friend: false,
+            foe: true,
+        },
+        hitbox,
+        cast_animation: AnimKey::Attack,
+        cast_visual_effect: None,
+        hit_visual_effect: None,
+        statuses: Vec::new(),
+        map_status: None,
+        heal: None,
+        stat_changes: Vec::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Center of the open map, with three ground tiles on every side
+    const CASTER: IVec2 = IVec2::new(4, 4);
+
+    fn open_map() -> GameMap {
+        GameMap::from_rows(&[
+            "#########",
+            "#.......#",
+            "#.......#",
+            "#.......#",
+            "#.......#",
+            "#.......#",
+            "#.......#",
+            "#.......#",
+            "#########",
+        ])
+    }
+
+    /// Affected tiles relative to the caster
+    fn offsets(
+        spell_type: SpellType,
+        map: &GameMap,
+        position: IVec2,
+        direction: IVec2,
+    ) -> HashSet<IVec2> {
+        spell_type
+            .affected_tiles(map, position, direction)
+            .into_iter()
+            .map(|tile| tile - position)
+            .collect()
+    }
+
+    fn set<const N: usize>(offsets: [(i32, i32); N]) -> HashSet<IVec2> {
+        offsets.into_iter().map(|(x, y)| IVec2::new(x, y)).collect()
+    }
+
+    #[test]
+    fn widens_the_cone_with_the_distance() {
+        let map = open_map();
+
+        assert_eq!(
+            offsets(SpellType::Cone { range: 1 }, &map, CASTER, IVec2::X),
+            set([(1, -1), (1, 0), (1, 1)])
+        );
+        assert_eq!(
+            offsets(SpellType::Cone { range: 2 }, &map, CASTER, IVec2::X),
+            set([
+                (1, -1),
+                (1, 0),
+                (1, 1),
+                (2, -2),
+                (2, -1),
+                (2, 0),
+                (2, 1),
+                (2, 2)
+            ])
+        );
+    }
+
+    #[test]
+    fn widens_the_diagonal_cone_with_the_distance() {
+        let map = open_map();
+
+        assert_eq!(
+            offsets(SpellType::Cone { range: 1 }, &map, CASTER, IVec2::ONE),
+            set([(1, 0), (1, 1), (0, 1)])
+        );
+        assert_eq!(
+            offsets(SpellType::Cone { range: 2 }, &map, CASTER, IVec2::ONE),
+            set([
+                (1, 0),
+                (1, 1),
+                (0, 1),
+                (2, 0),
+                (2, 1),
+                (2, 2),
+                (1, 2),
+                (0, 2)
+            ])
+        );
+    }
+
+    #[test]
+    fn hits_the_front_and_the_sides() {
+        let map = open_map();
+
+        assert_eq!(
+            offsets(SpellType::Wide, &map, CASTER, IVec2::X),
+            set([(0, -1), (1, -1), (1, 0), (1, 1), (0, 1)])
+        );
+        assert_eq!(
+            offsets(SpellType::Wide, &map, CASTER, IVec2::ONE),
+            set([(1, -1), (1, 0), (1, 1), (0, 1), (-1, 1)])
+        );
+        assert_eq!(
+            offsets(SpellType::Sides { range: 2 }, &map, CASTER, IVec2::X),
+            set([(0, -2), (0, -1), (0, 1), (0, 2)])
+        );
+    }
+
+    #[test]
+    fn stops_the_line_at_the_first_wall() {
+        let map = GameMap::from_rows(&["#########", "#...#...#", "#########"]);
+
+        assert_eq!(
+            offsets(
+                SpellType::Line {
+                    range: 10,
+                    wide: false
+                },
+                &map,
+                IVec2::new(1, 1),
+                IVec2::X
+            ),
+            set([(1, 0), (2, 0)])
+        );
+    }
+
+    #[test]
+    fn hits_both_sides_of_a_wide_line() {
+        let map = open_map();
+
+        assert_eq!(
+            offsets(
+                SpellType::Line {
+                    range: 2,
+                    wide: true
+                },
+                &map,
+                CASTER,
+                IVec2::X
+            ),
+            set([(1, -1), (1, 0), (1, 1), (2, -1), (2, 0), (2, 1)])
+        );
+    }
+
+    #[test]
+    fn hits_the_room_or_around_the_caster_in_a_corridor() {
+        let mut map = GameMap::from_rows(&[
+            "###########",
+            "#...#######",
+            "#.........#",
+            "#...#######",
+            "###########",
+        ]);
+        map.rooms = vec![Room::new(IVec2::ONE, IVec2::splat(3))];
+
+        // The whole room and its entrance
+        let tiles = SpellType::Room.affected_tiles(&map, IVec2::new(2, 2), IVec2::X);
+        assert_eq!(tiles.len(), 10);
+        assert!(tiles.contains(&IVec2::new(4, 2)));
+        assert!(!tiles.contains(&IVec2::new(5, 2)));
+
+        assert_eq!(
+            offsets(SpellType::Room, &map, IVec2::new(8, 2), IVec2::X),
+            set([(-2, 0), (-1, 0), (0, 0), (1, 0)])
+        );
+    }
+
+    #[test]
+    fn loads_the_sides_and_wide_hitboxes() {
+        let sides = test_spell_data(
+            "Sides",
+            SpellHitbox::Area {
+                range: 2,
+                limit: AreaLimit::Sides,
+            },
+        );
+        let wide = test_spell_data(
+            "Wide",
+            SpellHitbox::Attack {
+                coverage: AttackCoverage::Wide,
+            },
+        );
+
+        assert!(matches!(
+            Spell::from_data("sides", &sides).unwrap().spell_type,
+            SpellType::Sides { range: 2 }
+        ));
+        assert!(matches!(
+            Spell::from_data("wide", &wide).unwrap().spell_type,
+            SpellType::Wide
+        ));
+    }
+
+    #[test]
+    fn skips_the_unknown_stat_changes() {
+        let mut data = test_spell_data("Growl", SpellHitbox::SelfTarget);
+        data.stat_changes = ["mod_attack", "mod_unknown"]
+            .map(|stat_id| spell_data::SpellStatChange {
+                stat_id: stat_id.to_string(),
+                stages: -1,
+                affect_target: true,
+                chance: 100,
+            })
+            .to_vec();
+
+        let spell = Spell::from_data("growl", &data).unwrap();
+
+        assert_eq!(spell.hit.stat_changes.len(), 1);
+        assert_eq!(spell.hit.stat_changes[0].stat, BattleStat::Attack);
+    }
 }
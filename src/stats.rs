@@ -1,11 +1,14 @@
 use bevy::prelude::*;
 use bevy_inspector_egui::{inspector_options::ReflectInspectorOptions, InspectorOptions};
 use pokemon_data::PokemonData;
+use strum::EnumString;
 
-use crate::{pokemons::Pokemon, GameState};
+use crate::{map::FloorChangedEvent, pokemons::Pokemon, regeneration::Regeneration, GameState};
 
 const MAX_STAT: i32 = 255;
 const MAX_HP: i32 = 999;
+pub const MIN_STAGE: i32 = -6;
+pub const MAX_STAGE: i32 = 6;
 
 pub struct StatsPlugin;
 
@@ -13,11 +16,18 @@ impl Plugin for StatsPlugin {
     fn build(&self, app: &mut App) {
         app.register_type::<Stats>()
             .register_type::<Health>()
+            .register_type::<Level>()
+            .register_type::<Experience>()
+            .add_event::<StatStageChangedEvent>()
             .add_systems(
                 Update,
                 (update_stats_system, add_health_system)
                     .chain()
                     .run_if(in_state(GameState::Playing)),
+            )
+            .add_systems(
+                Update,
+                reset_stat_stages_system.run_if(on_event::<FloorChangedEvent>()),
             );
     }
 }
@@ -25,6 +35,7 @@ impl Plugin for StatsPlugin {
 #[derive(Debug, Default, Reflect)]
 pub struct Stat {
     base: i32,
+    /// Stage of the stat, between `MIN_STAGE` and `MAX_STAGE`
     bonus: i32,
 }
 
@@ -34,10 +45,84 @@ impl Stat {
     }
 
     pub fn value(&self) -> i32 {
-        self.base + self.bonus
+        (self.base as f32 * stage_multiplier(self.bonus)) as i32
+    }
+
+    pub fn stage(&self) -> i32 {
+        self.bonus
+    }
+
+    /// Raise or lower the stage, returns the number of stages actually changed
+    pub fn change_stage(&mut self, stages: i32) -> i32 {
+        let previous = self.bonus;
+        self.bonus = (self.bonus + stages).clamp(MIN_STAGE, MAX_STAGE);
+        self.bonus - previous
+    }
+}
+
+/// Multiplier of a stat at the given stage, based on the main series one
+pub fn stage_multiplier(stage: i32) -> f32 {
+    let stage = stage.clamp(MIN_STAGE, MAX_STAGE) as f32;
+    if stage >= 0. {
+        (2. + stage) / 2.
+    } else {
+        2. / (2. - stage)
+    }
+}
+
+/// Stat that can be raised or lowered in battle, matches the `StatusID` of the spell data
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, EnumString, Reflect)]
+pub enum BattleStat {
+    #[strum(serialize = "mod_attack")]
+    Attack,
+    #[strum(serialize = "mod_defense")]
+    Defense,
+    #[strum(serialize = "mod_special_attack")]
+    SpecialAttack,
+    #[strum(serialize = "mod_special_defense")]
+    SpecialDefense,
+    #[strum(serialize = "mod_speed")]
+    Speed,
+    #[strum(serialize = "mod_accuracy")]
+    Accuracy,
+    #[strum(serialize = "mod_evasion")]
+    Evasion,
+}
+
+impl BattleStat {
+    pub fn name(&self) -> &'static str {
+        match self {
+            BattleStat::Attack => "Attack",
+            BattleStat::Defense => "Defense",
+            BattleStat::SpecialAttack => "Sp. Atk.",
+            BattleStat::SpecialDefense => "Sp. Def.",
+            BattleStat::Speed => "Speed",
+            BattleStat::Accuracy => "Accuracy",
+            BattleStat::Evasion => "Evasion",
+        }
     }
 }
 
+/// Sent when a stage of the entity is raised or lowered
+#[derive(Event, Debug)]
+pub struct StatStageChangedEvent {
+    pub entity: Entity,
+    pub stat: BattleStat,
+    /// Stages asked by the action
+    pub stages: i32,
+    /// Stages actually changed, `0` if the stage was already at its limit
+    pub changed: i32,
+}
+
+#[derive(Component, Debug, Clone, Copy, Reflect)]
+#[reflect(Component)]
+pub struct Level(pub u32);
+
+/// Experience gathered since the current level was reached
+#[derive(Component, Debug, Default, Clone, Copy, Reflect)]
+#[reflect(Component)]
+pub struct Experience(pub u32);
+
 #[derive(Component, Default, Reflect)]
 pub struct Health {
     pub value: i32,
@@ -59,37 +144,114 @@ pub struct Stats {
     pub defense: Stat,
     pub special_defense: Stat,
     pub speed: Stat,
+    /// Only the stage of the accuracy and the evasion is used, see `accuracy_multiplier`
+    pub accuracy: Stat,
+    pub evasion: Stat,
+}
+
+impl Stats {
+    pub fn get(&self, stat: BattleStat) -> &Stat {
+        match stat {
+            BattleStat::Attack => &self.attack,
+            BattleStat::Defense => &self.defense,
+            BattleStat::SpecialAttack => &self.special_attack,
+            BattleStat::SpecialDefense => &self.special_defense,
+            BattleStat::Speed => &self.speed,
+            BattleStat::Accuracy => &self.accuracy,
+            BattleStat::Evasion => &self.evasion,
+        }
+    }
+
+    pub fn get_mut(&mut self, stat: BattleStat) -> &mut Stat {
+        match stat {
+            BattleStat::Attack => &mut self.attack,
+            BattleStat::Defense => &mut self.defense,
+            BattleStat::SpecialAttack => &mut self.special_attack,
+            BattleStat::SpecialDefense => &mut self.special_defense,
+            BattleStat::Speed => &mut self.speed,
+            BattleStat::Accuracy => &mut self.accuracy,
+            BattleStat::Evasion => &mut self.evasion,
+        }
+    }
+
+    pub fn reset_stages(&mut self) {
+        for stat in [
+            &mut self.attack,
+            &mut self.defense,
+            &mut self.special_attack,
+            &mut self.special_defense,
+            &mut self.speed,
+            &mut self.accuracy,
+            &mut self.evasion,
+        ] {
+            stat.bonus = 0;
+        }
+    }
+}
+
+/// Value of a stat at a given level, based on the main series formula
+fn stat_at_level(base: i32, level: u32) -> i32 {
+    (2 * base * level as i32 / 100 + 5).min(MAX_STAT)
 }
 
-/// Add or update the base stats of a pokemon based on its pokemon data
+fn hp_at_level(base: i32, level: u32) -> i32 {
+    (2 * base * level as i32 / 100 + level as i32 + 10).min(MAX_HP)
+}
+
+/// Add or update the stats of a pokemon based on its pokemon data and level
 #[allow(clippy::type_complexity)]
 fn update_stats_system(
     mut query: Query<
-        (Entity, &Pokemon, &Handle<PokemonData>, Option<&mut Stats>),
-        Changed<Handle<PokemonData>>,
+        (
+            Entity,
+            &Pokemon,
+            &Level,
+            &Handle<PokemonData>,
+            Option<&mut Stats>,
+            Option<&mut Health>,
+        ),
+        Or<(
+            Changed<Handle<PokemonData>>,
+            Changed<Level>,
+            Changed<Pokemon>,
+        )>,
     >,
     pokemon_data: Res<Assets<PokemonData>>,
     mut commands: Commands,
 ) {
-    for (entity, pokemon, pokemon_data_handle, mut stats) in query.iter_mut() {
+    for (entity, pokemon, level, pokemon_data_handle, mut stats, health) in query.iter_mut() {
         let Some(data) = pokemon_data.get(pokemon_data_handle) else {
             warn!("Unable to retrieve pokemon data for stats");
             continue;
         };
 
-        let pokemon_form = &data.forms[pokemon.form_index];
+        let Some(pokemon_form) = data.forms.get(pokemon.form_index) else {
+            warn!(
+                "Unable to retrieve pokemon form {} for stats",
+                pokemon.form_index
+            );
+            continue;
+        };
+        let level = level.0;
 
         let update_base_stats = |stats: &mut Stats| {
-            stats.attack.base = pokemon_form.base_atk;
-            stats.special_attack.base = pokemon_form.base_m_atk;
-            stats.defense.base = pokemon_form.base_def;
-            stats.special_defense.base = pokemon_form.base_m_def;
-            stats.speed.base = pokemon_form.base_speed;
-            stats.health.base = pokemon_form.base_hp;
+            stats.attack.base = stat_at_level(pokemon_form.base_atk, level);
+            stats.special_attack.base = stat_at_level(pokemon_form.base_m_atk, level);
+            stats.defense.base = stat_at_level(pokemon_form.base_def, level);
+            stats.special_defense.base = stat_at_level(pokemon_form.base_m_def, level);
+            stats.speed.base = stat_at_level(pokemon_form.base_speed, level);
+            stats.health.base = hp_at_level(pokemon_form.base_hp, level);
         };
 
         if let Some(stats) = stats.as_mut() {
-            update_base_stats(stats)
+            update_base_stats(stats);
+
+            // Keep the damage already taken when the max health changes
+            if let Some(mut health) = health.filter(|health| !health.is_dead()) {
+                let max = stats.health.value();
+                health.value = (health.value + max - health.max).clamp(1, max);
+                health.max = max;
+            }
         } else {
             let mut stats = Stats::default();
             update_base_stats(&mut stats);
@@ -100,9 +262,121 @@ fn update_stats_system(
 
 fn add_health_system(mut query: Query<(Entity, &Stats), Added<Stats>>, mut commands: Commands) {
     for (entity, stats) in query.iter_mut() {
-        commands.entity(entity).insert(Health {
-            value: stats.health.value(),
-            max: stats.health.value(),
-        });
+        commands.entity(entity).insert((
+            Health {
+                value: stats.health.value(),
+                max: stats.health.value(),
+            },
+            Regeneration::default(),
+        ));
+    }
+}
+
+/// The stages don't carry over to the next floor
+fn reset_stat_stages_system(mut query: Query<&mut Stats>) {
+    for mut stats in query.iter_mut() {
+        stats.reset_stages();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pokemon_data::{LocalTexts, PokemonForm, TextData};
+
+    use crate::pokemons::test_pokemon_form;
+
+    use super::*;
+
+    fn text(text: &str) -> TextData {
+        TextData {
+            default_text: text.to_string(),
+            local_texts: LocalTexts {
+                ja: None,
+                ko: None,
+                zh_hant: None,
+                fr: None,
+                de: None,
+                es: None,
+                it: None,
+                ja_jp: None,
+                zh_hans: None,
+            },
+        }
+    }
+
+    fn form(base_atk: i32) -> PokemonForm {
+        PokemonForm {
+            base_atk,
+            ..test_pokemon_form(&[])
+        }
+    }
+
+    #[test]
+    fn refreshes_the_stats_on_a_form_change() {
+        let data = PokemonData {
+            name: text("Pokemon"),
+            released: true,
+            comment: String::new(),
+            title: text("Pokemon"),
+            index_num: 1,
+            exp_table: "medium_fast".to_string(),
+            skill_group1: String::new(),
+            skill_group2: String::new(),
+            join_rate: 0,
+            promote_from: String::new(),
+            promotions: vec![],
+            forms: vec![form(50), form(100)],
+        };
+
+        let mut app = App::new();
+        app.init_resource::<Assets<PokemonData>>()
+            .add_systems(Update, update_stats_system);
+        let handle = app.world.resource_mut::<Assets<PokemonData>>().add(data);
+        let entity = app
+            .world
+            .spawn((
+                Pokemon {
+                    id: 1,
+                    form_index: 0,
+                },
+                Level(50),
+                handle,
+            ))
+            .id();
+
+        app.update();
+        assert_eq!(app.world.get::<Stats>(entity).unwrap().attack.value(), 55);
+
+        // A form-only evolution keeps the species and the data handle
+        app.world.get_mut::<Pokemon>(entity).unwrap().form_index = 1;
+        app.update();
+        assert_eq!(app.world.get::<Stats>(entity).unwrap().attack.value(), 105);
+    }
+
+    #[test]
+    fn multiplies_the_stat_with_the_stage() {
+        assert_eq!(stage_multiplier(0), 1.);
+        assert_eq!(stage_multiplier(1), 1.5);
+        assert_eq!(stage_multiplier(-1), 2. / 3.);
+        // 8/2 and 2/8 are the endpoints, the stages past them are clamped
+        assert_eq!(stage_multiplier(MAX_STAGE), 4.);
+        assert_eq!(stage_multiplier(MIN_STAGE), 0.25);
+        assert_eq!(stage_multiplier(MAX_STAGE + 1), 4.);
+        assert_eq!(stage_multiplier(MIN_STAGE - 1), 0.25);
+    }
+
+    #[test]
+    fn clamps_the_stage_changes() {
+        let mut stat = Stat::new(100);
+
+        assert_eq!(stat.change_stage(4), 4);
+        // Only the stages up to the limit are applied
+        assert_eq!(stat.change_stage(4), 2);
+        assert_eq!(stat.change_stage(1), 0);
+        assert_eq!(stat.value(), 400);
+
+        assert_eq!(stat.change_stage(-20), MIN_STAGE - MAX_STAGE);
+        assert_eq!(stat.change_stage(-1), 0);
+        assert_eq!(stat.value(), 25);
     }
 }
@@ -0,0 +1,262 @@
+//! Status conditions, based on the main series ones
+//! https://bulbapedia.bulbagarden.net/wiki/Status_condition
+
+use std::ops::RangeInclusive;
+
+use bevy::prelude::*;
+use common::element::Element;
+use rand::Rng;
+use strum::EnumString;
+
+use crate::pokemons::Elements;
+
+/// Chance in percent for a paralyzed pokemon to be unable to act
+pub const PARALYSIS_CHANCE: u32 = 25;
+/// Chance in percent for a confused pokemon to stumble in a random direction
+pub const CONFUSION_CHANCE: u32 = 50;
+// Poison and burn deal this fraction of the max health each turn
+const DAMAGE_DIVISOR: i32 = 16;
+
+pub struct StatusPlugin;
+
+impl Plugin for StatusPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<StatusConditions>();
+    }
+}
+
+/// Status given by a spell, matches the `StatusID` of the spell data
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, EnumString, Reflect)]
+#[strum(serialize_all = "snake_case")]
+pub enum StatusCondition {
+    #[strum(serialize = "poison", serialize = "poison_toxic")]
+    Poison,
+    Burn,
+    Sleep,
+    #[strum(serialize = "paralyze")]
+    Paralysis,
+    #[strum(serialize = "confuse")]
+    Confusion,
+}
+
+impl StatusCondition {
+    /// Number of turns the status lasts, picked when it is given
+    pub fn turns(&self) -> RangeInclusive<u32> {
+        match self {
+            StatusCondition::Poison | StatusCondition::Burn => 8..=12,
+            StatusCondition::Paralysis => 6..=10,
+            StatusCondition::Sleep => 3..=5,
+            StatusCondition::Confusion => 4..=6,
+        }
+    }
+
+    /// Damage taken at the end of each turn
+    pub fn damage(&self, max_health: i32) -> Option<i32> {
+        match self {
+            StatusCondition::Poison | StatusCondition::Burn => {
+                Some((max_health / DAMAGE_DIVISOR).max(1))
+            }
+            _ => None,
+        }
+    }
+
+    /// A pokemon has at most one non volatile status
+    pub fn is_volatile(&self) -> bool {
+        matches!(self, StatusCondition::Confusion)
+    }
+
+    pub fn is_immune(&self, elements: &Elements) -> bool {
+        match self {
+            StatusCondition::Poison => {
+                elements.contains(Element::Poison) || elements.contains(Element::Steel)
+            }
+            StatusCondition::Burn => elements.contains(Element::Fire),
+            StatusCondition::Paralysis => elements.contains(Element::Electric),
+            StatusCondition::Sleep | StatusCondition::Confusion => false,
+        }
+    }
+
+    /// Visual effect looping over the pokemon while the status lasts
+    pub fn icon(&self) -> &'static str {
+        match self {
+            StatusCondition::Poison => "Bubbles_Purple",
+            StatusCondition::Burn => "Burned",
+            StatusCondition::Sleep => "Sleep_Z",
+            StatusCondition::Paralysis => "Spark",
+            StatusCondition::Confusion => "Emote_Question",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Reflect)]
+pub struct ActiveStatus {
+    pub condition: StatusCondition,
+    /// Remaining turns before the status expires
+    pub turns: u32,
+    /// Piece which gave the status, credited for the defeat it causes
+    pub source: Entity,
+}
+
+#[derive(Component, Debug, Default, Clone, Reflect)]
+#[reflect(Component)]
+pub struct StatusConditions(pub Vec<ActiveStatus>);
+
+impl StatusConditions {
+    pub fn has(&self, condition: StatusCondition) -> bool {
+        self.0.iter().any(|status| status.condition == condition)
+    }
+
+    /// The same status can't be given twice, nor a second non volatile status
+    pub fn can_receive(&self, condition: StatusCondition) -> bool {
+        !self.0.iter().any(|status| {
+            status.condition == condition
+                || (!condition.is_volatile() && !status.condition.is_volatile())
+        })
+    }
+
+    /// Status preventing the pokemon from acting this turn, the paralysis and the confusion
+    /// only block it by chance
+    pub fn blocking_condition(&self, rng: &mut impl Rng) -> Option<StatusCondition> {
+        if self.has(StatusCondition::Sleep) {
+            return Some(StatusCondition::Sleep);
+        }
+        if self.has(StatusCondition::Paralysis) && rng.gen_ratio(PARALYSIS_CHANCE, 100) {
+            return Some(StatusCondition::Paralysis);
+        }
+        if self.has(StatusCondition::Confusion) && rng.gen_ratio(CONFUSION_CHANCE, 100) {
+            return Some(StatusCondition::Confusion);
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::rngs::mock::StepRng;
+
+    use crate::actions::{
+        cure_status_action::CureStatusAction, indirect_damage_action::IndirectDamageAction,
+        status_tick_action::StatusTickAction, Action, ActionQueue,
+    };
+    use crate::stats::Health;
+
+    use super::*;
+
+    fn status(condition: StatusCondition, turns: u32) -> ActiveStatus {
+        ActiveStatus {
+            condition,
+            turns,
+            source: Entity::PLACEHOLDER,
+        }
+    }
+
+    #[test]
+    fn deals_a_fraction_of_the_max_health() {
+        assert_eq!(StatusCondition::Poison.damage(160), Some(10));
+        // At least one point of damage
+        assert_eq!(StatusCondition::Burn.damage(10), Some(1));
+        assert_eq!(StatusCondition::Sleep.damage(160), None);
+    }
+
+    #[test]
+    fn keeps_a_single_non_volatile_status() {
+        let status_conditions = StatusConditions(vec![status(StatusCondition::Poison, 5)]);
+
+        assert!(!status_conditions.can_receive(StatusCondition::Poison));
+        assert!(!status_conditions.can_receive(StatusCondition::Sleep));
+        assert!(status_conditions.can_receive(StatusCondition::Confusion));
+
+        let confused = StatusConditions(vec![status(StatusCondition::Confusion, 5)]);
+        assert!(!confused.can_receive(StatusCondition::Confusion));
+        assert!(confused.can_receive(StatusCondition::Burn));
+    }
+
+    #[test]
+    fn protects_the_immune_elements() {
+        let elements = |primary, secondary| Elements { primary, secondary };
+
+        assert!(StatusCondition::Poison.is_immune(&elements(Element::Grass, Element::Poison)));
+        assert!(StatusCondition::Poison.is_immune(&elements(Element::Steel, Element::None)));
+        assert!(StatusCondition::Burn.is_immune(&elements(Element::Fire, Element::None)));
+        assert!(StatusCondition::Paralysis.is_immune(&elements(Element::Electric, Element::None)));
+        assert!(!StatusCondition::Burn.is_immune(&elements(Element::Water, Element::None)));
+        assert!(!StatusCondition::Sleep.is_immune(&elements(Element::Fire, Element::Electric)));
+    }
+
+    #[test]
+    fn blocks_the_sleeping_pokemons() {
+        let status_conditions = StatusConditions(vec![status(StatusCondition::Sleep, 3)]);
+
+        // Even with the luckiest roll
+        assert_eq!(
+            status_conditions.blocking_condition(&mut StepRng::new(u64::MAX, 0)),
+            Some(StatusCondition::Sleep)
+        );
+    }
+
+    #[test]
+    fn blocks_the_paralyzed_and_confused_pokemons_by_chance() {
+        let paralyzed = StatusConditions(vec![status(StatusCondition::Paralysis, 3)]);
+        let confused = StatusConditions(vec![status(StatusCondition::Confusion, 3)]);
+        // The lowest roll passes any chance, the highest fails it
+        let (mut low, mut high) = (StepRng::new(0, 0), StepRng::new(u64::MAX, 0));
+
+        assert_eq!(
+            paralyzed.blocking_condition(&mut low),
+            Some(StatusCondition::Paralysis)
+        );
+        assert_eq!(paralyzed.blocking_condition(&mut high), None);
+        assert_eq!(
+            confused.blocking_condition(&mut low),
+            Some(StatusCondition::Confusion)
+        );
+        assert_eq!(confused.blocking_condition(&mut high), None);
+        assert_eq!(
+            StatusConditions::default().blocking_condition(&mut low),
+            None
+        );
+    }
+
+    #[test]
+    fn ticks_down_and_expires_the_statuses() {
+        let mut world = World::new();
+        world.insert_resource(ActionQueue::default());
+        let entity = world
+            .spawn((
+                Health {
+                    value: 100,
+                    max: 160,
+                },
+                StatusConditions(vec![
+                    status(StatusCondition::Poison, 1),
+                    status(StatusCondition::Confusion, 3),
+                ]),
+            ))
+            .id();
+
+        StatusTickAction { entity }.execute(&mut world).unwrap();
+
+        let turns: Vec<_> = world
+            .get::<StatusConditions>(entity)
+            .unwrap()
+            .0
+            .iter()
+            .map(|status| status.turns)
+            .collect();
+        assert_eq!(turns, vec![0, 2]);
+
+        // The poison damage comes before its cure, the confusion is still running
+        let queue = &world.resource::<ActionQueue>().0;
+        assert_eq!(queue.len(), 2);
+        let damage = queue[0].performable_actions[0]
+            .as_any()
+            .downcast_ref::<IndirectDamageAction>()
+            .unwrap();
+        assert_eq!(damage.value, 10);
+        let cure = queue[1].performable_actions[0]
+            .as_any()
+            .downcast_ref::<CureStatusAction>()
+            .unwrap();
+        assert_eq!(cure.condition, StatusCondition::Poison);
+    }
+}
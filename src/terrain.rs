@@ -0,0 +1,217 @@
+//! Terrain rules of the environment tiles, which pieces can cross them and the lava damage
+
+use bevy::prelude::*;
+use common::element::Element;
+
+use crate::{
+    actions::{
+        indirect_damage_action::{IndirectDamageAction, IndirectDamageCause},
+        ActionQueue, QueuedAction,
+    },
+    map::{EnvironmentType, GameMap, Position, TerrainType},
+    player::PlayerActionEvent,
+    pokemons::Elements,
+    stats::Health,
+    turn::turn_system,
+    GamePlayingSet,
+};
+
+// Lava deals this fraction of the max health each turn
+const LAVA_DAMAGE_DIVISOR: i32 = 8;
+
+pub struct TerrainPlugin;
+
+impl Plugin for TerrainPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            Update,
+            terrain_turn_system
+                .after(turn_system)
+                .in_set(GamePlayingSet::TurnLogics),
+        );
+    }
+}
+
+/// How a piece moves over the environment tiles, matches the `WaterTerrainState`,
+/// `LavaTerrainState` and `AbyssTerrainState` of the spell data
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Mobility {
+    /// Can cross the water
+    pub water: bool,
+    /// Every piece can walk on the lava, only these ones bear its heat
+    pub lava_immune: bool,
+    /// Can cross the abyss
+    pub abyss: bool,
+}
+
+impl Mobility {
+    /// Water types swim, fire types bear the lava and flying types hover over everything
+    pub fn from_elements(elements: &Elements) -> Self {
+        let flying = elements.contains(Element::Flying);
+        Self {
+            water: flying || elements.contains(Element::Water),
+            lava_immune: flying || elements.contains(Element::Fire),
+            abyss: flying,
+        }
+    }
+
+    pub fn can_cross(&self, terrain: &TerrainType) -> bool {
+        match terrain {
+            TerrainType::Ground | TerrainType::Stairs => true,
+            TerrainType::Wall => false,
+            TerrainType::Environment(EnvironmentType::Water) => self.water,
+            TerrainType::Environment(EnvironmentType::Lava) => true,
+            TerrainType::Environment(EnvironmentType::Abyss) => self.abyss,
+        }
+    }
+
+    /// Tiles the pathfinding goes through, the lava is avoided by the pieces it would burn
+    pub fn is_safe(&self, terrain: &TerrainType) -> bool {
+        match terrain {
+            TerrainType::Environment(EnvironmentType::Lava) => self.lava_immune,
+            _ => self.can_cross(terrain),
+        }
+    }
+}
+
+/// Mobility of the piece, a piece without elements only walks on the ground
+pub fn mobility(world: &World, entity: Entity) -> Mobility {
+    world
+        .get::<Elements>(entity)
+        .map(Mobility::from_elements)
+        .unwrap_or_default()
+}
+
+/// Damage taken at the end of the turn when standing on the environment, only the fire and
+/// flying types bear the heat of the lava
+pub fn terrain_damage(
+    environment: EnvironmentType,
+    elements: &Elements,
+    max_health: i32,
+) -> Option<i32> {
+    match environment {
+        EnvironmentType::Lava if !Mobility::from_elements(elements).lava_immune => {
+            Some((max_health / LAVA_DAMAGE_DIVISOR).max(1))
+        }
+        _ => None,
+    }
+}
+
+/// Burn the pieces standing on the lava once all the actors took their turn
+fn terrain_turn_system(
+    query: Query<(Entity, &Position, &Health, &Elements)>,
+    map: Res<GameMap>,
+    mut action_queue: ResMut<ActionQueue>,
+    mut ev_player_action: EventReader<PlayerActionEvent>,
+) {
+    if ev_player_action.read().count() == 0 {
+        return;
+    }
+
+    for (entity, position, health, elements) in query.iter() {
+        let Some(TerrainType::Environment(environment)) =
+            map.tiles.get(&position.0).map(|tile| tile.r#type)
+        else {
+            continue;
+        };
+        let Some(value) = terrain_damage(environment, elements, health.max) else {
+            continue;
+        };
+        action_queue.0.push_back(QueuedAction {
+            entity,
+            performable_actions: vec![Box::new(IndirectDamageAction {
+                entity,
+                value,
+                cause: IndirectDamageCause::Terrain(environment),
+            })],
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn elements(primary: Element, secondary: Element) -> Elements {
+        Elements { primary, secondary }
+    }
+
+    const WATER: TerrainType = TerrainType::Environment(EnvironmentType::Water);
+    const LAVA: TerrainType = TerrainType::Environment(EnvironmentType::Lava);
+    const ABYSS: TerrainType = TerrainType::Environment(EnvironmentType::Abyss);
+
+    #[test]
+    fn derives_the_mobility_from_the_elements() {
+        let walker = Mobility::from_elements(&elements(Element::Normal, Element::None));
+        let swimmer = Mobility::from_elements(&elements(Element::Grass, Element::Water));
+        let flyer = Mobility::from_elements(&elements(Element::Normal, Element::Flying));
+
+        assert_eq!(walker, Mobility::default());
+        assert!(swimmer.water && !swimmer.lava_immune && !swimmer.abyss);
+        assert!(flyer.water && flyer.lava_immune && flyer.abyss);
+        assert!(Mobility::from_elements(&elements(Element::Fire, Element::None)).lava_immune);
+    }
+
+    #[test]
+    fn lets_every_walker_onto_the_lava() {
+        let walker = Mobility::default();
+        let swimmer = Mobility::from_elements(&elements(Element::Water, Element::None));
+
+        assert!(walker.can_cross(&TerrainType::Ground));
+        assert!(walker.can_cross(&TerrainType::Stairs));
+        assert!(!walker.can_cross(&TerrainType::Wall));
+        assert!(!walker.can_cross(&WATER));
+        assert!(!walker.can_cross(&ABYSS));
+        assert!(swimmer.can_cross(&WATER));
+
+        // The lava can be crossed but the pathfinding avoids it
+        assert!(walker.can_cross(&LAVA));
+        assert!(!walker.is_safe(&LAVA));
+        assert!(Mobility::from_elements(&elements(Element::Fire, Element::None)).is_safe(&LAVA));
+    }
+
+    #[test]
+    fn burns_the_walkers_without_immunity() {
+        assert_eq!(
+            terrain_damage(
+                EnvironmentType::Lava,
+                &elements(Element::Grass, Element::None),
+                80
+            ),
+            Some(10)
+        );
+        // At least one point of damage
+        assert_eq!(
+            terrain_damage(
+                EnvironmentType::Lava,
+                &elements(Element::Water, Element::None),
+                4
+            ),
+            Some(1)
+        );
+        assert_eq!(
+            terrain_damage(
+                EnvironmentType::Lava,
+                &elements(Element::Fire, Element::None),
+                80
+            ),
+            None
+        );
+        assert_eq!(
+            terrain_damage(
+                EnvironmentType::Lava,
+                &elements(Element::Bug, Element::Flying),
+                80
+            ),
+            None
+        );
+        assert_eq!(
+            terrain_damage(
+                EnvironmentType::Water,
+                &elements(Element::Grass, Element::None),
+                80
+            ),
+            None
+        );
+    }
+}
@@ -1,40 +1,134 @@
 use std::collections::VecDeque;
 
 use bevy::prelude::*;
+use common::map_status::MapStatus;
 
 use crate::{
-    actions::{ActionQueue, NextActions, ProcessingActionEvent, QueuedAction},
+    actions::{
+        plan_action::PlanAction, protection_tick_action::ProtectionTickAction,
+        status_check_action::StatusCheckAction, status_tick_action::StatusTickAction, Action,
+        ActionQueue, NextActions, ProcessingActionEvent, QueuedAction,
+    },
+    map_status::MapStatuses,
     pieces::Actor,
     player::{Player, PlayerActionEvent},
+    protection::Protected,
     stats::{Health, Stats},
+    status::StatusConditions,
     GamePlayingSet,
 };
 
+/// Energy spent by an actor to take one action
+pub const ACTION_COST: i32 = 100;
+// Energy can't pile up, a fast actor takes at most two actions per turn
+const MAX_ENERGY: i32 = 2 * ACTION_COST;
+// A slow actor still acts at least once every four turns
+const MIN_ENERGY_GAIN: i32 = ACTION_COST / 4;
+
 pub struct TurnPlugin;
 
 impl Plugin for TurnPlugin {
     fn build(&self, app: &mut App) {
         app.init_resource::<TurnOrder>()
+            .init_resource::<TurnOrderPreview>()
+            .register_type::<Energy>()
             .add_systems(
                 Update,
-                (add_actor_to_queue, turn_system)
+                (add_actor_to_queue, turn_system, update_turn_order_preview)
                     .chain()
                     .in_set(GamePlayingSet::TurnLogics),
             )
             .add_systems(
                 Update,
-                handle_actor_death.in_set(GamePlayingSet::LateLogics),
+                (handle_actor_death, remove_despawned_actors).in_set(GamePlayingSet::LateLogics),
             );
     }
 }
 
+/// Actors in spawn order, used to break energy ties
 #[derive(Default, Resource)]
 pub struct TurnOrder(pub VecDeque<Entity>);
 
+/// Actors in the order they will act during the next turn, an actor can appear twice
+#[derive(Default, Resource, Debug)]
+pub struct TurnOrderPreview(pub Vec<Entity>);
+
+/// Energy accumulated by an actor, it acts once it reaches [`ACTION_COST`]
+#[derive(Component, Default, Debug, Reflect)]
+#[reflect(Component)]
+pub struct Energy(pub i32);
+
+/// Energy gained by an actor each turn, relative to the speed of the player
+/// which always acts exactly once per turn.
+/// The speed stages are included in the value of the speed stat.
+/// Under Trick Room the slower actors gain the most energy.
+fn energy_gain(stats: Option<&Stats>, player_stats: Option<&Stats>, trick_room: bool) -> i32 {
+    let (Some(stats), Some(player_stats)) = (stats, player_stats) else {
+        return ACTION_COST;
+    };
+    let speed = stats.speed.value().max(1);
+    let player_speed = player_stats.speed.value().max(1);
+
+    let gain = if trick_room {
+        ACTION_COST * player_speed / speed
+    } else {
+        ACTION_COST * speed / player_speed
+    };
+    gain.clamp(MIN_ENERGY_GAIN, MAX_ENERGY)
+}
+
+/// Spend the energy of the actors, the one with the most energy acts first
+fn schedule_turn(energies: &mut [(Entity, i32)]) -> Vec<Entity> {
+    let mut schedule = Vec::new();
+
+    // `max_by_key` returns the last max element, reverse to keep the spawn order on ties
+    while let Some((entity, energy)) = energies
+        .iter_mut()
+        .rev()
+        .filter(|(_, energy)| *energy >= ACTION_COST)
+        .max_by_key(|(_, energy)| *energy)
+    {
+        *energy -= ACTION_COST;
+        schedule.push(*entity);
+    }
+
+    schedule
+}
+
+/// Give their energy to the actors and return the new energies, in turn order
+fn gain_energy(
+    turn_order: &TurnOrder,
+    query_actors: &Query<(Option<&Stats>, &Energy, Has<Player>)>,
+    trick_room: bool,
+) -> Vec<(Entity, i32)> {
+    let player_stats = query_actors
+        .iter()
+        .find_map(|(stats, _, is_player)| is_player.then_some(stats).flatten());
+
+    turn_order
+        .0
+        .iter()
+        .filter_map(|entity| {
+            let (stats, energy, is_player) = query_actors.get(*entity).ok()?;
+            let gain = if is_player {
+                ACTION_COST
+            } else {
+                energy_gain(stats, player_stats, trick_room)
+            };
+            Some((*entity, (energy.0 + gain).min(MAX_ENERGY)))
+        })
+        .collect()
+}
+
+#[allow(clippy::too_many_arguments)]
 pub fn turn_system(
-    turn_order: ResMut<TurnOrder>,
+    turn_order: Res<TurnOrder>,
+    mut query_actors: Query<(Option<&Stats>, &mut Energy, Has<Player>)>,
     query_player: Query<Entity, With<Player>>,
     query_next_actions: Query<&NextActions>,
+    query_status_conditions: Query<&StatusConditions>,
+    query_protected: Query<(), With<Protected>>,
+    map_statuses: Res<MapStatuses>,
     mut action_queue: ResMut<ActionQueue>,
     mut event_player_action: EventReader<PlayerActionEvent>,
 ) {
@@ -45,34 +139,86 @@ pub fn turn_system(
     info!("--------------- Turn ---------------");
     info!("------------------------------------");
 
-    for actor_turn in turn_order.0.iter() {
+    let trick_room = map_statuses.is_active(MapStatus::TrickRoom);
+    let mut energies = gain_energy(&turn_order, &query_actors.to_readonly(), trick_room);
+    let schedule = schedule_turn(&mut energies);
+
+    for (entity, energy) in energies {
+        if let Ok((_, mut actor_energy, _)) = query_actors.get_mut(entity) {
+            actor_energy.0 = energy;
+        }
+    }
+
+    for (index, actor_turn) in schedule.iter().enumerate() {
         let is_player = query_player.get(*actor_turn).is_ok();
 
-        if is_player {
-            let actions = player_action.0.clone();
+        let actions = if is_player {
+            player_action.0.clone()
+        } else if schedule[..index].contains(actor_turn) {
+            // The actions planned before the turn are stale once the npc already acted
+            vec![Box::new(PlanAction {
+                entity: *actor_turn,
+            }) as Box<dyn Action>]
+        } else {
+            let Ok(next_actions) = query_next_actions.get(*actor_turn) else {
+                warn!(
+                    "{:?} do not have a next action component during its turn",
+                    *actor_turn
+                );
+                continue;
+            };
+            next_actions.0.clone()
+        };
 
+        // The protection lasts until the next turn of the entity, so it covers the turns of the others
+        if query_protected.contains(*actor_turn) {
             action_queue.0.push_back(QueuedAction {
                 entity: *actor_turn,
-                performable_actions: actions,
+                performable_actions: vec![Box::new(ProtectionTickAction {
+                    entity: *actor_turn,
+                })],
             });
-            continue;
         }
 
-        let Ok(next_actions) = query_next_actions.get(*actor_turn) else {
-            warn!(
-                "{:?} do not have a next action component during its turn",
-                *actor_turn
-            );
+        let Ok(status_conditions) = query_status_conditions.get(*actor_turn) else {
+            action_queue.0.push_back(QueuedAction {
+                entity: *actor_turn,
+                performable_actions: actions,
+            });
             continue;
         };
-        let actions = next_actions.0.clone();
+
+        // The statuses are checked when the actions are executed, they can be given during the turn
         action_queue.0.push_back(QueuedAction {
             entity: *actor_turn,
-            performable_actions: actions,
+            performable_actions: vec![Box::new(StatusCheckAction {
+                entity: *actor_turn,
+                actions,
+            })],
         });
+
+        if !status_conditions.0.is_empty() {
+            action_queue.0.push_back(QueuedAction {
+                entity: *actor_turn,
+                performable_actions: vec![Box::new(StatusTickAction {
+                    entity: *actor_turn,
+                })],
+            });
+        }
     }
 }
 
+fn update_turn_order_preview(
+    turn_order: Res<TurnOrder>,
+    query_actors: Query<(Option<&Stats>, &Energy, Has<Player>)>,
+    map_statuses: Res<MapStatuses>,
+    mut turn_order_preview: ResMut<TurnOrderPreview>,
+) {
+    let trick_room = map_statuses.is_active(MapStatus::TrickRoom);
+    let mut energies = gain_energy(&turn_order, &query_actors, trick_room);
+    turn_order_preview.0 = schedule_turn(&mut energies);
+}
+
 fn handle_actor_death(
     mut actor_queue: ResMut<TurnOrder>,
     query_stats: Query<(Entity, &Health)>,
@@ -98,9 +244,103 @@ fn handle_actor_death(
     }
 }
 
-fn add_actor_to_queue(query: Query<Entity, Added<Actor>>, mut turn_order: ResMut<TurnOrder>) {
+fn add_actor_to_queue(
+    query: Query<Entity, Added<Actor>>,
+    mut turn_order: ResMut<TurnOrder>,
+    mut commands: Commands,
+) {
     for entity in query.iter() {
         info!("Add {:?} to turn order", entity);
         turn_order.0.push_back(entity);
+        commands.entity(entity).insert(Energy::default());
+    }
+}
+
+fn remove_despawned_actors(
+    mut removed_actors: RemovedComponents<Actor>,
+    mut turn_order: ResMut<TurnOrder>,
+) {
+    for entity in removed_actors.read() {
+        turn_order.0.retain(|e| *e != entity);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::stats::Stat;
+
+    use super::*;
+
+    fn stats(speed: i32) -> Stats {
+        Stats {
+            speed: Stat::new(speed),
+            ..default()
+        }
+    }
+
+    #[test]
+    fn keeps_the_spawn_order_on_ties() {
+        let (first, second) = (Entity::from_raw(1), Entity::from_raw(2));
+        let mut energies = [(first, ACTION_COST), (second, ACTION_COST)];
+
+        assert_eq!(schedule_turn(&mut energies), vec![first, second]);
+        assert_eq!(energies, [(first, 0), (second, 0)]);
+    }
+
+    #[test]
+    fn lets_the_fast_actors_act_twice() {
+        let (fast, slow) = (Entity::from_raw(1), Entity::from_raw(2));
+        let mut energies = [(slow, ACTION_COST), (fast, MAX_ENERGY)];
+
+        assert_eq!(schedule_turn(&mut energies), vec![fast, slow, fast]);
+    }
+
+    #[test]
+    fn waits_for_enough_energy() {
+        let entity = Entity::from_raw(1);
+        let mut energies = [(entity, ACTION_COST - 1)];
+
+        assert!(schedule_turn(&mut energies).is_empty());
+        assert_eq!(energies, [(entity, ACTION_COST - 1)]);
+    }
+
+    #[test]
+    fn caps_the_energy_gain() {
+        let player = stats(10);
+
+        assert_eq!(
+            energy_gain(Some(&stats(100)), Some(&player), false),
+            MAX_ENERGY
+        );
+        assert_eq!(energy_gain(Some(&stats(15)), Some(&player), false), 150);
+    }
+
+    #[test]
+    fn floors_the_energy_gain() {
+        let player = stats(100);
+
+        assert_eq!(
+            energy_gain(Some(&stats(1)), Some(&player), false),
+            MIN_ENERGY_GAIN
+        );
+    }
+
+    #[test]
+    fn favors_the_slow_actors_under_trick_room() {
+        let player = stats(20);
+
+        assert_eq!(
+            energy_gain(Some(&stats(10)), Some(&player), true),
+            MAX_ENERGY
+        );
+        assert_eq!(
+            energy_gain(Some(&stats(40)), Some(&player), true),
+            ACTION_COST / 2
+        );
+    }
+
+    #[test]
+    fn acts_once_per_turn_without_stats() {
+        assert_eq!(energy_gain(None, Some(&stats(10)), false), ACTION_COST);
     }
 }
@@ -0,0 +1,67 @@
+use bevy::prelude::*;
+use bevy_egui::{egui, EguiContexts};
+
+use crate::graphics::assets::font_assets::FontAssets;
+use crate::graphics::assets::ui_assets::UIAssets;
+use crate::graphics::ui::{BorderedFrame, UISpriteText};
+use crate::inventory::{BagMenu, Inventory, BAG_CAPACITY};
+use crate::player::Player;
+
+const SELECTED_ITEM_COLOR: egui::Color32 = egui::Color32::from_rgb(255, 200, 0);
+
+/// Content of the bag, the selected item is highlighted
+pub(crate) fn bag_ui(
+    mut ctx: EguiContexts,
+    bag_menu: Res<BagMenu>,
+    query: Query<&Inventory, With<Player>>,
+    font_assets: Res<FontAssets>,
+    ui_assets: Res<UIAssets>,
+) {
+    if !bag_menu.is_open {
+        return;
+    }
+    let Ok(inventory) = query.get_single() else {
+        return;
+    };
+    let ctx = ctx.ctx_mut();
+
+    egui::Area::new("bag")
+        .anchor(egui::Align2::RIGHT_TOP, egui::vec2(-8., 40.))
+        .show(ctx, |ui| {
+            BorderedFrame::new(&ui_assets.panel_green)
+                .background(&ui_assets.dark_panel_bg)
+                .padding(UiRect::axes(Val::Px(12.), Val::Px(10.)))
+                .show(ui, |ui| {
+                    ui.spacing_mut().item_spacing.y = 2.;
+
+                    UISpriteText::from_section(
+                        format!("Bag {}/{}", inventory.0.len(), BAG_CAPACITY),
+                        &font_assets.text,
+                    )
+                    .show(ui);
+
+                    if inventory.0.is_empty() {
+                        UISpriteText::from_section("Empty", &font_assets.text).show(ui);
+                    }
+                    for (slot, item) in inventory.0.iter().enumerate() {
+                        if slot == bag_menu.selected {
+                            UISpriteText::from_section_colored(
+                                format!("> {}", item.name),
+                                &font_assets.text,
+                                SELECTED_ITEM_COLOR,
+                            )
+                            .show(ui);
+                        } else {
+                            UISpriteText::from_section(
+                                format!("  {}", item.name),
+                                &font_assets.text,
+                            )
+                            .show(ui);
+                        }
+                    }
+
+                    UISpriteText::from_section("E: Use  T: Throw  X: Drop", &font_assets.text)
+                        .show(ui);
+                });
+        });
+}
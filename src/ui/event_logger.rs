@@ -1,18 +1,46 @@
 use bevy_inspector_egui::prelude::*;
+use common::element::Effectiveness;
+use common::map_status::MapStatus;
 use egui::Color32;
 use std::collections::VecDeque;
 
 use bevy::prelude::*;
 use bevy_egui::{egui, EguiContexts};
 
+use crate::actions::apply_status_action::ApplyStatusAction;
+use crate::actions::cure_status_action::CureStatusAction;
 use crate::actions::damage_action::DamageAction;
 use crate::actions::death_action::DeathAction;
+use crate::actions::drop_item_action::DropItemAction;
+use crate::actions::gain_exp_action::GainExpAction;
+use crate::actions::heal_action::HealAction;
+use crate::actions::indirect_damage_action::{IndirectDamageAction, IndirectDamageCause};
+use crate::actions::level_up_action::LevelUpAction;
+use crate::actions::miss_action::MissAction;
+use crate::actions::pick_up_item_action::PickUpItemAction;
+use crate::actions::protect_action::ProtectAction;
+use crate::actions::protected_action::ProtectedAction;
+use crate::actions::restore_belly_action::RestoreBellyAction;
+use crate::actions::restore_pp_action::RestorePPAction;
 use crate::actions::spell_action::SpellAction;
+use crate::actions::status_blocked_action::StatusBlockedAction;
+use crate::actions::substitute_hit_action::SubstituteHitAction;
+use crate::actions::throw_item_action::ThrowItemAction;
+use crate::actions::use_item_action::UseItemAction;
 use crate::actions::walk_action::WalkAction;
 use crate::actions::ActionExecutedEvent;
+use crate::belly::HungerWarningEvent;
+use crate::evolution::PokemonEvolvedEvent;
 use crate::graphics::assets::font_assets::FontAssets;
 use crate::graphics::assets::ui_assets::UIAssets;
 use crate::graphics::ui::{BorderedFrame, UISpriteText, UISpriteTextSection};
+use crate::inventory::BagFullEvent;
+use crate::map::{EnvironmentType, FloorChangedEvent};
+use crate::map_status::MapStatusChangedEvent;
+use crate::move_set::MoveOutOfPPEvent;
+use crate::protection::{Protection, Substitute};
+use crate::stats::StatStageChangedEvent;
+use crate::status::StatusCondition;
 
 const SCROLL_SPEED: f32 = 15.;
 
@@ -29,6 +57,8 @@ pub enum EventLogColor {
     Foe,        // #00ffff
     Spell,
     Damage,
+    Heal,
+    Item,
     #[default]
     None,
 }
@@ -42,6 +72,8 @@ impl EventLogColor {
             EventLogColor::Foe => Color32::from_rgb(0, 255, 255),
             EventLogColor::Spell => Color32::from_rgb(255, 0, 0),
             EventLogColor::Damage => Color32::from_rgb(0, 255, 255),
+            EventLogColor::Heal => Color32::from_rgb(0, 255, 0),
+            EventLogColor::Item => Color32::from_rgb(255, 200, 0),
             EventLogColor::None => Color32::WHITE,
         }
     }
@@ -61,11 +93,155 @@ impl EventLogLineSection {
 #[derive(Default, InspectorOptions)]
 pub struct EventLogLine(Vec<EventLogLineSection>);
 
+fn status_applied_text(condition: StatusCondition) -> &'static str {
+    match condition {
+        StatusCondition::Poison => " was poisoned!",
+        StatusCondition::Burn => " was burned!",
+        StatusCondition::Sleep => " fell asleep!",
+        StatusCondition::Paralysis => " is paralyzed! It may be unable to move!",
+        StatusCondition::Confusion => " became confused!",
+    }
+}
+
+fn status_cured_text(condition: StatusCondition) -> &'static str {
+    match condition {
+        StatusCondition::Poison => " is no longer poisoned.",
+        StatusCondition::Burn => "'s burn was healed.",
+        StatusCondition::Sleep => " woke up!",
+        StatusCondition::Paralysis => " is no longer paralyzed.",
+        StatusCondition::Confusion => " snapped out of its confusion!",
+    }
+}
+
+/// Text of a map status starting or ending, `None` for the statuses without a message
+fn map_status_text(status: MapStatus, started: bool) -> Option<&'static str> {
+    let text = match (status, started) {
+        (MapStatus::Rain, true) => "It started to rain!",
+        (MapStatus::Rain, false) => "The rain stopped.",
+        (MapStatus::Sunny, true) => "The sunlight turned harsh!",
+        (MapStatus::Sunny, false) => "The harsh sunlight faded.",
+        (MapStatus::Sandstorm, true) => "A sandstorm kicked up!",
+        (MapStatus::Sandstorm, false) => "The sandstorm subsided.",
+        (MapStatus::Hail, true) => "It started to hail!",
+        (MapStatus::Hail, false) => "The hail stopped.",
+        (MapStatus::Fog, true) => "The fog is deep...",
+        (MapStatus::Fog, false) => "The fog cleared.",
+        (MapStatus::TrickRoom, true) => "The dimensions were twisted!",
+        (MapStatus::TrickRoom, false) => "The twisted dimensions returned to normal.",
+        _ => return None,
+    };
+    Some(text)
+}
+
+fn status_blocked_text(condition: StatusCondition) -> &'static str {
+    match condition {
+        StatusCondition::Sleep => " is fast asleep.",
+        StatusCondition::Paralysis => " is paralyzed! It can't move!",
+        StatusCondition::Confusion => " is confused!",
+        StatusCondition::Poison | StatusCondition::Burn => " can't move!",
+    }
+}
+
+fn stat_stage_text(stages: i32, changed: i32) -> &'static str {
+    match changed {
+        0 if stages > 0 => "won't go any higher!",
+        0 => "won't go any lower!",
+        1 => "rose!",
+        2 => "rose sharply!",
+        3.. => "rose drastically!",
+        -1 => "fell!",
+        -2 => "fell sharply!",
+        _ => "fell drastically!",
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
 pub(crate) fn gather_logs(
     mut ev_action_executed: EventReader<ActionExecutedEvent>,
+    mut ev_floor_changed: EventReader<FloorChangedEvent>,
+    mut ev_evolved: EventReader<PokemonEvolvedEvent>,
+    mut ev_map_status_changed: EventReader<MapStatusChangedEvent>,
+    mut ev_hunger_warning: EventReader<HungerWarningEvent>,
+    mut ev_move_out_of_pp: EventReader<MoveOutOfPPEvent>,
+    mut ev_stat_stage_changed: EventReader<StatStageChangedEvent>,
+    mut ev_bag_full: EventReader<BagFullEvent>,
     name_query: Query<&Name>,
+    substitute_query: Query<&Substitute>,
     mut event_logs: ResMut<EventLogs>,
 ) {
+    for floor_changed in ev_floor_changed.read() {
+        let log_line_sections = vec![
+            EventLogLineSection::new("Welcome to ".to_string(), EventLogColor::None),
+            EventLogLineSection::new(format!("B{}F", floor_changed.floor), EventLogColor::Spell),
+            EventLogLineSection::new("!".to_string(), EventLogColor::None),
+        ];
+        event_logs.logs.push_back(EventLogLine(log_line_sections));
+    }
+
+    for map_status_changed in ev_map_status_changed.read() {
+        let Some(text) = map_status_text(map_status_changed.status, map_status_changed.started)
+        else {
+            continue;
+        };
+        let log_line_sections = vec![EventLogLineSection::new(
+            text.to_string(),
+            EventLogColor::None,
+        )];
+        event_logs.logs.push_back(EventLogLine(log_line_sections));
+    }
+
+    for hunger_warning in ev_hunger_warning.read() {
+        let Ok(name) = name_query.get(hunger_warning.entity) else {
+            continue;
+        };
+        let text = match hunger_warning.threshold {
+            0 => " is starving! Eat something, quick!",
+            1..=10 => " is getting dizzy from hunger...",
+            _ => " is getting hungry...",
+        };
+        let log_line_sections = vec![
+            EventLogLineSection::new(name.to_string(), EventLogColor::TeamLeader),
+            EventLogLineSection::new(text.to_string(), EventLogColor::None),
+        ];
+        event_logs.logs.push_back(EventLogLine(log_line_sections));
+    }
+
+    for move_out_of_pp in ev_move_out_of_pp.read() {
+        let log_line_sections = vec![
+            EventLogLineSection::new(move_out_of_pp.name.clone(), EventLogColor::Spell),
+            EventLogLineSection::new(" has no PP left!".to_string(), EventLogColor::None),
+        ];
+        event_logs.logs.push_back(EventLogLine(log_line_sections));
+    }
+
+    for stat_stage_changed in ev_stat_stage_changed.read() {
+        let Ok(name) = name_query.get(stat_stage_changed.entity) else {
+            continue;
+        };
+        let text = stat_stage_text(stat_stage_changed.stages, stat_stage_changed.changed);
+        let log_line_sections = vec![
+            EventLogLineSection::new(name.to_string(), EventLogColor::TeamLeader),
+            EventLogLineSection::new(
+                format!("'s {} {}", stat_stage_changed.stat.name(), text),
+                EventLogColor::None,
+            ),
+        ];
+        event_logs.logs.push_back(EventLogLine(log_line_sections));
+    }
+
+    for bag_full in ev_bag_full.read() {
+        let Ok(name) = name_query.get(bag_full.entity) else {
+            continue;
+        };
+        let log_line_sections = vec![
+            EventLogLineSection::new(name.to_string(), EventLogColor::TeamLeader),
+            EventLogLineSection::new("'s bag is full! The ".to_string(), EventLogColor::None),
+            EventLogLineSection::new(bag_full.item.name.clone(), EventLogColor::Item),
+            EventLogLineSection::new(" was left behind.".to_string(), EventLogColor::None),
+        ];
+        event_logs.logs.push_back(EventLogLine(log_line_sections));
+    }
+
     for action_executed in ev_action_executed.read() {
         info!("Gather logs -> {:?}", action_executed.action);
         let action = action_executed.action.as_any();
@@ -83,6 +259,10 @@ pub(crate) fn gather_logs(
             continue;
         };
         if let Some(spell_action) = action.downcast_ref::<SpellAction>() {
+            // The following strikes of the spell are not logged
+            if spell_action.strike > 0 {
+                continue;
+            }
             let log_line_sections = vec![
                 EventLogLineSection::new(entity_name.to_string(), EventLogColor::TeamLeader),
                 EventLogLineSection::new(" used ".to_string(), EventLogColor::None),
@@ -94,6 +274,37 @@ pub(crate) fn gather_logs(
         if let Some(damage_action) = action.downcast_ref::<DamageAction>() {
             let entity_name = name_query.get(damage_action.target).unwrap().as_str();
 
+            if damage_action.critical && damage_action.effectiveness != Effectiveness::Immune {
+                let log_line_sections = vec![EventLogLineSection::new(
+                    "A critical hit!".to_string(),
+                    EventLogColor::Damage,
+                )];
+                event_logs.logs.push_back(EventLogLine(log_line_sections));
+            }
+
+            let effectiveness_line = match damage_action.effectiveness {
+                Effectiveness::SuperEffective => Some(vec![EventLogLineSection::new(
+                    "It's super effective!".to_string(),
+                    EventLogColor::None,
+                )]),
+                Effectiveness::NotVeryEffective => Some(vec![EventLogLineSection::new(
+                    "It's not very effective...".to_string(),
+                    EventLogColor::None,
+                )]),
+                Effectiveness::Immune => Some(vec![
+                    EventLogLineSection::new("It doesn't affect ".to_string(), EventLogColor::None),
+                    EventLogLineSection::new(entity_name.to_string(), EventLogColor::Foe),
+                    EventLogLineSection::new("...".to_string(), EventLogColor::None),
+                ]),
+                Effectiveness::Neutral => None,
+            };
+            if let Some(log_line_sections) = effectiveness_line {
+                event_logs.logs.push_back(EventLogLine(log_line_sections));
+            }
+            if damage_action.effectiveness == Effectiveness::Immune {
+                continue;
+            }
+
             let log_line_sections = vec![
                 EventLogLineSection::new(entity_name.to_string(), EventLogColor::Foe),
                 EventLogLineSection::new(" took ".to_string(), EventLogColor::None),
@@ -104,6 +315,75 @@ pub(crate) fn gather_logs(
             continue;
         }
 
+        if let Some(miss_action) = action.downcast_ref::<MissAction>() {
+            let attacker_name = name_query
+                .get(miss_action.attacker)
+                .map_or(entity_name, |name| name.as_str());
+            let target_name = name_query
+                .get(miss_action.target)
+                .map_or("", |name| name.as_str());
+            let log_line_sections = vec![
+                EventLogLineSection::new(attacker_name.to_string(), EventLogColor::TeamLeader),
+                EventLogLineSection::new(" missed ".to_string(), EventLogColor::None),
+                EventLogLineSection::new(target_name.to_string(), EventLogColor::Foe),
+                EventLogLineSection::new("!".to_string(), EventLogColor::None),
+            ];
+            event_logs.logs.push_back(EventLogLine(log_line_sections));
+            continue;
+        }
+
+        if let Some(protect_action) = action.downcast_ref::<ProtectAction>() {
+            let text = match protect_action.protection {
+                Protection::Protect => " protected itself!",
+                Protection::Substitute => " made a substitute!",
+            };
+            let log_line_sections = vec![
+                EventLogLineSection::new(entity_name.to_string(), EventLogColor::TeamLeader),
+                EventLogLineSection::new(text.to_string(), EventLogColor::None),
+            ];
+            event_logs.logs.push_back(EventLogLine(log_line_sections));
+            continue;
+        }
+
+        if let Some(protected_action) = action.downcast_ref::<ProtectedAction>() {
+            let target_name = name_query
+                .get(protected_action.target)
+                .map_or("", |name| name.as_str());
+            let log_line_sections = vec![
+                EventLogLineSection::new(target_name.to_string(), EventLogColor::Foe),
+                EventLogLineSection::new(" protected itself!".to_string(), EventLogColor::None),
+            ];
+            event_logs.logs.push_back(EventLogLine(log_line_sections));
+            continue;
+        }
+
+        if let Some(substitute_hit_action) = action.downcast_ref::<SubstituteHitAction>() {
+            let target_name = name_query
+                .get(substitute_hit_action.target)
+                .map_or("", |name| name.as_str());
+            let log_line_sections = vec![
+                EventLogLineSection::new(
+                    "The substitute took the damage for ".to_string(),
+                    EventLogColor::None,
+                ),
+                EventLogLineSection::new(target_name.to_string(), EventLogColor::Foe),
+                EventLogLineSection::new("!".to_string(), EventLogColor::None),
+            ];
+            event_logs.logs.push_back(EventLogLine(log_line_sections));
+
+            if substitute_query.get(substitute_hit_action.target).is_err() {
+                let log_line_sections = vec![
+                    EventLogLineSection::new(target_name.to_string(), EventLogColor::Foe),
+                    EventLogLineSection::new(
+                        "'s substitute faded!".to_string(),
+                        EventLogColor::None,
+                    ),
+                ];
+                event_logs.logs.push_back(EventLogLine(log_line_sections));
+            }
+            continue;
+        }
+
         if let Some(death_action) = action.downcast_ref::<DeathAction>() {
             let entity_name = name_query.get(death_action.target).unwrap().as_str();
 
@@ -114,6 +394,204 @@ pub(crate) fn gather_logs(
             event_logs.logs.push_back(EventLogLine(log_line_sections));
             continue;
         }
+
+        if let Some(gain_exp_action) = action.downcast_ref::<GainExpAction>() {
+            let log_line_sections = vec![
+                EventLogLineSection::new(entity_name.to_string(), EventLogColor::TeamLeader),
+                EventLogLineSection::new(" gained ".to_string(), EventLogColor::None),
+                EventLogLineSection::new(gain_exp_action.value.to_string(), EventLogColor::Damage),
+                EventLogLineSection::new(" Exp. Points!".to_string(), EventLogColor::None),
+            ];
+            event_logs.logs.push_back(EventLogLine(log_line_sections));
+            continue;
+        }
+
+        if let Some(level_up_action) = action.downcast_ref::<LevelUpAction>() {
+            let log_line_sections = vec![
+                EventLogLineSection::new(entity_name.to_string(), EventLogColor::TeamLeader),
+                EventLogLineSection::new(" grew to level ".to_string(), EventLogColor::None),
+                EventLogLineSection::new(level_up_action.level.to_string(), EventLogColor::Damage),
+                EventLogLineSection::new("!".to_string(), EventLogColor::None),
+            ];
+            event_logs.logs.push_back(EventLogLine(log_line_sections));
+            continue;
+        }
+
+        if let Some(apply_status_action) = action.downcast_ref::<ApplyStatusAction>() {
+            let entity_name = name_query.get(apply_status_action.target).unwrap().as_str();
+
+            let log_line_sections = vec![
+                EventLogLineSection::new(entity_name.to_string(), EventLogColor::Foe),
+                EventLogLineSection::new(
+                    status_applied_text(apply_status_action.condition).to_string(),
+                    EventLogColor::None,
+                ),
+            ];
+            event_logs.logs.push_back(EventLogLine(log_line_sections));
+            continue;
+        }
+
+        if let Some(cure_status_action) = action.downcast_ref::<CureStatusAction>() {
+            let log_line_sections = vec![
+                EventLogLineSection::new(entity_name.to_string(), EventLogColor::TeamLeader),
+                EventLogLineSection::new(
+                    status_cured_text(cure_status_action.condition).to_string(),
+                    EventLogColor::None,
+                ),
+            ];
+            event_logs.logs.push_back(EventLogLine(log_line_sections));
+            continue;
+        }
+
+        if let Some(status_blocked_action) = action.downcast_ref::<StatusBlockedAction>() {
+            let log_line_sections = vec![
+                EventLogLineSection::new(entity_name.to_string(), EventLogColor::TeamLeader),
+                EventLogLineSection::new(
+                    status_blocked_text(status_blocked_action.condition).to_string(),
+                    EventLogColor::None,
+                ),
+            ];
+            event_logs.logs.push_back(EventLogLine(log_line_sections));
+            continue;
+        }
+
+        if let Some(indirect_damage_action) = action.downcast_ref::<IndirectDamageAction>() {
+            let mut log_line_sections = vec![EventLogLineSection::new(
+                entity_name.to_string(),
+                EventLogColor::TeamLeader,
+            )];
+            // The statuses and the hunger tell the damage, the weather and the terrain only the cause
+            let took_damage = |cause: &str| {
+                vec![
+                    EventLogLineSection::new(" took ".to_string(), EventLogColor::None),
+                    EventLogLineSection::new(
+                        indirect_damage_action.value.to_string(),
+                        EventLogColor::Damage,
+                    ),
+                    EventLogLineSection::new(cause.to_string(), EventLogColor::None),
+                ]
+            };
+            let cause_sections = match indirect_damage_action.cause {
+                IndirectDamageCause::Status {
+                    condition: StatusCondition::Burn,
+                    ..
+                } => took_damage(" damage from its burn!"),
+                IndirectDamageCause::Status { .. } => took_damage(" damage from poison!"),
+                IndirectDamageCause::Hunger => took_damage(" damage from hunger!"),
+                IndirectDamageCause::Weather(weather) => {
+                    let cause = match weather {
+                        MapStatus::Hail => " is pelted by the hail!",
+                        _ => " is buffeted by the sandstorm!",
+                    };
+                    vec![EventLogLineSection::new(
+                        cause.to_string(),
+                        EventLogColor::None,
+                    )]
+                }
+                IndirectDamageCause::Terrain(environment) => {
+                    let cause = match environment {
+                        EnvironmentType::Lava => " is burned by the lava!",
+                        _ => " is hurt by the terrain!",
+                    };
+                    vec![EventLogLineSection::new(
+                        cause.to_string(),
+                        EventLogColor::None,
+                    )]
+                }
+            };
+            log_line_sections.extend(cause_sections);
+            event_logs.logs.push_back(EventLogLine(log_line_sections));
+            continue;
+        }
+
+        if let Some(pick_up_item_action) = action.downcast_ref::<PickUpItemAction>() {
+            let log_line_sections = vec![
+                EventLogLineSection::new(entity_name.to_string(), EventLogColor::TeamLeader),
+                EventLogLineSection::new(" picked up the ".to_string(), EventLogColor::None),
+                EventLogLineSection::new(
+                    pick_up_item_action.item.name.clone(),
+                    EventLogColor::Item,
+                ),
+                EventLogLineSection::new(".".to_string(), EventLogColor::None),
+            ];
+            event_logs.logs.push_back(EventLogLine(log_line_sections));
+            continue;
+        }
+
+        if let Some(use_item_action) = action.downcast_ref::<UseItemAction>() {
+            let log_line_sections = vec![
+                EventLogLineSection::new(entity_name.to_string(), EventLogColor::TeamLeader),
+                EventLogLineSection::new(" ate the ".to_string(), EventLogColor::None),
+                EventLogLineSection::new(use_item_action.item.name.clone(), EventLogColor::Item),
+                EventLogLineSection::new("!".to_string(), EventLogColor::None),
+            ];
+            event_logs.logs.push_back(EventLogLine(log_line_sections));
+            continue;
+        }
+
+        if let Some(throw_item_action) = action.downcast_ref::<ThrowItemAction>() {
+            let log_line_sections = vec![
+                EventLogLineSection::new(entity_name.to_string(), EventLogColor::TeamLeader),
+                EventLogLineSection::new(" threw the ".to_string(), EventLogColor::None),
+                EventLogLineSection::new(throw_item_action.item.name.clone(), EventLogColor::Item),
+                EventLogLineSection::new("!".to_string(), EventLogColor::None),
+            ];
+            event_logs.logs.push_back(EventLogLine(log_line_sections));
+            continue;
+        }
+
+        if let Some(drop_item_action) = action.downcast_ref::<DropItemAction>() {
+            let log_line_sections = vec![
+                EventLogLineSection::new(entity_name.to_string(), EventLogColor::TeamLeader),
+                EventLogLineSection::new(" dropped the ".to_string(), EventLogColor::None),
+                EventLogLineSection::new(drop_item_action.item.name.clone(), EventLogColor::Item),
+                EventLogLineSection::new(".".to_string(), EventLogColor::None),
+            ];
+            event_logs.logs.push_back(EventLogLine(log_line_sections));
+            continue;
+        }
+
+        if let Some(heal_action) = action.downcast_ref::<HealAction>() {
+            let log_line_sections = vec![
+                EventLogLineSection::new(entity_name.to_string(), EventLogColor::TeamLeader),
+                EventLogLineSection::new(" recovered ".to_string(), EventLogColor::None),
+                EventLogLineSection::new(heal_action.value.to_string(), EventLogColor::Heal),
+                EventLogLineSection::new(" HP!".to_string(), EventLogColor::None),
+            ];
+            event_logs.logs.push_back(EventLogLine(log_line_sections));
+            continue;
+        }
+
+        if action.is::<RestoreBellyAction>() {
+            let log_line_sections = vec![
+                EventLogLineSection::new(entity_name.to_string(), EventLogColor::TeamLeader),
+                EventLogLineSection::new("'s belly filled up!".to_string(), EventLogColor::None),
+            ];
+            event_logs.logs.push_back(EventLogLine(log_line_sections));
+            continue;
+        }
+
+        if action.is::<RestorePPAction>() {
+            let log_line_sections = vec![
+                EventLogLineSection::new(entity_name.to_string(), EventLogColor::TeamLeader),
+                EventLogLineSection::new("'s moves regained PP!".to_string(), EventLogColor::None),
+            ];
+            event_logs.logs.push_back(EventLogLine(log_line_sections));
+            continue;
+        }
+    }
+
+    for evolved in ev_evolved.read() {
+        let Ok(entity_name) = name_query.get(evolved.entity) else {
+            continue;
+        };
+        let log_line_sections = vec![
+            EventLogLineSection::new(entity_name.to_string(), EventLogColor::TeamLeader),
+            EventLogLineSection::new(" evolved into ".to_string(), EventLogColor::None),
+            EventLogLineSection::new(evolved.species.clone(), EventLogColor::Spell),
+            EventLogLineSection::new("!".to_string(), EventLogColor::None),
+        ];
+        event_logs.logs.push_back(EventLogLine(log_line_sections));
     }
 }
 
@@ -0,0 +1,43 @@
+use bevy::prelude::*;
+use bevy_egui::{egui, EguiContexts};
+
+use crate::belly::{Belly, HUNGER_THRESHOLDS};
+use crate::graphics::assets::font_assets::FontAssets;
+use crate::graphics::ui::UISpriteText;
+use crate::map::Dungeon;
+use crate::player::Player;
+
+use super::WARNING_COLOR;
+
+pub(crate) fn hud_ui(
+    mut ctx: EguiContexts,
+    dungeon: Res<Dungeon>,
+    font_assets: Res<FontAssets>,
+    query_belly: Query<&Belly, With<Player>>,
+) {
+    let ctx = ctx.ctx_mut();
+
+    egui::TopBottomPanel::top("top")
+        .frame(egui::Frame::none().inner_margin(egui::Margin::symmetric(12., 8.)))
+        .show_separator_line(false)
+        .show(ctx, |ui| {
+            ui.horizontal(|ui| {
+                // Floor number, PMD style (B1F, B2F...)
+                UISpriteText::from_section(format!("B{}F", dungeon.floor), &font_assets.text)
+                    .show(ui);
+
+                let Ok(belly) = query_belly.get_single() else {
+                    return;
+                };
+                ui.add_space(16.);
+                let text = format!("Belly {}/{}", belly.value, belly.max);
+                // The meter turns red from the first hunger warning
+                if belly.value <= HUNGER_THRESHOLDS[0] {
+                    UISpriteText::from_section_colored(text, &font_assets.text, WARNING_COLOR)
+                        .show(ui);
+                } else {
+                    UISpriteText::from_section(text, &font_assets.text).show(ui);
+                }
+            });
+        });
+}
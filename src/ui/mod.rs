@@ -1,10 +1,20 @@
+mod bag;
 mod event_logger;
+mod hud;
+mod moves;
 
 use bevy::prelude::*;
+use bevy_egui::egui;
 
 use crate::GameState;
 
+use self::bag::bag_ui;
 use self::event_logger::{event_logger_ui, gather_logs, EventLogs};
+use self::hud::hud_ui;
+use self::moves::moves_ui;
+
+// Highlights the values needing attention, ie: a low belly or a move out of PP
+const WARNING_COLOR: egui::Color32 = egui::Color32::from_rgb(255, 80, 80);
 
 pub struct UIPlugin;
 
@@ -12,7 +22,8 @@ impl Plugin for UIPlugin {
     fn build(&self, app: &mut App) {
         app.init_resource::<EventLogs>().add_systems(
             Update,
-            (gather_logs, event_logger_ui).run_if(in_state(GameState::Playing)),
+            (gather_logs, event_logger_ui, hud_ui, bag_ui, moves_ui)
+                .run_if(in_state(GameState::Playing)),
         );
 
         #[cfg(debug_assertions)]
@@ -0,0 +1,65 @@
+use bevy::prelude::*;
+use bevy_egui::{egui, EguiContexts};
+use spell_data::SpellData;
+
+use crate::data::assets::spell_data::SpellDataLookup;
+use crate::graphics::assets::font_assets::FontAssets;
+use crate::graphics::assets::ui_assets::UIAssets;
+use crate::graphics::ui::{BorderedFrame, UISpriteText};
+use crate::move_set::MoveSet;
+use crate::player::Player;
+
+use super::WARNING_COLOR;
+
+/// Moves of the player with their remaining PP, next to the key using them
+pub(crate) fn moves_ui(
+    mut ctx: EguiContexts,
+    query: Query<&MoveSet, With<Player>>,
+    spell_data_lookup: Res<SpellDataLookup>,
+    spell_data_assets: Res<Assets<SpellData>>,
+    font_assets: Res<FontAssets>,
+    ui_assets: Res<UIAssets>,
+) {
+    let Ok(move_set) = query.get_single() else {
+        return;
+    };
+    let ctx = ctx.ctx_mut();
+
+    egui::Area::new("moves")
+        .anchor(egui::Align2::LEFT_TOP, egui::vec2(8., 40.))
+        .show(ctx, |ui| {
+            BorderedFrame::new(&ui_assets.panel_green)
+                .background(&ui_assets.dark_panel_bg)
+                .padding(UiRect::axes(Val::Px(12.), Val::Px(10.)))
+                .show(ui, |ui| {
+                    ui.spacing_mut().item_spacing.y = 2.;
+
+                    for (slot, m) in move_set.0.iter().enumerate() {
+                        let Some(m) = m else {
+                            UISpriteText::from_section(
+                                format!("{}: -", slot + 1),
+                                &font_assets.text,
+                            )
+                            .show(ui);
+                            continue;
+                        };
+                        let name = spell_data_lookup
+                            .0
+                            .get(&m.id)
+                            .and_then(|handle| spell_data_assets.get(handle))
+                            .map_or(m.id.as_str(), |spell| spell.name.default_text.as_str());
+                        let text = format!("{}: {} {}/{}", slot + 1, name, m.pp, m.max_pp);
+                        if m.pp == 0 {
+                            UISpriteText::from_section_colored(
+                                text,
+                                &font_assets.text,
+                                WARNING_COLOR,
+                            )
+                            .show(ui);
+                        } else {
+                            UISpriteText::from_section(text, &font_assets.text).show(ui);
+                        }
+                    }
+                });
+        });
+}